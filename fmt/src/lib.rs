@@ -0,0 +1,209 @@
+//! Formats the code inside invocations of the for-else macros.
+//!
+//! rustfmt leaves the contents of macro invocations alone, so the bodies of `for_!` loops and
+//! their `else` blocks are never formatted. This crate finds the blocks of every invocation of
+//! a for-else macro and formats each one with rustfmt as if it were the body of a function,
+//! leaving the loop headers as they are written.
+//!
+//! Blocks that rustfmt cannot parse, such as the bodies of collecting loops with their `emit`
+//! statements, are left unchanged.
+
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+
+use proc_macro2::{Delimiter, LineColumn, TokenStream, TokenTree};
+
+/// The macros whose invocations are formatted.
+pub const MACROS: &[&str] = &[
+    "bisect_loop_",
+    "cfor_",
+    "chain_for_",
+    "do_while_",
+    "eventually_",
+    "for_",
+    "for_await_",
+    "for_stdin_",
+    "forelse",
+    "fs_search_",
+    "loop_",
+    "loop_else",
+    "machine_loop_",
+    "merge_for_",
+    "paginate_for_",
+    "par_for_",
+    "read_chunks_",
+    "regex_search_",
+    "retry_",
+    "rows_for_",
+    "search_",
+    "spawn_for_",
+    "terminal_for_",
+    "try_for_",
+    "watch_for_",
+    "while_let_",
+    "worklist_for_",
+];
+
+/// Formats the blocks of the for-else macro invocations in `source`, the contents of a Rust
+/// source file written for the given edition, and returns the formatted source.
+///
+/// Fails when `source` cannot be tokenized or rustfmt cannot be run. The rustfmt binary is
+/// taken from the `RUSTFMT` environment variable, or else from the `PATH`.
+pub fn format_source(source: &str, edition: &str) -> io::Result<String> {
+    let tokens: TokenStream = source
+        .parse()
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, format!("{:?}", err)))?;
+    let lines = Lines::new(source);
+    let mut blocks = Vec::new();
+    find_blocks(tokens, &lines, &mut blocks);
+
+    // blocks are found in source order and never overlap, so replacing them from the end keeps
+    // the offsets of the others valid
+    let mut formatted = source.to_string();
+    for (start, end) in blocks.into_iter().rev() {
+        let indent = lines.indent_at(start);
+        if let Some(block) = format_block(&source[start + 1..end - 1], indent, edition)? {
+            formatted.replace_range(start..end, &block);
+        }
+    }
+    Ok(formatted)
+}
+
+/// Collects the byte ranges of the blocks of the for-else macro invocations in `tokens`,
+/// braces included. Invocations nested in those blocks are handled when the block is formatted.
+fn find_blocks(tokens: TokenStream, lines: &Lines, blocks: &mut Vec<(usize, usize)>) {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    let mut i = 0;
+    while i < tokens.len() {
+        match &tokens[i..] {
+            [TokenTree::Ident(name), TokenTree::Punct(bang), TokenTree::Group(input), ..]
+                if bang.as_char() == '!'
+                    && input.delimiter() == Delimiter::Brace
+                    && MACROS.contains(&name.to_string().as_str()) =>
+            {
+                // the braces of a header expression that starts with `match`, `loop` or
+                // `unsafe` belong to the expression, not to the loop
+                let mut in_expression = false;
+                for token in input.stream() {
+                    match token {
+                        TokenTree::Ident(keyword)
+                            if ["match", "loop", "unsafe"]
+                                .contains(&keyword.to_string().as_str()) =>
+                        {
+                            in_expression = true;
+                        }
+                        TokenTree::Group(block) if block.delimiter() == Delimiter::Brace => {
+                            if in_expression {
+                                in_expression = false;
+                            } else {
+                                let span = block.span();
+                                blocks.push((lines.offset(span.start()), lines.offset(span.end())));
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                i += 3;
+            }
+            [TokenTree::Group(group), ..] => {
+                find_blocks(group.stream(), lines, blocks);
+                i += 1;
+            }
+            _ => i += 1,
+        }
+    }
+}
+
+/// Formats the contents of a block whose opening brace is on a line indented by `indent`,
+/// returning the whole block, or `None` when rustfmt cannot format it.
+fn format_block(contents: &str, indent: &str, edition: &str) -> io::Result<Option<String>> {
+    if contents.trim().is_empty() {
+        return Ok(Some("{}".to_string()));
+    }
+    let Some(formatted) = rustfmt(&format!("fn main() {{\n{}\n}}\n", contents), edition)? else {
+        return Ok(None);
+    };
+    let Some(body) = formatted
+        .strip_prefix("fn main() {\n")
+        .and_then(|formatted| formatted.strip_suffix("}\n"))
+    else {
+        return Ok(None);
+    };
+
+    // a line rustfmt did not indent continues a multi-line string literal, whose contents
+    // would change by indenting it
+    let mut dedented = String::new();
+    for line in body.lines() {
+        match line.strip_prefix("    ") {
+            Some(line) => dedented.push_str(line),
+            None if line.is_empty() => {}
+            None => return Ok(None),
+        }
+        dedented.push('\n');
+    }
+    let dedented = format_source(&dedented, edition)?;
+
+    let mut block = String::from("{\n");
+    for line in dedented.lines() {
+        if !line.is_empty() {
+            block.push_str(indent);
+            block.push_str("    ");
+            block.push_str(line);
+        }
+        block.push('\n');
+    }
+    block.push_str(indent);
+    block.push('}');
+    Ok(Some(block))
+}
+
+/// Runs rustfmt on `source`, returning `None` when it fails to format it.
+fn rustfmt(source: &str, edition: &str) -> io::Result<Option<String>> {
+    let rustfmt = std::env::var_os("RUSTFMT").unwrap_or_else(|| "rustfmt".into());
+    let mut child = Command::new(rustfmt)
+        .args(["--edition", edition])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()?;
+    child.stdin.take().unwrap().write_all(source.as_bytes())?;
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Ok(None);
+    }
+    String::from_utf8(output.stdout)
+        .map(Some)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+/// The lines of a source file, to turn the line and column of a span into a byte offset.
+struct Lines<'a> {
+    source: &'a str,
+    starts: Vec<usize>,
+}
+
+impl<'a> Lines<'a> {
+    fn new(source: &'a str) -> Self {
+        let starts = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Lines { source, starts }
+    }
+
+    /// The byte offset of a line and column, where lines start at 1 and columns count
+    /// characters from 0.
+    fn offset(&self, position: LineColumn) -> usize {
+        let start = self.starts[position.line - 1];
+        self.source[start..]
+            .char_indices()
+            .nth(position.column)
+            .map_or(self.source.len(), |(i, _)| start + i)
+    }
+
+    /// The leading whitespace of the line containing the byte offset.
+    fn indent_at(&self, offset: usize) -> &'a str {
+        let start = self.starts[self.starts.partition_point(|&start| start <= offset) - 1];
+        let line = &self.source[start..];
+        &line[..line.len() - line.trim_start_matches([' ', '\t']).len()]
+    }
+}
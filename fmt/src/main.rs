@@ -0,0 +1,107 @@
+//! `for-else-fmt [--check] [--edition EDITION] [PATH...]`
+//!
+//! Formats the blocks of the for-else macro invocations in the given Rust files, and in the
+//! `.rs` files under the given directories, `src` by default. Run it after `cargo fmt`, which
+//! leaves those blocks alone. With `--check`, files are only listed when they would change, and
+//! the exit code is 1 if any would.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let mut check = false;
+    let mut edition = "2021".to_string();
+    let mut paths = Vec::new();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--check" => check = true,
+            "--edition" => match args.next() {
+                Some(value) => edition = value,
+                None => return usage("`--edition` needs a value"),
+            },
+            "-h" | "--help" => {
+                println!("usage: for-else-fmt [--check] [--edition EDITION] [PATH...]");
+                return ExitCode::SUCCESS;
+            }
+            _ if arg.starts_with('-') => return usage(&format!("unknown option `{}`", arg)),
+            _ => paths.push(PathBuf::from(arg)),
+        }
+    }
+    if paths.is_empty() {
+        paths.push(PathBuf::from("src"));
+    }
+
+    let mut files = Vec::new();
+    for path in paths {
+        if let Err(err) = collect_files(&path, &mut files) {
+            eprintln!("for-else-fmt: {}: {}", path.display(), err);
+            return ExitCode::from(2);
+        }
+    }
+
+    let mut unformatted = false;
+    for file in files {
+        match format_file(&file, &edition, check) {
+            Ok(true) if check => {
+                println!("{}", file.display());
+                unformatted = true;
+            }
+            Ok(_) => {}
+            Err(err) => {
+                eprintln!("for-else-fmt: {}: {}", file.display(), err);
+                return ExitCode::from(2);
+            }
+        }
+    }
+    if unformatted {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn usage(message: &str) -> ExitCode {
+    eprintln!("for-else-fmt: {}", message);
+    eprintln!("usage: for-else-fmt [--check] [--edition EDITION] [PATH...]");
+    ExitCode::from(2)
+}
+
+/// Adds `path` if it is a file, or the `.rs` files under it if it is a directory, skipping
+/// hidden directories and `target`.
+fn collect_files(path: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    if !path.is_dir() {
+        files.push(path.to_path_buf());
+        return Ok(());
+    }
+    let mut entries = fs::read_dir(path)?.collect::<io::Result<Vec<_>>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+    for entry in entries {
+        let path = entry.path();
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if path.is_dir() {
+            if !name.starts_with('.') && name != "target" {
+                collect_files(&path, files)?;
+            }
+        } else if name.ends_with(".rs") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Formats `file` in place, or only checks it with `check`, and returns whether it changed.
+fn format_file(file: &Path, edition: &str, check: bool) -> io::Result<bool> {
+    let source = fs::read_to_string(file)?;
+    let formatted = for_else_fmt::format_source(&source, edition)?;
+    if formatted == source {
+        return Ok(false);
+    }
+    if !check {
+        fs::write(file, formatted)?;
+    }
+    Ok(true)
+}
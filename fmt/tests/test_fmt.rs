@@ -0,0 +1,118 @@
+use for_else_fmt::format_source;
+
+fn format(source: &str) -> String {
+    format_source(source, "2021").unwrap()
+}
+
+#[test]
+fn test_formats_blocks() {
+    let source = r#"fn main() {
+    for_! { n in [1,2,3] {
+            if n%2==0 {println!("{}",n); break;}
+    } else { println!("none") }}
+}
+"#;
+    let expected = r#"fn main() {
+    for_! { n in [1,2,3] {
+        if n % 2 == 0 {
+            println!("{}", n);
+            break;
+        }
+    } else {
+        println!("none")
+    }}
+}
+"#;
+    assert_eq!(format(source), expected);
+}
+
+#[test]
+fn test_idempotent() {
+    let source = r#"fn main() {
+    for_! { n in [1,2,3] {
+        if n % 2 == 0 {
+            break;
+        }
+    } else {
+        println!("none")
+    }}
+}
+"#;
+    assert_eq!(format(source), source);
+}
+
+#[test]
+fn test_nested_invocations() {
+    let source = r#"fn main() {
+    for_else::loop_else! { while let Some(row) = rows.pop() {
+        for_! { cell in row {  if cell==0 {break;}  } else {   found=true; }}
+    } else {}}
+}
+"#;
+    let expected = r#"fn main() {
+    for_else::loop_else! { while let Some(row) = rows.pop() {
+        for_! { cell in row {
+            if cell == 0 {
+                break;
+            }
+        } else {
+            found = true;
+        }}
+    } else {}}
+}
+"#;
+    assert_eq!(format(source), expected);
+}
+
+#[test]
+fn test_leaves_other_code_and_invalid_blocks() {
+    let source = r#"fn main() {
+    let v = vec![ 1,2 ];
+    other! { x in y {  a;b; } }
+    let odd = for_! { collect n in v {
+        if n%2==1 { emit n; }
+    }};
+}
+"#;
+    assert_eq!(format(source), source);
+}
+
+#[test]
+fn test_keyword_started_header() {
+    let source = r#"fn main() {
+    for_! { x in match mode { Mode::A => a.iter(), Mode::B => b.iter() } {  if *x==0 {break;}  } else {}}
+    loop_else! { while unsafe {  ready()  } {  poll();  } else {}}
+}
+"#;
+    let expected = r#"fn main() {
+    for_! { x in match mode { Mode::A => a.iter(), Mode::B => b.iter() } {
+        if *x == 0 {
+            break;
+        }
+    } else {}}
+    loop_else! { while unsafe {  ready()  } {
+        poll();
+    } else {}}
+}
+"#;
+    assert_eq!(format(source), expected);
+}
+
+#[test]
+fn test_compat_alias() {
+    let source = r#"fn main() {
+    forelse! { n in [1,3,5] {  if n%2==0 {break;}  } else {  println!("no even numbers")  }}
+}
+"#;
+    let expected = r#"fn main() {
+    forelse! { n in [1,3,5] {
+        if n % 2 == 0 {
+            break;
+        }
+    } else {
+        println!("no even numbers")
+    }}
+}
+"#;
+    assert_eq!(format(source), expected);
+}
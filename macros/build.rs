@@ -0,0 +1,18 @@
+use std::env;
+use std::process::Command;
+
+/// Turns on `for_else_nightly` on a nightly or dev compiler, whose `proc_macro::Diagnostic`
+/// reports the warnings of the macros as compiler warnings.
+fn main() {
+    println!("cargo:rerun-if-env-changed=RUSTC");
+    let rustc = env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+    let nightly = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .is_some_and(|version| version.contains("-nightly") || version.contains("-dev"));
+    if nightly {
+        println!("cargo:rustc-cfg=for_else_nightly");
+    }
+}
@@ -0,0 +1,64 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{Block, Expr, Pat, Result};
+
+use crate::errors::{parse_body, parse_else, parse_in, parse_pat};
+use crate::{else_branch, modify_breaks, BREAK_FLAG};
+
+pub struct BisectLoop {
+    var: Pat,
+    range: Expr,
+    body: Block,
+    else_block: Block,
+}
+
+impl Parse for BisectLoop {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let var = parse_pat(input)?;
+        parse_in(input)?;
+        let range = Expr::parse_without_eager_brace(input)?;
+        let body = parse_body(input)?;
+        let else_block = parse_else(input)?;
+        Ok(BisectLoop {
+            var,
+            range,
+            body,
+            else_block,
+        })
+    }
+}
+
+pub fn expand(mut input: BisectLoop) -> TokenStream {
+    modify_breaks(&mut input.body);
+
+    let var = input.var;
+    let range = input.range;
+    let body = input.body;
+    let else_branch = else_branch(&input.else_block);
+
+    // the probe is computed without `lo + hi`, which could overflow
+    quote! {
+        {
+            let mut #BREAK_FLAG = false;
+            let ::std::ops::Range {
+                start: mut _for_else_lo,
+                end: mut _for_else_hi,
+            } = #range;
+            while _for_else_lo < _for_else_hi {
+                let _for_else_probe = _for_else_lo + (_for_else_hi - _for_else_lo) / 2;
+                let #var = _for_else_probe;
+                let _for_else_ordering: ::std::cmp::Ordering = #body;
+                match _for_else_ordering {
+                    ::std::cmp::Ordering::Less => _for_else_lo = _for_else_probe + 1,
+                    ::std::cmp::Ordering::Greater => _for_else_hi = _for_else_probe,
+                    ::std::cmp::Ordering::Equal => {
+                        #BREAK_FLAG = true;
+                        break;
+                    }
+                }
+            }
+            #else_branch
+        }
+    }
+}
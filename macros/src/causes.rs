@@ -0,0 +1,161 @@
+use proc_macro2::{TokenStream, TokenTree};
+use quote::quote;
+use syn::parse::{ParseStream, Parser};
+use syn::{parse_quote, token, BinOp, Block, Expr, ExprBinary, Ident, Result, Token};
+
+/// A `while` condition chained with `&&` whose conjuncts are named, as in
+/// `open: !queue.is_closed() && in_time: Instant::now() < deadline`, so that an `else name`
+/// clause runs when that conjunct is the one that ends the loop.
+pub struct Causes {
+    conjuncts: Vec<(Option<Ident>, Expr)>,
+}
+
+impl Causes {
+    /// Parses the condition if any of its conjuncts is named, and leaves the input untouched
+    /// otherwise.
+    ///
+    /// The condition is split at the `&&`s outside of any delimiters, so a conjunct with a
+    /// block, such as a `match`, or with a `||` has to be wrapped in parentheses.
+    pub fn parse(input: ParseStream) -> Result<Option<Self>> {
+        if !has_names(&input.fork()) {
+            return Ok(None);
+        }
+        let mut conjuncts = Vec::new();
+        loop {
+            let name = parse_name(input)?;
+            // the conjunct ends at the next `&&`, where the rest of the condition is cut off
+            // for the expression parser to stop at the end of the header as usual
+            let fork = input.fork();
+            let mut tokens = TokenStream::new();
+            while !fork.is_empty() && !fork.peek(Token![&&]) && !fork.peek(token::Brace) {
+                tokens.extend([fork.parse::<TokenTree>()?]);
+            }
+            let (conjunct, rest) = (|tokens: ParseStream| {
+                let conjunct = Expr::parse_without_eager_brace(tokens)?;
+                Ok((conjunct, tokens.parse::<TokenStream>()?))
+            })
+            .parse2(tokens.clone())?;
+            if let Expr::Binary(ExprBinary {
+                op: BinOp::Or(or), ..
+            }) = conjunct
+            {
+                return Err(syn::Error::new_spanned(
+                    or,
+                    "a conjunct with `||` has to be wrapped in parentheses when the conjuncts are named",
+                ));
+            }
+            let rest = rest.into_iter().count();
+            for _ in rest..tokens.into_iter().count() {
+                input.parse::<TokenTree>()?;
+            }
+            conjuncts.push((name, conjunct));
+            if rest > 0 || !input.peek(Token![&&]) {
+                return Ok(Some(Causes { conjuncts }));
+            }
+            input.parse::<Token![&&]>()?;
+        }
+    }
+
+    /// The loop condition, which records the index of each conjunct in `_for_else_cause`
+    /// before evaluating it, so that the index of the false one is left there when the loop
+    /// ends.
+    pub fn condition(&self, setup: &mut TokenStream) -> Expr {
+        setup.extend(quote! {
+            let mut _for_else_cause = 0usize;
+        });
+        let checks = self
+            .conjuncts
+            .iter()
+            .enumerate()
+            .map(|(index, (_, conjunct))| {
+                quote! {
+                    _for_else_cause = #index;
+                    if !(#conjunct) {
+                        break '_for_else_condition false;
+                    }
+                }
+            });
+        parse_quote! {
+            '_for_else_condition: {
+                #(#checks)*
+                true
+            }
+        }
+    }
+
+    /// Parses the `else name { ... }` clauses after the loop body, and the plain `else` block
+    /// that may end them, into a single `else` block that runs the clause of the conjunct that
+    /// ended the loop. The plain `else` block runs for the conjuncts without a clause.
+    pub fn parse_else(&self, input: ParseStream) -> Result<Block> {
+        let mut clauses: Vec<(usize, Block)> = Vec::new();
+        let mut fallback = None;
+        while input.peek(Token![else]) {
+            input.parse::<Token![else]>()?;
+            if !input.peek(Ident) {
+                fallback = Some(input.parse::<Block>()?);
+                break;
+            }
+            let name: Ident = input.parse()?;
+            let Some(index) = self
+                .conjuncts
+                .iter()
+                .position(|(conjunct, _)| conjunct.as_ref() == Some(&name))
+            else {
+                return Err(syn::Error::new(
+                    name.span(),
+                    format!("the `while` condition has no conjunct named `{name}`"),
+                ));
+            };
+            if clauses.iter().any(|(covered, _)| *covered == index) {
+                return Err(syn::Error::new(
+                    name.span(),
+                    format!("the `else {name}` clause is given twice"),
+                ));
+            }
+            clauses.push((index, input.parse()?));
+        }
+        // with every conjunct covered the last clause takes the `_` arm, so that the clauses
+        // may evaluate to a value
+        let fallback = match fallback {
+            Some(block) => block,
+            None if clauses.len() == self.conjuncts.len() => clauses.pop().unwrap().1,
+            None => parse_quote! {{}},
+        };
+        let arms = clauses
+            .iter()
+            .map(|(index, block)| quote! { #index => #block });
+        Ok(parse_quote! {
+            {
+                match _for_else_cause {
+                    #(#arms,)*
+                    _ => #fallback
+                }
+            }
+        })
+    }
+}
+
+/// Whether any conjunct of the `&&` chain at the start of `input` is named.
+fn has_names(input: ParseStream) -> bool {
+    while !input.is_empty() && !input.peek(token::Brace) {
+        if let Ok(Some(_)) = parse_name(input) {
+            return true;
+        }
+        while !input.is_empty() && !input.peek(Token![&&]) && !input.peek(token::Brace) {
+            let _ = input.parse::<TokenTree>();
+        }
+        let _ = input.parse::<Option<Token![&&]>>();
+    }
+    false
+}
+
+/// Parses the `name:` of a conjunct, if it has one.
+fn parse_name(input: ParseStream) -> Result<Option<Ident>> {
+    if input.peek(Ident) && input.peek2(Token![:]) && !input.peek2(Token![::]) {
+        let name = input.parse()?;
+        input.parse::<Token![:]>()?;
+        Ok(Some(name))
+    } else {
+        Ok(None)
+    }
+}
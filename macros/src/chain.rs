@@ -0,0 +1,77 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::{parenthesized, Block, Expr, Ident, Pat, Result, Token};
+
+use crate::errors::{parse_body, parse_else, parse_in, parse_pat};
+use crate::{else_branch, modify_breaks, BREAK_FLAG};
+
+pub struct ChainLoop {
+    var: Pat,
+    sources: Vec<(Ident, Expr)>,
+    body: Block,
+    else_block: Block,
+}
+
+impl Parse for ChainLoop {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let var = parse_pat(input)?;
+        parse_in(input)?;
+        let mut sources = Vec::new();
+        loop {
+            let variant: Ident = input.parse()?;
+            let content;
+            parenthesized!(content in input);
+            sources.push((variant, content.parse()?));
+            if !input.peek(Token![,]) {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+        let body = parse_body(input)?;
+        let else_block = parse_else(input)?;
+        Ok(ChainLoop {
+            var,
+            sources,
+            body,
+            else_block,
+        })
+    }
+}
+
+pub fn expand(mut input: ChainLoop) -> TokenStream {
+    modify_breaks(&mut input.body);
+
+    let var = input.var;
+    let body = input.body;
+    let else_branch = else_branch(&input.else_block);
+    let params: Vec<_> = (0..input.sources.len())
+        .map(|i| format_ident!("T{}", i))
+        .collect();
+    let variants = input.sources.iter().map(|(variant, _)| variant);
+    let chained = input
+        .sources
+        .iter()
+        .map(|(variant, source)| {
+            quote! {
+                ::std::iter::Iterator::map(::std::iter::IntoIterator::into_iter(#source), #variant)
+            }
+        })
+        .reduce(|chained, source| quote! { ::std::iter::Iterator::chain(#chained, #source) });
+
+    // the variants name the sources, and are imported so that the body can match on them
+    quote! {
+        {
+            #[derive(Debug)]
+            enum _ForElseSource<#(#params),*> {
+                #(#variants(#params)),*
+            }
+            use _ForElseSource::*;
+
+            let mut #BREAK_FLAG = false;
+            for #var in #chained
+                #body
+            #else_branch
+        }
+    }
+}
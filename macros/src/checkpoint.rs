@@ -0,0 +1,152 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{parenthesized, parse_quote, Expr, Result, Token};
+
+use crate::{Adapter, STOPPED};
+
+mod kw {
+    syn::custom_keyword!(checkpoint);
+    syn::custom_keyword!(every);
+    syn::custom_keyword!(key);
+}
+
+/// The `checkpoint(store, every n[, key expr])` clause of `for_!`.
+///
+/// Without a `key` the checkpoint is the number of items taken from the iterable, and a resumed
+/// loop skips that many items. With a `key` the checkpoint is the key of the last item, and a
+/// resumed loop skips the items whose key is not greater.
+pub struct Checkpoint {
+    store: Expr,
+    every: Expr,
+    key: Option<Expr>,
+}
+
+impl Checkpoint {
+    pub fn peek(input: ParseStream) -> bool {
+        input.peek(kw::checkpoint)
+    }
+
+    /// The statements loading the checkpoint, to put before the loop.
+    pub fn setup(&self) -> TokenStream {
+        let store = &self.store;
+        let every = &self.every;
+        let progress = match self.key {
+            Some(_) => quote! {
+                let _for_else_resume = ::for_else::CheckpointStore::load(&mut _for_else_store);
+                let mut _for_else_last_key = ::core::option::Option::None;
+            },
+            None => quote! {
+                let mut _for_else_position: ::core::primitive::usize =
+                    ::core::option::Option::unwrap_or(::for_else::CheckpointStore::load(&mut _for_else_store), 0);
+            },
+        };
+        quote! {
+            let mut _for_else_store = #store;
+            let _for_else_every: ::core::primitive::usize = #every;
+            let mut _for_else_pending: ::core::primitive::usize = 0;
+            #progress
+        }
+    }
+
+    /// The adapter skipping the items walked by a previous run, when counting items.
+    pub fn adapter(&self) -> Option<Adapter> {
+        match self.key {
+            Some(_) => None,
+            None => Some(Adapter::Skip(parse_quote! { _for_else_position })),
+        }
+    }
+
+    /// Wraps the loop body, which runs once per item taken from the iterable, when counting
+    /// items.
+    pub fn count_items(&self, body: TokenStream) -> TokenStream {
+        if self.key.is_some() {
+            return body;
+        }
+        quote! {
+            {
+                if _for_else_pending == _for_else_every {
+                    ::for_else::CheckpointStore::save(&mut _for_else_store, &_for_else_position);
+                    _for_else_pending = 0;
+                }
+                _for_else_position += 1;
+                _for_else_pending += 1;
+                #body
+            }
+        }
+    }
+
+    /// Wraps the loop body, with the pattern bound, when tracking keys.
+    pub fn track_keys(&self, body: TokenStream) -> TokenStream {
+        let Some(key) = &self.key else {
+            return body;
+        };
+        quote! {
+            {
+                let _for_else_key = #key;
+                if let ::core::option::Option::Some(resume) = &_for_else_resume {
+                    if _for_else_key <= *resume {
+                        continue;
+                    }
+                }
+                if _for_else_pending == _for_else_every {
+                    if let ::core::option::Option::Some(last) = &_for_else_last_key {
+                        ::for_else::CheckpointStore::save(&mut _for_else_store, last);
+                    }
+                    _for_else_pending = 0;
+                }
+                _for_else_pending += 1;
+                _for_else_last_key = ::core::option::Option::Some(_for_else_key);
+                #body
+            }
+        }
+    }
+
+    /// The statements after the loop, which save the final progress when the loop was stopped
+    /// by its `until` condition and clear the checkpoint otherwise.
+    pub fn finish(&self, until: bool) -> TokenStream {
+        let save = match self.key {
+            Some(_) => quote! {
+                if let ::core::option::Option::Some(last) = &_for_else_last_key {
+                    ::for_else::CheckpointStore::save(&mut _for_else_store, last);
+                }
+            },
+            None => quote! {
+                ::for_else::CheckpointStore::save(&mut _for_else_store, &_for_else_position);
+            },
+        };
+        if until {
+            quote! {
+                if #STOPPED {
+                    #save
+                } else {
+                    ::for_else::CheckpointStore::clear(&mut _for_else_store);
+                }
+            }
+        } else {
+            quote! {
+                ::for_else::CheckpointStore::clear(&mut _for_else_store);
+            }
+        }
+    }
+}
+
+impl Parse for Checkpoint {
+    fn parse(input: ParseStream) -> Result<Self> {
+        input.parse::<kw::checkpoint>()?;
+        let content;
+        parenthesized!(content in input);
+        let store = content.parse()?;
+        content.parse::<Token![,]>()?;
+        content.parse::<kw::every>()?;
+        let every = content.parse()?;
+        let key = if content.peek(Token![,]) {
+            content.parse::<Token![,]>()?;
+            content.parse::<kw::key>()?;
+            Some(content.parse()?)
+        } else {
+            None
+        };
+        Ok(Checkpoint { store, every, key })
+    }
+}
@@ -0,0 +1,61 @@
+use proc_macro2::TokenStream;
+
+/// The environment variable naming the directory that expansions are dumped to.
+#[cfg(feature = "dump")]
+const DUMP_DIR: &str = "FOR_ELSE_DUMP";
+
+/// Hands the expansion of the macro `name` back to the compiler.
+///
+/// With the `dump` feature and `FOR_ELSE_DUMP` set, the expansion is first written to
+/// `<FOR_ELSE_DUMP>/<crate name>.rs`, prettified and preceded by the location of the
+/// invocation. The first expansion of a build truncates the file, so rebuilding a crate
+/// replaces its dump instead of repeating it. Failing to write the dump never fails the build.
+#[cfg(feature = "dump")]
+pub fn finish(name: &str, expansion: TokenStream) -> proc_macro::TokenStream {
+    if let Some(dir) = std::env::var_os(DUMP_DIR) {
+        let _ = write(std::path::Path::new(&dir), name, &expansion);
+    }
+    expansion.into()
+}
+
+#[cfg(not(feature = "dump"))]
+pub fn finish(_name: &str, expansion: TokenStream) -> proc_macro::TokenStream {
+    expansion.into()
+}
+
+/// Whether this compiler process has written a dump yet. Every build of a crate is a new
+/// process, and it expands every invocation of the crate again.
+#[cfg(feature = "dump")]
+static STARTED: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+#[cfg(feature = "dump")]
+fn write(dir: &std::path::Path, name: &str, expansion: &TokenStream) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let span = proc_macro::Span::call_site();
+    let crate_name = std::env::var("CARGO_CRATE_NAME").unwrap_or_else(|_| "unknown".into());
+    // prettyplease only unparses whole files, and every expansion is an expression, so it
+    // becomes the body of a function
+    let pretty = match syn::parse2(quote::quote! { fn expansion() { #expansion } }) {
+        Ok(file) => prettyplease::unparse(&file),
+        Err(_) => expansion.to_string(),
+    };
+
+    std::fs::create_dir_all(dir)?;
+    let first = !STARTED.swap(true, std::sync::atomic::Ordering::Relaxed);
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(first)
+        .append(!first)
+        .open(dir.join(format!("{}.rs", crate_name)))?;
+    writeln!(
+        file,
+        "// {}! at {}:{}:{}\n{}",
+        name,
+        span.file(),
+        span.line(),
+        span.column(),
+        pretty
+    )
+}
@@ -0,0 +1,54 @@
+use proc_macro2::{Group, TokenStream, TokenTree};
+
+/// Rewrites every `emit value;` statement in `stream` into the statement returned by `push`
+/// for `value`.
+///
+/// `emit` is not Rust syntax, so this works on tokens before the body is parsed. It descends
+/// into nested groups, but not into the input of nested macro invocations, which may give
+/// `emit` a meaning of their own. An `emit` followed by punctuation is left alone, so that
+/// variables named `emit` keep working in expressions such as `emit.len()` or `emit + 1`.
+pub fn rewrite_emits(
+    stream: TokenStream,
+    push: &dyn Fn(TokenStream) -> TokenStream,
+) -> TokenStream {
+    let mut tokens = stream.into_iter().peekable();
+    let mut output = Vec::new();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Ident(ident) if ident == "emit" && starts_value(tokens.peek()) => {
+                let mut value = TokenStream::new();
+                for token in tokens.by_ref() {
+                    if matches!(&token, TokenTree::Punct(punct) if punct.as_char() == ';') {
+                        break;
+                    }
+                    value.extend([token]);
+                }
+                output.extend(push(rewrite_emits(value, push)));
+            }
+            TokenTree::Group(group) => {
+                let is_macro_input = matches!(
+                    output.last(),
+                    Some(TokenTree::Punct(punct)) if punct.as_char() == '!'
+                );
+                let stream = if is_macro_input {
+                    group.stream()
+                } else {
+                    rewrite_emits(group.stream(), push)
+                };
+                let mut rewritten = Group::new(group.delimiter(), stream);
+                rewritten.set_span(group.span());
+                output.push(TokenTree::Group(rewritten));
+            }
+            token => output.push(token),
+        }
+    }
+    output.into_iter().collect()
+}
+
+fn starts_value(token: Option<&TokenTree>) -> bool {
+    match token {
+        Some(TokenTree::Ident(_)) | Some(TokenTree::Literal(_)) | Some(TokenTree::Group(_)) => true,
+        Some(TokenTree::Punct(punct)) => matches!(punct.as_char(), '&' | '*' | '-'),
+        None => false,
+    }
+}
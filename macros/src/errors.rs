@@ -0,0 +1,338 @@
+use proc_macro2::{Delimiter, Span, TokenStream, TokenTree};
+use quote::quote;
+use std::fmt::Display;
+use syn::buffer::Cursor;
+use syn::ext::IdentExt;
+use syn::parse::ParseStream;
+use syn::spanned::Spanned;
+use syn::token::{Brace, Paren};
+use syn::{parse_quote, Attribute, Block, Error, Expr, Ident, Pat, PatParen, Result, Stmt, Token};
+
+/// Stable codes for the errors reported by the macros, explained in the crate documentation.
+///
+/// Codes are never reused or renumbered, so build logs can be searched for them.
+#[derive(Clone, Copy)]
+pub enum ErrorCode {
+    MissingIn,
+    MissingBody,
+    MissingElse,
+    UnknownOption,
+    Unstable,
+    #[cfg(any(feature = "tokio", feature = "rayon"))]
+    LabeledBreakInTask,
+    InvalidIterable,
+}
+
+impl ErrorCode {
+    pub fn code(self) -> &'static str {
+        match self {
+            ErrorCode::MissingIn => "FE0001",
+            ErrorCode::MissingBody => "FE0002",
+            ErrorCode::MissingElse => "FE0003",
+            ErrorCode::UnknownOption => "FE0004",
+            ErrorCode::Unstable => "FE0005",
+            #[cfg(any(feature = "tokio", feature = "rayon"))]
+            ErrorCode::LabeledBreakInTask => "FE0006",
+            ErrorCode::InvalidIterable => "FE0007",
+        }
+    }
+
+    /// Renders `message` tagged with this code.
+    pub fn message(self, message: impl Display) -> String {
+        format!("[{}] {}", self.code(), message)
+    }
+
+    /// Builds an error at `span` tagged with this code.
+    pub fn error(self, span: Span, message: impl Display) -> Error {
+        Error::new(span, self.message(message))
+    }
+
+    /// Tags an error reported by syn with this code, keeping its span and message.
+    pub fn tag(self, error: Error) -> Error {
+        self.error(error.span(), error)
+    }
+}
+
+/// Parses the loop pattern, which may be any pattern allowed in a `for` loop.
+///
+/// A top-level or-pattern is wrapped in parentheses, so that the expansion can use the pattern
+/// anywhere a pattern is accepted, such as in a `let` statement or closure parameter.
+pub fn parse_pat(input: ParseStream) -> Result<Pat> {
+    let pat = Pat::parse_multi_with_leading_vert(input)?;
+    Ok(match pat {
+        Pat::Or(_) => Pat::Paren(PatParen {
+            attrs: Vec::new(),
+            paren_token: Paren::default(),
+            pat: Box::new(pat),
+        }),
+        pat => pat,
+    })
+}
+
+/// Parses the `in` separating the loop pattern from the header expression.
+///
+/// The separators of other languages' for-each loops get a targeted error at the offending
+/// token, since they are the most likely mistake here.
+pub fn parse_in(input: ParseStream) -> Result<()> {
+    if input.peek(Token![in]) {
+        input.parse::<Token![in]>()?;
+        return Ok(());
+    }
+
+    let message = if input.peek(Token![:]) {
+        "expected `in` but found `:`, which is Java and C++ syntax; write `in` instead".to_string()
+    } else if input.peek(Ident::peek_any) {
+        let found = input.fork().call(Ident::parse_any)?.to_string();
+        if found == "of" {
+            "expected `in` but found `of`, which is JavaScript syntax; write `in` instead"
+                .to_string()
+        } else if found.to_lowercase().starts_with("in") {
+            format!("expected `in` but found `{}`; did you mean `in`?", found)
+        } else {
+            format!("expected `in` after the loop pattern but found `{}`", found)
+        }
+    } else {
+        return Err(ErrorCode::MissingIn.tag(input.error("expected `in` after the loop pattern")));
+    };
+    Err(ErrorCode::MissingIn.error(input.span(), message))
+}
+
+/// Parses the expression after `in` that the loop runs over.
+pub fn parse_iterable(input: ParseStream) -> Result<Expr> {
+    Expr::parse_without_eager_brace(input).map_err(|err| {
+        ErrorCode::InvalidIterable.error(
+            err.span(),
+            format_args!("could not parse iterable expression: {}", err),
+        )
+    })
+}
+
+/// The longest header or body quoted in full by the errors of [`Split`].
+const SNIPPET_LEN: usize = 60;
+
+/// Where the macro split its input into the loop header and body, for the errors about the
+/// parts around that point.
+///
+/// The header expression ends at the first block it cannot continue into, so an expression
+/// that reads too far or stops too early shows up as an error about the body or the `else`.
+/// Those errors quote the tokens that were read as the header to show where the split went.
+pub struct Split<'a> {
+    start: Cursor<'a>,
+    /// The tokens of the header and where the body starts, once the header has been read.
+    header: Option<(TokenStream, Cursor<'a>)>,
+}
+
+impl<'a> Split<'a> {
+    /// Starts a split at the beginning of the header.
+    pub fn new(input: ParseStream<'a>) -> Self {
+        Split {
+            start: input.cursor(),
+            header: None,
+        }
+    }
+
+    /// Marks the end of the header, clauses included, once `input` reaches the body.
+    pub fn header_end(&mut self, input: ParseStream<'a>) {
+        self.header = Some((read_since(self.start, input), input.cursor()));
+    }
+
+    /// Explains an error in the header itself with the tokens read before it.
+    pub fn header_error(&self, error: Error, input: ParseStream<'a>) -> Error {
+        let read = read_since(self.start, input);
+        if read.is_empty() {
+            return error;
+        }
+        explain(
+            error,
+            format_args!(
+                "the header was read as `{}` up to this point",
+                snippet(&read)
+            ),
+        )
+    }
+
+    /// Explains an error in the body with the header that was read and where the body was
+    /// expected to start.
+    ///
+    /// Errors inside a block are only explained when the block is followed by something other
+    /// than a clause, such as another block or a method call, which means it most likely
+    /// belongs to the header as the fields of a struct literal.
+    pub fn body_error(&self, error: Error) -> Error {
+        let Some((header, body_start)) = &self.header else {
+            return error;
+        };
+        let next = match body_start.token_tree() {
+            Some((token, rest)) => {
+                let followed_by_clause = rest
+                    .token_tree()
+                    .is_none_or(|(next, _)| starts_clause(&next));
+                if is_block(&token) && followed_by_clause {
+                    return error;
+                }
+                format!("`{}`", snippet(&token))
+            }
+            None => "the end of the input".to_string(),
+        };
+        explain(
+            error,
+            format_args!(
+                "the header was read as `{}`, so the body was expected to start at {}",
+                snippet(header),
+                next
+            ),
+        )
+    }
+
+    /// Explains a missing `else` right after the body with how the header and body were read.
+    ///
+    /// When the input ends after the body, the error points at the closing brace of the body
+    /// rather than at the whole invocation.
+    pub fn else_error(&self, error: Error, input: ParseStream<'a>) -> Error {
+        let Some((header, body_start)) = &self.header else {
+            return error;
+        };
+        // errors after other clauses or inside the `else` block are not about the split
+        match body_start.token_tree() {
+            Some((body, after_body)) if after_body == input.cursor() => {
+                let error = match &body {
+                    TokenTree::Group(group) if input.is_empty() => ErrorCode::MissingElse
+                        .error(group.span_close(), "expected `else` block after loop body"),
+                    _ => error,
+                };
+                explain(
+                    error,
+                    format_args!(
+                        "the header was read as `{}` and the body as `{}`",
+                        snippet(header),
+                        snippet(&body)
+                    ),
+                )
+            }
+            _ => error,
+        }
+    }
+}
+
+fn is_block(token: &TokenTree) -> bool {
+    matches!(token, TokenTree::Group(group) if group.delimiter() == Delimiter::Brace)
+}
+
+/// Whether `token` can start what follows the body: a keyword, a plugin path or an attribute.
+fn starts_clause(token: &TokenTree) -> bool {
+    match token {
+        TokenTree::Ident(_) => true,
+        TokenTree::Punct(punct) => punct.as_char() == '#',
+        _ => false,
+    }
+}
+
+/// Collects the tokens `input` has read since it was at `start`.
+fn read_since(start: Cursor, input: ParseStream) -> TokenStream {
+    let end = input.cursor();
+    let mut cursor = start;
+    let mut read = TokenStream::new();
+    while cursor < end {
+        let Some((token, next)) = cursor.token_tree() else {
+            break;
+        };
+        read.extend([token]);
+        cursor = next;
+    }
+    read
+}
+
+/// Renders `tokens`, shortened when they are too long to quote in a message.
+fn snippet(tokens: &impl Display) -> String {
+    let text = tokens.to_string();
+    match text.char_indices().nth(SNIPPET_LEN) {
+        Some((end, _)) => format!("{} ...", &text[..end]),
+        None => text,
+    }
+}
+
+/// Appends `explanation` to every message of `error`, keeping their spans.
+fn explain(error: Error, explanation: impl Display) -> Error {
+    error
+        .into_iter()
+        .map(|error| Error::new(error.span(), format!("{}; {}", error, explanation)))
+        .reduce(|mut combined, error| {
+            combined.combine(error);
+            combined
+        })
+        .unwrap()
+}
+
+/// Parses the loop body.
+pub fn parse_body(input: ParseStream) -> Result<Block> {
+    if !input.peek(Brace) {
+        return Err(ErrorCode::MissingBody.tag(input.error("expected loop body in braces")));
+    }
+    input.parse().map_err(|err| ErrorCode::MissingBody.tag(err))
+}
+
+/// Parses the `else` keyword and the block that follows it, along with the attributes written
+/// before the keyword.
+pub fn parse_else(input: ParseStream) -> Result<Block> {
+    let attrs = parse_clause_attrs(input)?;
+    if !input.peek(Token![else]) {
+        return Err(
+            ErrorCode::MissingElse.tag(input.error("expected `else` block after loop body"))
+        );
+    }
+    input.parse::<Token![else]>()?;
+    Ok(attach_attrs(attrs, parse_else_block(input)?))
+}
+
+/// Parses what follows `else`: a block, or a single expression ended by `;`, such as
+/// `continue 'outer;`, which becomes a block holding that statement.
+pub fn parse_else_block(input: ParseStream) -> Result<Block> {
+    if input.peek(Brace) {
+        return input.parse();
+    }
+    let expr: Expr = input.parse()?;
+    let semi = input.parse::<Token![;]>()?;
+    Ok(Block {
+        brace_token: Brace(expr.span()),
+        stmts: vec![Stmt::Expr(expr, Some(semi))],
+    })
+}
+
+/// Parses the outer attributes written before a clause. Doc comments are dropped, since there
+/// is nothing for them to document.
+pub fn parse_clause_attrs(input: ParseStream) -> Result<Vec<Attribute>> {
+    let mut attrs = input.call(Attribute::parse_outer)?;
+    attrs.retain(|attr| !attr.path().is_ident("doc"));
+    Ok(attrs)
+}
+
+/// Applies the attributes written before a clause to its block.
+///
+/// The attributes go on a `let` statement holding the value of the block, since attributes on
+/// expressions are unstable. A clause removed by `#[cfg(...)]` evaluates to `()` instead.
+pub fn attach_attrs(attrs: Vec<Attribute>, block: Block) -> Block {
+    if attrs.is_empty() {
+        return block;
+    }
+    let cfgs = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("cfg"))
+        .filter_map(|attr| attr.meta.require_list().ok())
+        .map(|list| &list.tokens)
+        .collect::<Vec<_>>();
+    let removed = if cfgs.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            #[cfg(not(all(#(#cfgs),*)))]
+            let _for_else_clause = ();
+        }
+    };
+    parse_quote! {
+        {
+            #(#attrs)*
+            let _for_else_clause = #block;
+            #removed
+            _for_else_clause
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{Block, Expr, Result, Token};
+
+use crate::errors::{parse_body, ErrorCode};
+use crate::{invocation_location, BREAK_FLAG};
+
+mod kw {
+    syn::custom_keyword!(timeout);
+    syn::custom_keyword!(interval);
+}
+
+pub struct Eventually {
+    timeout: Option<Expr>,
+    interval: Option<Expr>,
+    body: Block,
+}
+
+impl Parse for Eventually {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut timeout = None;
+        let mut interval = None;
+        while !input.peek(syn::token::Brace) {
+            let lookahead = input.lookahead1();
+            if lookahead.peek(kw::timeout) && timeout.is_none() {
+                input.parse::<kw::timeout>()?;
+                timeout = Some(Expr::parse_without_eager_brace(input)?);
+            } else if lookahead.peek(kw::interval) && interval.is_none() {
+                input.parse::<kw::interval>()?;
+                interval = Some(Expr::parse_without_eager_brace(input)?);
+            } else {
+                return Err(ErrorCode::UnknownOption.tag(lookahead.error()));
+            }
+            if !input.peek(syn::token::Brace) {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        let body = parse_body(input)?;
+        Ok(Eventually {
+            timeout,
+            interval,
+            body,
+        })
+    }
+}
+
+pub fn expand(input: Eventually) -> TokenStream {
+    let location = invocation_location();
+    let timeout = input
+        .timeout
+        .unwrap_or_else(|| syn::parse_quote!(::std::time::Duration::from_secs(5)));
+    let interval = input
+        .interval
+        .unwrap_or_else(|| syn::parse_quote!(::std::time::Duration::from_millis(50)));
+    let body = input.body;
+
+    // a clean pass of the body is the `break`, running out of time is the else
+    quote! {
+        {
+            let _for_else_timeout: ::std::time::Duration = #timeout;
+            let mut _for_else_interval = #interval;
+            let _for_else_start = ::std::time::Instant::now();
+            let mut _for_else_attempts: ::std::primitive::usize = 0;
+            let mut _for_else_last_failure = ::std::string::String::new();
+            let mut #BREAK_FLAG = false;
+            loop {
+                _for_else_attempts += 1;
+                match ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| #body)) {
+                    ::std::result::Result::Ok(()) => {
+                        #BREAK_FLAG = true;
+                        break;
+                    }
+                    ::std::result::Result::Err(payload) => {
+                        _for_else_last_failure = if let ::std::option::Option::Some(message) = payload.downcast_ref::<&str>() {
+                            ::std::string::ToString::to_string(message)
+                        } else if let ::std::option::Option::Some(message) = payload.downcast_ref::<::std::string::String>() {
+                            ::std::clone::Clone::clone(message)
+                        } else {
+                            ::std::string::ToString::to_string("<non-string panic payload>")
+                        };
+                    }
+                }
+                if _for_else_start.elapsed() >= _for_else_timeout {
+                    break;
+                }
+                ::std::thread::sleep(::for_else::Backoff::next_delay(&mut _for_else_interval));
+            }
+            if !#BREAK_FLAG {
+                ::std::panic!(
+                    "eventually_! block at {} still failing after {} attempts over {:?}; last failure: {}",
+                    #location,
+                    _for_else_attempts,
+                    _for_else_start.elapsed(),
+                    _for_else_last_failure,
+                );
+            }
+        }
+    }
+}
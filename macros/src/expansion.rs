@@ -0,0 +1,140 @@
+use proc_macro2::{Delimiter, Group, Spacing, TokenStream, TokenTree};
+use quote::quote;
+use syn::parse::{Parse, ParseStream, Parser};
+use syn::{parse2, Error, Ident, LitStr, Result, Token};
+
+use crate::{
+    bisect, chain, eventually, loop_else, machine, merge, read_chunks, retry, search, stdin,
+    try_for, warning, worklist,
+};
+
+/// An `assert_expansion!` invocation: a macro invocation of this crate and the tokens it is
+/// expected to expand to.
+pub struct AssertExpansion {
+    name: Ident,
+    input: TokenStream,
+    expected: TokenStream,
+}
+
+impl Parse for AssertExpansion {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let name: Ident = input.parse()?;
+        input.parse::<Token![!]>()?;
+        let invocation: Group = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let expected = if input.peek(LitStr) {
+            let expected: LitStr = input.parse()?;
+            expected.parse()?
+        } else {
+            let expected: Group = input.parse()?;
+            if expected.delimiter() != Delimiter::Brace {
+                return Err(Error::new(
+                    expected.span(),
+                    "expected the expansion as a string literal or in braces",
+                ));
+            }
+            expected.stream()
+        };
+        input.parse::<Option<Token![,]>>()?;
+        Ok(AssertExpansion {
+            name,
+            input: invocation.stream(),
+            expected,
+        })
+    }
+}
+
+/// Expands the invocation the way the named macro would.
+fn expand_invocation(name: &Ident, input: TokenStream) -> Result<TokenStream> {
+    Ok(match name.to_string().as_str() {
+        "for_" => crate::expand_for(parse2(input)?),
+        "loop_else" => crate::expand_for(loop_else::parse.parse2(input)?),
+        "while_let_" => crate::expand_for(loop_else::parse_while_let.parse2(input)?),
+        "do_while_" => crate::expand_for(loop_else::parse_do_while.parse2(input)?),
+        "loop_" => crate::expand_for(loop_else::parse_limited.parse2(input)?),
+        "cfor_" => crate::expand_for(loop_else::parse_cfor.parse2(input)?),
+        "try_for_" => try_for::expand(parse2(input)?),
+        "retry_" => retry::expand(parse2(input)?),
+        "search_" => search::expand(parse2(input)?),
+        #[cfg(feature = "futures")]
+        "for_await_" => crate::expand_for(loop_else::parse_await.parse2(input)?),
+        "bisect_loop_" => bisect::expand(parse2(input)?),
+        "chain_for_" => chain::expand(parse2(input)?),
+        "machine_loop_" => machine::expand(parse2(input)?),
+        "merge_for_" => merge::expand(parse2(input)?),
+        "worklist_for_" => worklist::expand(parse2(input)?),
+        "eventually_" => eventually::expand(parse2(input)?),
+        "for_stdin_" => stdin::expand(parse2(input)?),
+        "read_chunks_" => read_chunks::expand(parse2(input)?),
+        #[cfg(feature = "fs")]
+        "fs_search_" => crate::fs_search::expand(parse2(input)?),
+        #[cfg(feature = "paginate")]
+        "paginate_for_" => crate::paginate::expand(parse2(input)?),
+        #[cfg(feature = "rayon")]
+        "par_for_" => crate::par::expand(parse2(input)?),
+        #[cfg(feature = "tokio")]
+        "spawn_for_" => crate::spawn::expand(parse2(input)?),
+        #[cfg(feature = "notify")]
+        "watch_for_" => crate::watch::expand(parse2(input)?),
+        #[cfg(feature = "fallible-rows")]
+        "rows_for_" => crate::rows::expand(parse2(input)?),
+        #[cfg(feature = "regex")]
+        "regex_search_" => crate::regex_search::expand(parse2(input)?),
+        #[cfg(feature = "crossterm")]
+        "terminal_for_" => crate::terminal::expand(parse2(input)?),
+        _ => {
+            return Err(Error::new(
+                name.span(),
+                format!("`{}!` is not a macro of `for-else`", name),
+            ))
+        }
+    })
+}
+
+/// Renders `stream` with one space between tokens, so that the rendering only depends on the
+/// tokens and not on where they came from. Joint punctuation such as `::` stays together.
+fn render(stream: TokenStream, out: &mut String) {
+    // no space after an opening delimiter
+    let mut joint = true;
+    for tree in stream {
+        if !joint {
+            out.push(' ');
+        }
+        joint = false;
+        match tree {
+            TokenTree::Group(group) => {
+                let (open, close) = match group.delimiter() {
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::Bracket => ("[", "]"),
+                    Delimiter::Brace => ("{", "}"),
+                    Delimiter::None => ("", ""),
+                };
+                out.push_str(open);
+                render(group.stream(), out);
+                out.push_str(close);
+            }
+            TokenTree::Punct(punct) => {
+                out.push(punct.as_char());
+                joint = punct.spacing() == Spacing::Joint;
+            }
+            tree => out.push_str(&tree.to_string()),
+        }
+    }
+}
+
+pub fn expand(input: AssertExpansion) -> TokenStream {
+    // warnings are left out, since they are reported differently on nightly
+    let actual = match warning::silenced(|| expand_invocation(&input.name, input.input)) {
+        Ok(actual) => actual,
+        Err(err) => return err.to_compile_error(),
+    };
+    // both sides are rendered from token streams, so only the tokens are compared,
+    // not the whitespace or comments of the expected expansion
+    let (mut rendered_actual, mut rendered_expected) = (String::new(), String::new());
+    render(actual, &mut rendered_actual);
+    render(input.expected, &mut rendered_expected);
+    let message = format!("unexpected expansion of `{}!`", input.name);
+    quote! {
+        ::std::assert_eq!(#rendered_actual, #rendered_expected, #message)
+    }
+}
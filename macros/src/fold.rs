@@ -0,0 +1,169 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{parse_quote, Result};
+
+use crate::{
+    apply_adapters, is_irrefutable, replace_breaks, replace_continues, ForLoop, Header, NoBreak,
+    BREAK_FLAG,
+};
+
+/// Rejects the clauses a `fold` loop cannot run, which either need to stop between items or
+/// run code the closure cannot reach.
+pub fn validate(input: &ForLoop) -> Result<()> {
+    let Some(fold) = &input.fold else {
+        return Ok(());
+    };
+    let unsupported = if !matches!(input.header, Header::For { .. }) {
+        Some("a `while`, `loop` or `lend` header")
+    } else if input.collect {
+        Some("`collect`")
+    } else if input.until.is_some() {
+        Some("`until`")
+    } else if input.within.is_some() {
+        Some("`within`")
+    } else if input.cooldown.is_some() {
+        Some("`cooldown`")
+    } else if input.interrupt.is_some() {
+        Some("`interrupt`")
+    } else if input.observe.is_some() {
+        Some("`observe`")
+    } else if input.on_try.is_some() {
+        Some("`try continue` or `try break`")
+    } else if input.defer.is_some() {
+        Some("`defer`")
+    } else if input.fallback.is_some() {
+        Some("`else continue with`")
+    } else if input.skipped.is_some() {
+        Some("`skipped`")
+    } else if input.empty.is_some() {
+        Some("`empty`")
+    } else if input.count.is_some() {
+        Some("`else |count|`")
+    } else if !input.plugins.is_empty() {
+        Some("clause plugins")
+    } else {
+        #[cfg(feature = "resume")]
+        if input.checkpoint.is_some() {
+            return Err(syn::Error::new(
+                fold.span,
+                "`fold` loops do not support `checkpoint`",
+            ));
+        }
+        None
+    };
+    match unsupported {
+        Some(clause) => Err(syn::Error::new(
+            fold.span,
+            format!("`fold` loops do not support {}", clause),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Expands a `fold` loop, which drives the iterator with `Iterator::try_fold` instead of a `for`
+/// loop.
+///
+/// The body becomes a closure returning `ControlFlow`: `break` returns `Break` and `continue`
+/// returns `Continue`, so the loop broke exactly when `try_fold` does.
+pub fn expand(mut input: ForLoop) -> TokenStream {
+    let label = match &input.label {
+        Some(label) => label.name.clone(),
+        None => parse_quote! { '_for_else_loop },
+    };
+    let breaks = replace_breaks(&mut input.body, &|expr_break| match &expr_break.label {
+        Some(target) if *target != label => quote_spanned! {target.span()=>
+            {
+                ::core::compile_error!("`fold` loops cannot break out of an enclosing loop");
+            }
+        },
+        _ => match &expr_break.expr {
+            Some(value) => quote_spanned! {value.span()=>
+                ::core::compile_error!("`fold` loops do not evaluate to a value, so their `break`s cannot have one");
+            },
+            None => quote! {
+                return ::core::ops::ControlFlow::Break(());
+            },
+        },
+    });
+    replace_continues(
+        &mut input.body,
+        &label,
+        &parse_quote! { return ::core::ops::ControlFlow::Continue(()) },
+    );
+    let warning = input.warning(&breaks);
+
+    let Header::For {
+        var,
+        expr,
+        adapters,
+    } = input.header
+    else {
+        unreachable!("`fold` loops are validated to have a `for` header")
+    };
+
+    let body = input.body;
+    let guard = input.guard.map(|guard| {
+        quote! {
+            if !(#guard) {
+                return ::core::ops::ControlFlow::Continue(());
+            }
+        }
+    });
+    let expr = apply_adapters(expr, &adapters);
+    let item = if is_irrefutable(&var) {
+        quote! { #var }
+    } else {
+        quote! { _for_else_item }
+    };
+    let unpack = if is_irrefutable(&var) {
+        quote! {}
+    } else {
+        quote! {
+            #[allow(irrefutable_let_patterns)]
+            let #var = _for_else_item else {
+                return ::core::ops::ControlFlow::Continue(());
+            };
+        }
+    };
+    let no_break_span = input
+        .no_break
+        .as_ref()
+        .map_or_else(Span::call_site, NoBreak::span);
+    let no_break = match (input.then, input.no_break) {
+        (Some(then), no_break) => {
+            let no_break = no_break.map_or_else(|| quote! { {} }, |no_break| quote! { #no_break });
+            quote_spanned! {no_break_span=>
+                if #BREAK_FLAG
+                    #then
+                else
+                    #no_break
+            }
+        }
+        (None, no_break) => quote_spanned! {no_break_span=>
+            if !#BREAK_FLAG
+                #no_break
+        },
+    };
+    let state = input.state;
+
+    quote! {
+        {
+            #(#state)*
+            #warning
+            let #BREAK_FLAG = ::core::ops::ControlFlow::is_break(
+                &::core::iter::Iterator::try_fold(
+                    &mut ::core::iter::IntoIterator::into_iter(#expr),
+                    (),
+                    |(), #item| -> ::core::ops::ControlFlow<()> {
+                        #unpack
+                        #guard
+                        #body
+                        ::core::ops::ControlFlow::Continue(())
+                    },
+                ),
+            );
+            #no_break
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{Block, Expr, Ident, Pat, Result, Token};
+
+use crate::errors::{parse_body, parse_else, parse_in, parse_pat};
+use crate::max_errors::MaxErrors;
+use crate::{else_branch, modify_breaks, BREAK_FLAG};
+
+mod kw {
+    syn::custom_keyword!(recursive);
+    syn::custom_keyword!(err);
+}
+
+pub struct FsSearch {
+    var: Pat,
+    recursive: bool,
+    path: Expr,
+    max_errors: Option<MaxErrors>,
+    body: Block,
+    err_var: Ident,
+    err_block: Block,
+    else_block: Block,
+}
+
+impl Parse for FsSearch {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let var = parse_pat(input)?;
+        parse_in(input)?;
+        let recursive = if input.peek(kw::recursive) {
+            input.parse::<kw::recursive>()?;
+            true
+        } else {
+            false
+        };
+        let path = Expr::parse_without_eager_brace(input)?;
+        let max_errors = if input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        let body = parse_body(input)?;
+        input.parse::<kw::err>()?;
+        let err_var: Ident = input.parse()?;
+        let err_block: Block = input.parse()?;
+        let else_block = parse_else(input)?;
+        Ok(FsSearch {
+            var,
+            recursive,
+            path,
+            max_errors,
+            body,
+            err_var,
+            err_block,
+            else_block,
+        })
+    }
+}
+
+pub fn expand(mut input: FsSearch) -> TokenStream {
+    modify_breaks(&mut input.body);
+    modify_breaks(&mut input.err_block);
+
+    let var = input.var;
+    let path = input.path;
+    let body = input.body;
+    let err_var = input.err_var;
+    let err_block = input.err_block;
+    let else_branch = else_branch(&input.else_block);
+    let max_errors = input.max_errors.as_ref().map(MaxErrors::setup);
+    let tolerate = input.max_errors.as_ref().map(MaxErrors::tolerate);
+
+    // directories are walked depth-first by keeping a stack of open `ReadDir`s,
+    // which keeps the whole walk inside a single loop that the user's `break` can leave
+    let descend = if input.recursive {
+        quote! {
+            if let ::std::result::Result::Ok(file_type) = _for_else_entry.file_type() {
+                if file_type.is_dir() {
+                    _for_else_dirs.push(::std::fs::read_dir(_for_else_entry.path()));
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    quote! {
+        {
+            let mut #BREAK_FLAG = false;
+            let mut _for_else_dirs = ::std::vec![::std::fs::read_dir(#path)];
+            #max_errors
+            loop {
+                let _for_else_item = match _for_else_dirs.last_mut() {
+                    ::std::option::Option::None => break,
+                    ::std::option::Option::Some(::std::result::Result::Ok(dir)) => match dir.next() {
+                        ::std::option::Option::Some(item) => item,
+                        ::std::option::Option::None => {
+                            _for_else_dirs.pop();
+                            continue;
+                        }
+                    },
+                    ::std::option::Option::Some(::std::result::Result::Err(_)) => match _for_else_dirs.pop() {
+                        ::std::option::Option::Some(::std::result::Result::Err(err)) => ::std::result::Result::Err(err),
+                        _ => ::std::unreachable!(),
+                    },
+                };
+                match _for_else_item {
+                    ::std::result::Result::Ok(_for_else_entry) => {
+                        #descend
+                        let #var = _for_else_entry;
+                        #body
+                    }
+                    ::std::result::Result::Err(#err_var) => {
+                        #tolerate
+                        #err_block
+                    }
+                }
+            }
+            #else_branch
+        }
+    }
+}
@@ -0,0 +1,4030 @@
+//! Procedural macros of the `for-else` crate.
+//!
+//! Depend on `for-else` instead, which re-exports these macros along with the types
+//! they share with its runtime API.
+//!
+//! The examples that need crates such as `tokio` or `rusqlite` are not run as doctests here,
+//! since this crate does not depend on them; the `for-else` integration tests cover them.
+
+#![cfg_attr(for_else_nightly, feature(proc_macro_diagnostic))]
+
+extern crate proc_macro;
+
+mod bisect;
+mod causes;
+mod chain;
+#[cfg(feature = "resume")]
+mod checkpoint;
+mod dump;
+mod emit;
+mod errors;
+mod eventually;
+mod expansion;
+mod fold;
+#[cfg(feature = "fs")]
+mod fs_search;
+mod loop_else;
+mod machine;
+mod max_errors;
+mod merge;
+#[cfg(feature = "paginate")]
+mod paginate;
+#[cfg(feature = "rayon")]
+mod par;
+mod plugin;
+mod read_chunks;
+#[cfg(feature = "regex")]
+mod regex_search;
+mod retry;
+#[cfg(feature = "fallible-rows")]
+mod rows;
+mod search;
+#[cfg(feature = "tokio")]
+mod spawn;
+mod stdin;
+#[cfg(feature = "crossterm")]
+mod terminal;
+mod try_clause;
+mod try_for;
+mod unstable;
+mod warning;
+#[cfg(feature = "notify")]
+mod watch;
+mod worklist;
+
+use emit::rewrite_emits;
+use errors::{
+    attach_attrs, parse_body, parse_clause_attrs, parse_else_block, parse_in, parse_iterable,
+    parse_pat, ErrorCode, Split,
+};
+use proc_macro::TokenStream;
+use proc_macro2::{Delimiter, Group, Span, TokenStream as TokenStream2, TokenTree};
+use quote::{quote, quote_spanned, ToTokens};
+use syn::parse::discouraged::Speculative;
+use syn::parse::{Parse, ParseStream, Parser};
+use syn::spanned::Spanned;
+use syn::visit::{self, Visit};
+use syn::visit_mut::{self, VisitMut};
+use syn::{
+    parse2, parse_macro_input, parse_quote, token, Block, Expr, ExprBlock, ExprBreak, ExprContinue,
+    ExprForLoop, ExprLit, ExprLoop, ExprRange, ExprUnary, ExprWhile, Ident, Label, Lifetime, Lit,
+    Macro, Pat, RangeLimits, Result, Stmt, Token, Type, UnOp,
+};
+use try_clause::TryClause;
+use unstable::Unstable;
+use warning::Warning;
+
+mod kw {
+    syn::custom_keyword!(must_break);
+    syn::custom_keyword!(skip);
+    syn::custom_keyword!(take);
+    syn::custom_keyword!(step);
+    syn::custom_keyword!(skipped);
+    syn::custom_keyword!(empty);
+    syn::custom_keyword!(then);
+    syn::custom_keyword!(collect);
+    syn::custom_keyword!(until);
+    syn::custom_keyword!(stopped);
+    syn::custom_keyword!(max);
+    syn::custom_keyword!(limit);
+    syn::custom_keyword!(with);
+    syn::custom_keyword!(overflow);
+    syn::custom_keyword!(cooldown);
+    syn::custom_keyword!(interrupt);
+    syn::custom_keyword!(within);
+    syn::custom_keyword!(timed_out);
+    syn::custom_keyword!(observe);
+    syn::custom_keyword!(defer);
+    syn::custom_keyword!(fold);
+    #[cfg(feature = "lending")]
+    syn::custom_keyword!(lend);
+}
+
+struct ForLoop {
+    label: Option<Label>,
+    fold: Option<kw::fold>,
+    collect: bool,
+    collection: Option<Type>,
+    header: Header,
+    guard: Option<Expr>,
+    until: Option<Expr>,
+    cooldown: Option<Expr>,
+    interrupt: Option<Expr>,
+    within: Option<Expr>,
+    observe: Option<Expr>,
+    on_try: Option<TryClause>,
+    state: Vec<Stmt>,
+    defer: Option<Block>,
+    #[cfg(feature = "resume")]
+    checkpoint: Option<checkpoint::Checkpoint>,
+    body: Block,
+    fallback: Option<Expr>,
+    then: Option<Block>,
+    no_break: Option<NoBreak>,
+    count: Option<Pat>,
+    empty: Option<Block>,
+    skipped: Option<Block>,
+    stopped: Option<Block>,
+    timed_out: Option<Block>,
+    plugins: Vec<plugin::Plugin>,
+}
+
+/// What a loop runs over.
+enum Header {
+    /// `pattern in iterable`, with the adapters of the header modifiers.
+    For {
+        var: Pat,
+        expr: Expr,
+        adapters: Vec<Adapter>,
+    },
+    /// `while condition`.
+    While(Expr),
+    /// `while name: condition && ...`, with named conjuncts.
+    WhileCauses(causes::Causes),
+    /// The condition of a do-while loop, which is not checked before the first iteration.
+    DoWhile(Expr),
+    /// `while let pattern = expression`.
+    WhileLet(Pat, Expr),
+    /// `while let pattern = expression && condition && ...`, a let chain with its conjuncts in
+    /// order, each either a `let` expression or a condition.
+    WhileChain(Vec<Expr>),
+    /// `pattern in lend lender`, over the items of a lending iterator.
+    #[cfg(feature = "lending")]
+    Lend { var: Pat, expr: Expr },
+}
+
+impl Header {
+    /// Parses the `pattern in iterable` header of `for_!`, with its header modifiers.
+    ///
+    /// `in ref iterable` and `in mut iterable` loop over a shared or mutable borrow of the
+    /// iterable, like `.iter()` and `.iter_mut()`.
+    fn parse_for(input: ParseStream) -> Result<Self> {
+        let var = parse_pat(input)?;
+        parse_in(input)?;
+        #[cfg(feature = "lending")]
+        if input.peek(kw::lend) {
+            input.parse::<kw::lend>()?;
+            let expr = parse_iterable(input)?;
+            if input.peek(Token![,]) {
+                return Err(syn::Error::new(
+                    input.span(),
+                    "header modifiers need an iterator, not a lending iterator",
+                ));
+            }
+            return Ok(Header::Lend { var, expr });
+        }
+        let expr = if input.peek(Token![ref]) {
+            input.parse::<Token![ref]>()?;
+            let expr = parse_iterable(input)?;
+            parse_quote! { &(#expr) }
+        } else if input.peek(Token![mut]) {
+            input.parse::<Token![mut]>()?;
+            let expr = parse_iterable(input)?;
+            parse_quote! { &mut (#expr) }
+        } else {
+            parse_iterable(input)?
+        };
+        let mut adapters = Vec::new();
+        while input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+            adapters.push(input.parse()?);
+        }
+        Ok(Header::For {
+            var,
+            expr,
+            adapters,
+        })
+    }
+}
+
+/// An iterator adapter applied to the iterable by a `, modifier n` in the loop header.
+#[derive(Clone)]
+enum Adapter {
+    Skip(Expr),
+    Take(Expr),
+    Step(Expr),
+}
+
+impl Parse for Adapter {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(kw::skip) {
+            input.parse::<kw::skip>()?;
+            Ok(Adapter::Skip(Expr::parse_without_eager_brace(input)?))
+        } else if lookahead.peek(kw::take) {
+            input.parse::<kw::take>()?;
+            Ok(Adapter::Take(Expr::parse_without_eager_brace(input)?))
+        } else if lookahead.peek(kw::step) {
+            input.parse::<kw::step>()?;
+            Ok(Adapter::Step(Expr::parse_without_eager_brace(input)?))
+        } else {
+            Err(ErrorCode::UnknownOption.tag(lookahead.error()))
+        }
+    }
+}
+
+impl Adapter {
+    fn apply(&self, iter: TokenStream2) -> TokenStream2 {
+        match self {
+            Adapter::Skip(n) => quote! { ::core::iter::Iterator::skip(#iter, #n) },
+            Adapter::Take(n) => quote! { ::core::iter::Iterator::take(#iter, #n) },
+            Adapter::Step(n) => quote! { ::core::iter::Iterator::step_by(#iter, #n) },
+        }
+    }
+}
+
+/// What happens when a loop finishes without a `break`.
+enum NoBreak {
+    /// `else { ... }`: the block runs.
+    Else(Block),
+    /// `must_break`: the loop is expected to always break, so finishing is a bug and panics.
+    MustBreak,
+    /// `unsafe must_break`: the caller guarantees the loop always breaks, so finishing is
+    /// undefined behavior.
+    UncheckedMustBreak,
+}
+
+impl Parse for NoBreak {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(Token![else]) {
+            input.parse::<Token![else]>()?;
+            Ok(NoBreak::Else(parse_else_block(input)?))
+        } else if lookahead.peek(kw::must_break) {
+            input.parse::<kw::must_break>()?;
+            Ok(NoBreak::MustBreak)
+        } else if lookahead.peek(Token![unsafe]) {
+            let unsafe_token = input.parse::<Token![unsafe]>()?;
+            input.parse::<kw::must_break>()?;
+            Unstable::UncheckedMustBreak.require(unsafe_token.span)?;
+            Ok(NoBreak::UncheckedMustBreak)
+        } else {
+            let message = "expected `else` block or `must_break` after loop body";
+            Err(ErrorCode::MissingElse.tag(input.error(message)))
+        }
+    }
+}
+
+impl NoBreak {
+    /// The span of the `else` block, which coverage tools report the branch taken when the
+    /// loop finishes under.
+    fn span(&self) -> Span {
+        match self {
+            NoBreak::Else(else_block) => else_block.brace_token.span.join(),
+            NoBreak::MustBreak | NoBreak::UncheckedMustBreak => Span::call_site(),
+        }
+    }
+}
+
+impl ToTokens for NoBreak {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        match self {
+            NoBreak::Else(else_block) => else_block.to_tokens(tokens),
+            NoBreak::MustBreak => {
+                let location = invocation_location();
+                tokens.extend(quote! {
+                    {
+                        ::core::panic!(
+                            "`for_!` loop declared `must_break` at {} finished without a `break`",
+                            #location,
+                        );
+                    }
+                })
+            }
+            NoBreak::UncheckedMustBreak => tokens.extend(quote! {
+                {
+                    unsafe { ::core::hint::unreachable_unchecked() }
+                }
+            }),
+        }
+    }
+}
+
+impl Parse for ForLoop {
+    fn parse(input: ParseStream) -> Result<Self> {
+        ForLoop::parse_with(input, |input| {
+            // a loop pasted with its keyword parses the same as one without
+            if input.peek(Token![for]) {
+                input.parse::<Token![for]>()?;
+            }
+            Header::parse_for(input)
+        })
+    }
+}
+
+impl ForLoop {
+    /// The warning about the loop, if any, once the `breaks` of its body are known.
+    ///
+    /// Besides iterables that are empty by their syntax and `break`s trapped in nested loops,
+    /// it warns about an `else` block that always
+    /// runs because nothing can stop the loop early, and about an empty `else` block on a loop
+    /// that uses nothing a plain loop lacks.
+    fn warning(&self, breaks: &Breaks) -> TokenStream2 {
+        if let Header::For { expr, .. } = &self.header {
+            if is_empty_literal(expr) {
+                return Warning::EmptyIterable.emit(expr.span());
+            }
+        }
+        // `break`s trapped in nested loops are almost always meant to leave the loop itself
+        if let (Some(span), false) = (breaks.trapped, breaks.leaving) {
+            return Warning::BreakInInnerLoop.emit(span);
+        }
+        let Some(NoBreak::Else(else_block)) = &self.no_break else {
+            return quote! {};
+        };
+        let span = else_block.brace_token.span.join();
+        // the `else` clauses of named conjuncts tell apart why the loop ended, and an `else`
+        // block binding the count knows how often the body ran, which a block after a plain
+        // loop cannot
+        let stoppable = self.until.is_some()
+            || self.interrupt.is_some()
+            || self.within.is_some()
+            || self.skipped.is_some()
+            || self.empty.is_some()
+            || self.count.is_some()
+            || matches!(self.header, Header::WhileCauses(_));
+        // both warnings suggest a plain loop instead, which only fits a loop using nothing else
+        if !self.is_plain() {
+            return quote! {};
+        }
+        if !else_block.stmts.is_empty() {
+            if !breaks.leaving && !stoppable && !returns_early(&self.body) {
+                return Warning::NeverBreaks.emit(span);
+            }
+        } else {
+            return Warning::EmptyElse.emit(span);
+        }
+        quote! {}
+    }
+
+    /// Whether the loop uses nothing but a header and a body, which a plain loop can do.
+    fn is_plain(&self) -> bool {
+        #[cfg(feature = "resume")]
+        if self.checkpoint.is_some() {
+            return false;
+        }
+        let irrefutable = match &self.header {
+            Header::For { var, .. } => is_irrefutable(var),
+            Header::While(_)
+            | Header::WhileCauses(_)
+            | Header::DoWhile(_)
+            | Header::WhileLet(..)
+            | Header::WhileChain(_) => true,
+            #[cfg(feature = "lending")]
+            Header::Lend { var, .. } => is_irrefutable(var),
+        };
+        irrefutable
+            && self.fold.is_none()
+            && !self.collect
+            && self.guard.is_none()
+            && self.until.is_none()
+            && self.cooldown.is_none()
+            && self.interrupt.is_none()
+            && self.within.is_none()
+            && self.observe.is_none()
+            && self.on_try.is_none()
+            && self.state.is_empty()
+            && self.defer.is_none()
+            && self.fallback.is_none()
+            && self.then.is_none()
+            && self.count.is_none()
+            && self.empty.is_none()
+            && self.skipped.is_none()
+            && self.stopped.is_none()
+            && self.plugins.is_empty()
+    }
+
+    /// Parses a loop whose header is parsed by `parse_header`; the label, `collect` and all
+    /// clauses after the header are shared by every form of loop.
+    fn parse_with(
+        input: ParseStream,
+        parse_header: impl FnOnce(ParseStream) -> Result<Header>,
+    ) -> Result<Self> {
+        let label = if input.peek(Lifetime) {
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        let fold = if input.peek(kw::fold) && !input.peek2(Token![in]) {
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        let collect = if input.peek(kw::collect) && !input.peek2(Token![in]) {
+            input.parse::<kw::collect>()?;
+            true
+        } else {
+            false
+        };
+        let collection = if collect && input.peek(Token![::]) {
+            input.parse::<Token![::]>()?;
+            input.parse::<Token![<]>()?;
+            let collection: Type = input.parse()?;
+            input.parse::<Token![>]>()?;
+            Some(collection)
+        } else {
+            None
+        };
+        let mut split = Split::new(input);
+        let header = parse_header(input).map_err(|err| split.header_error(err, input))?;
+        let mut guard = None;
+        let mut until = None;
+        let mut cooldown = None;
+        let mut interrupt = None;
+        let mut within = None;
+        let mut observe = None;
+        let mut on_try = None;
+        let mut state = Vec::new();
+        #[cfg(feature = "resume")]
+        let mut checkpoint = None;
+        loop {
+            if guard.is_none() && input.peek(Token![if]) {
+                input.parse::<Token![if]>()?;
+                guard = Some(Expr::parse_without_eager_brace(input)?);
+            } else if until.is_none() && input.peek(kw::until) {
+                input.parse::<kw::until>()?;
+                until = Some(Expr::parse_without_eager_brace(input)?);
+            } else if cooldown.is_none() && input.peek(kw::cooldown) {
+                input.parse::<kw::cooldown>()?;
+                let content;
+                syn::parenthesized!(content in input);
+                cooldown = Some(content.parse()?);
+            } else if interrupt.is_none() && input.peek(kw::interrupt) {
+                input.parse::<kw::interrupt>()?;
+                let content;
+                syn::parenthesized!(content in input);
+                interrupt = Some(content.parse()?);
+            } else if within.is_none() && input.peek(kw::within) {
+                input.parse::<kw::within>()?;
+                let content;
+                syn::parenthesized!(content in input);
+                within = Some(content.parse()?);
+            } else if observe.is_none() && input.peek(kw::observe) {
+                input.parse::<kw::observe>()?;
+                let content;
+                syn::parenthesized!(content in input);
+                observe = Some(content.parse()?);
+            } else if on_try.is_none() && TryClause::peek(input) {
+                on_try = Some(input.parse()?);
+            } else if state.is_empty() && input.peek(kw::with) {
+                input.parse::<kw::with>()?;
+                loop {
+                    state.push(parse_state(input)?);
+                    if !input.peek(Token![,]) {
+                        break;
+                    }
+                    input.parse::<Token![,]>()?;
+                }
+            } else {
+                #[cfg(feature = "resume")]
+                if checkpoint.is_none() && checkpoint::Checkpoint::peek(input) {
+                    let span = input.span();
+                    checkpoint = Some(input.parse()?);
+                    if !matches!(header, Header::For { .. }) {
+                        return Err(syn::Error::new(
+                            span,
+                            "`checkpoint` needs a `for` loop to count or key its items",
+                        ));
+                    }
+                    continue;
+                }
+                break;
+            }
+        }
+        split.header_end(input);
+        // in collect mode `emit` is rewritten once the `overflow` clause is known
+        let (emitting_body, body) = if collect {
+            let body: Group = input
+                .parse()
+                .map_err(|err| split.body_error(ErrorCode::MissingBody.tag(err)))?;
+            (Some(body), None)
+        } else {
+            let body = parse_body(input).map_err(|err| split.body_error(err))?;
+            (None, Some(body))
+        };
+        // attributes before a clause apply to its block
+        let mut attrs = parse_clause_attrs(input)?;
+        let defer = if input.peek(kw::defer) {
+            input.parse::<kw::defer>()?;
+            let defer = attach_attrs(std::mem::take(&mut attrs), input.parse()?);
+            attrs = parse_clause_attrs(input)?;
+            Some(defer)
+        } else {
+            None
+        };
+        let fallback =
+            if input.peek(Token![else]) && input.peek2(Token![continue]) && is_fallback(input) {
+                if let Some(attr) = attrs.first() {
+                    return Err(misplaced_attr(attr));
+                }
+                input.parse::<Token![else]>()?;
+                let continue_token = input.parse::<Token![continue]>()?;
+                input.parse::<kw::with>()?;
+                let fallback = Expr::parse_without_eager_brace(input)?;
+                input.parse::<Token![;]>()?;
+                if !matches!(header, Header::For { .. }) {
+                    return Err(syn::Error::new(
+                        continue_token.span,
+                        "`else continue with` needs a `for` loop to run over another iterable",
+                    ));
+                }
+                #[cfg(feature = "resume")]
+                if checkpoint.is_some() {
+                    return Err(syn::Error::new(
+                        continue_token.span,
+                        "`else continue with` cannot resume from a `checkpoint`",
+                    ));
+                }
+                attrs = parse_clause_attrs(input)?;
+                Some(fallback)
+            } else {
+                None
+            };
+        let then = if input.peek(kw::then) {
+            input.parse::<kw::then>()?;
+            let then = attach_attrs(std::mem::take(&mut attrs), input.parse()?);
+            attrs = parse_clause_attrs(input)?;
+            Some(then)
+        } else {
+            None
+        };
+        // in collect mode the loop evaluates to the collected values when there is no `else`,
+        // and a `then` block may stand on its own
+        let mut count = None;
+        let no_break = if (collect || then.is_some()) && !input.peek(Token![else]) {
+            None
+        } else if input.peek(Token![else]) && input.peek2(Token![|]) {
+            // `else |count|` binds the number of iterations for the block
+            input.parse::<Token![else]>()?;
+            input.parse::<Token![|]>()?;
+            count = Some(Pat::parse_single(input)?);
+            input.parse::<Token![|]>()?;
+            Some(NoBreak::Else(attach_attrs(
+                std::mem::take(&mut attrs),
+                input.parse()?,
+            )))
+        } else if let (Header::WhileCauses(causes), true) = (
+            &header,
+            input.peek(Token![else]) && input.peek2(Ident) && input.peek3(token::Brace),
+        ) {
+            let else_block = causes.parse_else(input)?;
+            Some(NoBreak::Else(attach_attrs(
+                std::mem::take(&mut attrs),
+                else_block,
+            )))
+        } else {
+            let no_break = input.parse().map_err(|err| split.else_error(err, input))?;
+            Some(match no_break {
+                NoBreak::Else(block) => {
+                    NoBreak::Else(attach_attrs(std::mem::take(&mut attrs), block))
+                }
+                no_break => {
+                    if let Some(attr) = attrs.first() {
+                        return Err(misplaced_attr(attr));
+                    }
+                    no_break
+                }
+            })
+        };
+        let mut empty = None;
+        let mut skipped = None;
+        let mut stopped = None;
+        let mut timed_out = None;
+        let mut overflow = None;
+        let mut plugins = Vec::new();
+        loop {
+            attrs.extend(parse_clause_attrs(input)?);
+            if empty.is_none() && input.peek(kw::empty) {
+                input.parse::<kw::empty>()?;
+                empty = Some(attach_attrs(std::mem::take(&mut attrs), input.parse()?));
+            } else if skipped.is_none() && input.peek(kw::skipped) {
+                input.parse::<kw::skipped>()?;
+                skipped = Some(attach_attrs(std::mem::take(&mut attrs), input.parse()?));
+            } else if stopped.is_none()
+                && (until.is_some() || interrupt.is_some())
+                && input.peek(kw::stopped)
+            {
+                input.parse::<kw::stopped>()?;
+                stopped = Some(attach_attrs(std::mem::take(&mut attrs), input.parse()?));
+            } else if timed_out.is_none() && within.is_some() && input.peek(kw::timed_out) {
+                input.parse::<kw::timed_out>()?;
+                timed_out = Some(attach_attrs(std::mem::take(&mut attrs), input.parse()?));
+            } else if overflow.is_none() && collection.is_some() && input.peek(kw::overflow) {
+                input.parse::<kw::overflow>()?;
+                // a single identifier, since a pattern would take the block for struct fields
+                let value = if input.peek(Token![_]) {
+                    let underscore = input.parse::<Token![_]>()?;
+                    quote! { #underscore }
+                } else {
+                    let value: Ident = input.parse()?;
+                    quote! { #value }
+                };
+                let block = attach_attrs(std::mem::take(&mut attrs), input.parse()?);
+                overflow = Some((value, block));
+            } else if plugin::Plugin::peek(input) {
+                plugins.push(plugin::Plugin::parse(std::mem::take(&mut attrs), input)?);
+            } else {
+                break;
+            }
+        }
+        if let Some(attr) = attrs.first() {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "expected a clause after these attributes",
+            ));
+        }
+        if input.peek(kw::overflow) {
+            return Err(syn::Error::new(
+                input.span(),
+                "`overflow` needs a bounded collection, as in `collect::<heapless::Vec<T, N>>`",
+            ));
+        }
+        let body = if let Some(body) = emitting_body {
+            let body = rewrite_emits(quote! { #body }, &|value| match &collection {
+                None => quote! { ::std::vec::Vec::push(&mut #COLLECTED, #value); },
+                Some(_) => {
+                    let (pat, handler) = match &overflow {
+                        Some((pat, handler)) => (quote! { #pat }, quote! { #handler }),
+                        None => (quote! { _ }, {
+                            let location = invocation_location();
+                            quote! {
+                                {
+                                    ::core::panic!("`for_!` collection is full in the loop at {}", #location);
+                                }
+                            }
+                        }),
+                    };
+                    quote! {
+                        if let ::core::result::Result::Err(#pat) =
+                            ::for_else::Collector::push(&mut #COLLECTED, #value)
+                            #handler
+                    }
+                }
+            });
+            parse2(body)?
+        } else {
+            body.unwrap()
+        };
+        let for_loop = ForLoop {
+            label,
+            fold,
+            collect,
+            collection,
+            header,
+            guard,
+            until,
+            cooldown,
+            interrupt,
+            within,
+            observe,
+            on_try,
+            state,
+            defer,
+            #[cfg(feature = "resume")]
+            checkpoint,
+            body,
+            fallback,
+            then,
+            no_break,
+            count,
+            empty,
+            skipped,
+            stopped,
+            timed_out,
+            plugins,
+        };
+        if !input.is_empty() {
+            let message = format!(
+                "unexpected `{}` after the loop; expected a clause or the end of the invocation",
+                input.cursor().token_tree().unwrap().0
+            );
+            return Err(ErrorCode::UnknownOption.error(input.span(), message));
+        }
+        fold::validate(&for_loop)?;
+        Ok(for_loop)
+    }
+}
+
+/// Parses one `pattern[: Type] = value` declaration of the `with` clause into a `let` statement.
+fn parse_state(input: ParseStream) -> Result<Stmt> {
+    let pat = Pat::parse_single(input)?;
+    let ty = if input.peek(Token![:]) {
+        let colon = input.parse::<Token![:]>()?;
+        let ty: Type = input.parse()?;
+        quote! { #colon #ty }
+    } else {
+        quote! {}
+    };
+    input.parse::<Token![=]>()?;
+    let value = Expr::parse_without_eager_brace(input)?;
+    Ok(parse_quote! { let #pat #ty = #value; })
+}
+
+/// Whether the `else continue` ahead is `else continue with fallback;` rather than an `else`
+/// clause that is a single `continue`.
+fn is_fallback(input: ParseStream) -> bool {
+    let fork = input.fork();
+    fork.parse::<Token![else]>().is_ok()
+        && fork.parse::<Token![continue]>().is_ok()
+        && fork.peek(kw::with)
+}
+
+/// The error for attributes before a clause without a block of its own.
+fn misplaced_attr(attr: &syn::Attribute) -> syn::Error {
+    syn::Error::new_spanned(
+        attr,
+        "attributes are only allowed before clauses with a block",
+    )
+}
+
+/// A variable the expansion keeps its state in, such as `#BREAK_FLAG`.
+///
+/// These variables have mixed-site hygiene, so that neither a variable of the user's code nor
+/// the variable of another loop expanded inside the body can stand in for them. A `break 'outer`
+/// rewritten by the outer loop then writes to the outer loop's variables, even once it is part
+/// of the body of an inner loop.
+struct Slot(&'static str);
+
+/// The flag recording whether the loop broke.
+const BREAK_FLAG: Slot = Slot("_for_else_break_occurred");
+/// The value of the `break` that ended a loop whose `break`s have a value.
+const BREAK_VALUE: Slot = Slot("_for_else_break_value");
+/// The values emitted by a `collect` loop.
+const COLLECTED: Slot = Slot("_for_else_collected");
+/// Whether an `until` condition, an interruption or the time limit stopped the loop.
+const STOPPED: Slot = Slot("_for_else_stopped");
+/// The number of iterations, for an `else |count|` block.
+const COUNT: Slot = Slot("_for_else_count");
+
+impl ToTokens for Slot {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        Ident::new(self.0, Span::mixed_site()).to_tokens(tokens);
+    }
+}
+
+/// The label of the block wrapping the body of a loop with a `defer` block, which a `continue`
+/// breaks out of. Like the slots, it is distinct from the label of a nested loop's block.
+fn iteration_label() -> Lifetime {
+    Lifetime::new("'_for_else_iteration", Span::mixed_site())
+}
+
+fn modify_breaks(body: &mut Block) -> Breaks {
+    replace_breaks(body, &|expr_break| {
+        // we need to replace a stement with another statement, but we have two statements instead,
+        // so we put them into a block to make it a single statement
+        let ExprBreak {
+            break_token, label, ..
+        } = expr_break;
+        quote_spanned! {expr_break.span()=>
+            {
+                #BREAK_FLAG = true;
+                #break_token #label;
+            }
+        }
+    })
+}
+
+/// Whether a `break` of the loop `body` labeled `label` has a value, walking the body like
+/// [`replace_breaks`] does.
+fn breaks_with_value(body: &Block, label: Option<&Lifetime>) -> bool {
+    let valued = std::cell::Cell::new(false);
+    replace_breaks(&mut body.clone(), &|expr_break| {
+        if expr_break.expr.is_some()
+            && (expr_break.label.is_none() || expr_break.label.as_ref() == label)
+        {
+            valued.set(true);
+        }
+        quote! { {} }
+    });
+    valued.get()
+}
+
+/// A `&'static str` expression holding the `file:line:column` of the macro invocation, for
+/// the messages of a loop that has to say which of many call sites it is.
+fn invocation_location() -> TokenStream2 {
+    quote! {
+        ::core::concat!(::core::file!(), ":", ::core::line!(), ":", ::core::column!())
+    }
+}
+
+/// The `if` running `else_block` when the loop did not break.
+///
+/// The `if` takes the span of the block, so that coverage tools report the fallback branch as
+/// a region of its own instead of folding it into the region of the whole invocation.
+fn else_branch(else_block: &Block) -> TokenStream2 {
+    let span = else_block.brace_token.span.join();
+    quote_spanned! {span=>
+        if !#BREAK_FLAG #else_block
+    }
+}
+
+/// Whether `body` can leave the enclosing function with a `return` or a `?`, which skips the
+/// `else` block just like a `break`. Closures, async blocks and nested items are not searched,
+/// since their `return`s and `?`s do not leave the loop, and neither are macro invocations.
+fn returns_early(body: &Block) -> bool {
+    struct Finder(bool);
+
+    impl<'ast> Visit<'ast> for Finder {
+        fn visit_expr(&mut self, expr: &'ast Expr) {
+            match expr {
+                Expr::Return(_) | Expr::Try(_) => self.0 = true,
+                Expr::Closure(_) | Expr::Async(_) => {}
+                _ => visit::visit_expr(self, expr),
+            }
+        }
+
+        fn visit_item(&mut self, _: &'ast syn::Item) {}
+    }
+
+    let mut finder = Finder(false);
+    finder.visit_block(body);
+    finder.0
+}
+
+/// The `break`s found in a loop body by [`replace_breaks`].
+#[derive(Default)]
+struct Breaks {
+    /// Whether any `break` leaves the loop.
+    leaving: bool,
+    /// The first unlabeled `break` that only leaves a loop nested in the body.
+    trapped: Option<Span>,
+}
+
+/// Replaces every `break` of the loop `body` with the tokens produced by `replacement`,
+/// which is given the `break` expression. Replacements reuse its `break` token, so that
+/// diagnostics about the `break` point at the body rather than at the whole invocation.
+///
+/// A `break` is found in any expression position, such as a `let` initializer, a function
+/// argument or a match guard. Closures, async blocks and nested items are not searched, since a
+/// `break` cannot leave them. The tokens of macro invocations are only searched for labeled
+/// `break`s, which is how a `break 'outer` in a nested `for_!` reaches the outer loop.
+fn replace_breaks(body: &mut Block, replacement: &dyn Fn(&ExprBreak) -> TokenStream2) -> Breaks {
+    let mut replacer = BreakReplacer {
+        replacement,
+        labels: Vec::new(),
+        nested: false,
+        breaks: Breaks::default(),
+    };
+    replacer.visit_block_mut(body);
+    replacer.breaks
+}
+
+/// Walks a loop body for [`replace_breaks`]. Inside loops and labeled blocks nested in the loop
+/// body, `labels` holds their labels: only breaks labeled with some other loop leave the loop
+/// body from there, and unlabeled breaks only do outside of nested loops.
+struct BreakReplacer<'a> {
+    replacement: &'a dyn Fn(&ExprBreak) -> TokenStream2,
+    labels: Vec<Lifetime>,
+    nested: bool,
+    breaks: Breaks,
+}
+
+impl BreakReplacer<'_> {
+    fn leaves_body(&self, label: &Option<Lifetime>) -> bool {
+        match label {
+            Some(label) => !self.labels.contains(label),
+            None => !self.nested,
+        }
+    }
+
+    /// The replacement of a `break` that leaves the loop body, as an expression.
+    fn replace_break(&mut self, expr_break: &ExprBreak) -> Expr {
+        self.breaks.leaving = true;
+        // the replacement is a statement, which a block turns into an expression
+        match parse2((self.replacement)(expr_break)).unwrap() {
+            Stmt::Expr(replaced, None) => replaced,
+            stmt => Expr::Block(ExprBlock {
+                attrs: Vec::new(),
+                label: None,
+                block: Block {
+                    brace_token: token::Brace(expr_break.span()),
+                    stmts: vec![stmt],
+                },
+            }),
+        }
+    }
+
+    /// Replaces the labeled `break`s in the tokens of a macro invocation that leave the loop
+    /// body, such as a `break 'outer` in the body of a nested `for_!`, which would otherwise skip
+    /// the `else` block without setting the flag. Unlabeled `break`s are left alone, since the
+    /// shape of the macro does not tell which loop they leave.
+    fn replace_in_tokens(&mut self, tokens: TokenStream2) -> TokenStream2 {
+        // a single pass over the tokens, where only a `break` followed by a label is parsed
+        let parser = |input: ParseStream| {
+            let mut replaced = TokenStream2::new();
+            while !input.is_empty() {
+                if input.peek(Token![break]) && input.peek2(Lifetime) {
+                    let fork = input.fork();
+                    if let Ok(mut expr_break) = fork.parse::<ExprBreak>() {
+                        if self.leaves_body(&expr_break.label) {
+                            input.advance_to(&fork);
+                            self.visit_break_value(&mut expr_break);
+                            self.replace_break(&expr_break).to_tokens(&mut replaced);
+                            continue;
+                        }
+                    }
+                }
+                match input.parse::<TokenTree>()? {
+                    TokenTree::Group(group) => {
+                        let mut new =
+                            Group::new(group.delimiter(), self.replace_in_tokens(group.stream()));
+                        new.set_span(group.span());
+                        replaced.extend([TokenTree::Group(new)]);
+                    }
+                    token => replaced.extend([token]),
+                }
+            }
+            Ok(replaced)
+        };
+        parser.parse2(tokens).unwrap()
+    }
+
+    fn visit_break_value(&mut self, expr_break: &mut ExprBreak) {
+        if let Some(value) = &mut expr_break.expr {
+            self.visit_expr_mut(value);
+        }
+    }
+
+    /// Walks `body` as the body of a nested loop labeled `label`.
+    fn visit_nested(&mut self, label: Option<&Label>, body: &mut Block) {
+        let nested = std::mem::replace(&mut self.nested, true);
+        let len = self.labels.len();
+        self.labels.extend(label.map(|label| label.name.clone()));
+        self.visit_block_mut(body);
+        self.labels.truncate(len);
+        self.nested = nested;
+    }
+}
+
+impl VisitMut for BreakReplacer<'_> {
+    fn visit_block_mut(&mut self, block: &mut Block) {
+        for stmt in &mut block.stmts {
+            // a `break` statement is replaced with the statement produced by `replacement`
+            if let Stmt::Expr(Expr::Break(expr_break), _) = stmt {
+                if self.leaves_body(&expr_break.label) {
+                    self.visit_break_value(expr_break);
+                    self.breaks.leaving = true;
+                    let replaced = (self.replacement)(expr_break);
+                    *stmt = parse2(replaced).unwrap();
+                    continue;
+                }
+            }
+            self.visit_stmt_mut(stmt);
+        }
+    }
+
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        match expr {
+            Expr::Break(expr_break) => {
+                self.visit_break_value(expr_break);
+                if self.leaves_body(&expr_break.label) {
+                    *expr = self.replace_break(expr_break);
+                } else if expr_break.label.is_none() {
+                    self.breaks
+                        .trapped
+                        .get_or_insert(expr_break.break_token.span);
+                }
+            }
+            Expr::ForLoop(ExprForLoop {
+                label, expr, body, ..
+            }) => {
+                self.visit_expr_mut(expr);
+                self.visit_nested(label.as_ref(), body);
+            }
+            Expr::While(ExprWhile {
+                label, cond, body, ..
+            }) => {
+                self.visit_expr_mut(cond);
+                self.visit_nested(label.as_ref(), body);
+            }
+            Expr::Loop(ExprLoop { label, body, .. }) => self.visit_nested(label.as_ref(), body),
+            // an unlabeled `break` cannot leave a labeled block, so only its label is nested
+            Expr::Block(ExprBlock {
+                label: Some(label),
+                block,
+                ..
+            }) => {
+                self.labels.push(label.name.clone());
+                self.visit_block_mut(block);
+                self.labels.pop();
+            }
+            Expr::Closure(_) | Expr::Async(_) => {}
+            _ => visit_mut::visit_expr_mut(self, expr),
+        }
+    }
+
+    fn visit_macro_mut(&mut self, mac: &mut Macro) {
+        let len = self.labels.len();
+        defined_labels(mac.tokens.clone(), &mut self.labels);
+        mac.tokens = self.replace_in_tokens(std::mem::take(&mut mac.tokens));
+        self.labels.truncate(len);
+    }
+
+    fn visit_item_mut(&mut self, _: &mut syn::Item) {}
+}
+
+/// Adds the labels defined in `tokens`, as `'label:`, to `labels`.
+fn defined_labels(tokens: TokenStream2, labels: &mut Vec<Lifetime>) {
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+    for (i, token) in tokens.iter().enumerate() {
+        match (token, tokens.get(i + 1), tokens.get(i + 2)) {
+            (TokenTree::Group(group), _, _) => defined_labels(group.stream(), labels),
+            (
+                TokenTree::Punct(quote),
+                Some(TokenTree::Ident(name)),
+                Some(TokenTree::Punct(colon)),
+            ) if quote.as_char() == '\'' && colon.as_char() == ':' => {
+                labels.push(Lifetime {
+                    apostrophe: quote.span(),
+                    ident: name.clone(),
+                });
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Replaces every `continue` of the loop labeled `label` in `body` with `replacement`, such as
+/// a `break` out of the `'_for_else_iteration` block wrapping the body. Inside loops nested in
+/// the body, only `continue`s naming `label` are replaced.
+///
+/// Like a `break` in [`replace_breaks`], a `continue` is found in any expression position, and
+/// closures, async blocks and nested items are not searched. Unlike a `break`, a `continue` is
+/// also replaced in the tokens of macro invocations when it is unlabeled, such as a `continue` in
+/// the `else` block of a nested `for_!`, since a `continue` left there would fail to compile
+/// inside the `'_for_else_iteration` block.
+fn replace_continues(body: &mut Block, label: &Lifetime, replacement: &Expr) {
+    ContinueReplacer {
+        label,
+        replacement,
+        nested: false,
+    }
+    .visit_block_mut(body);
+}
+
+/// Walks a loop body for [`replace_continues`], with `nested` set inside nested loops.
+struct ContinueReplacer<'a> {
+    label: &'a Lifetime,
+    replacement: &'a Expr,
+    nested: bool,
+}
+
+impl ContinueReplacer<'_> {
+    fn replaces(&self, expr_continue: &ExprContinue) -> bool {
+        match &expr_continue.label {
+            Some(label) => label == self.label,
+            None => !self.nested,
+        }
+    }
+
+    /// Walks `body` as the body of a nested loop.
+    fn visit_nested(&mut self, body: &mut Block) {
+        let nested = std::mem::replace(&mut self.nested, true);
+        self.visit_block_mut(body);
+        self.nested = nested;
+    }
+
+    /// Replaces the `continue`s in the tokens of a macro invocation, which are not parsed, so
+    /// loop bodies are told apart by their shape: the block after `for`, `while` or `loop`, and
+    /// in the invocation of a loop macro of this crate, its first block and its `defer` block.
+    fn replace_in_tokens(&mut self, tokens: TokenStream2, loop_macro: bool) -> TokenStream2 {
+        let parser = |input: ParseStream| {
+            let mut replaced = TokenStream2::new();
+            // the next block is the body of a loop, unless an expression such as an `if` comes
+            // first, whose block is not nested
+            let mut body_next = loop_macro;
+            let mut expression_next = false;
+            let mut skip_next = false;
+            let mut macro_next = false;
+            while !input.is_empty() {
+                if input.peek(Token![continue]) {
+                    let expr_continue = input.parse::<ExprContinue>()?;
+                    if self.replaces(&expr_continue) {
+                        self.replacement.to_tokens(&mut replaced);
+                    } else {
+                        expr_continue.to_tokens(&mut replaced);
+                    }
+                    continue;
+                }
+                match input.parse::<TokenTree>()? {
+                    TokenTree::Ident(ident) => {
+                        match ident.to_string().as_str() {
+                            "for" | "while" | "loop" | "defer" => body_next = true,
+                            "if" | "match" | "unsafe" | "else" => expression_next = true,
+                            "async" => skip_next = true,
+                            name => macro_next = LOOP_MACROS.contains(&name),
+                        }
+                        replaced.extend([TokenTree::Ident(ident)]);
+                    }
+                    TokenTree::Group(group) => {
+                        let brace = group.delimiter() == Delimiter::Brace;
+                        let stream = if std::mem::take(&mut macro_next) {
+                            self.replace_in_tokens(group.stream(), true)
+                        } else if !brace {
+                            self.replace_in_tokens(group.stream(), false)
+                        } else if std::mem::take(&mut skip_next) {
+                            group.stream()
+                        } else if std::mem::take(&mut expression_next) || !body_next {
+                            self.replace_in_tokens(group.stream(), false)
+                        } else {
+                            body_next = false;
+                            let nested = std::mem::replace(&mut self.nested, true);
+                            let stream = self.replace_in_tokens(group.stream(), false);
+                            self.nested = nested;
+                            stream
+                        };
+                        let mut new = Group::new(group.delimiter(), stream);
+                        new.set_span(group.span());
+                        replaced.extend([TokenTree::Group(new)]);
+                    }
+                    TokenTree::Punct(punct) => {
+                        macro_next &= punct.as_char() == '!';
+                        replaced.extend([TokenTree::Punct(punct)]);
+                    }
+                    token => replaced.extend([token]),
+                }
+            }
+            Ok(replaced)
+        };
+        parser.parse2(tokens).unwrap()
+    }
+}
+
+/// The loop macros of this crate, whose invocations start with the body of a loop.
+const LOOP_MACROS: &[&str] = &[
+    "bisect_loop_",
+    "cfor_",
+    "chain_for_",
+    "do_while_",
+    "eventually_",
+    "for_",
+    "for_await_",
+    "for_stdin_",
+    "forelse",
+    "fs_search_",
+    "loop_",
+    "loop_else",
+    "machine_loop_",
+    "merge_for_",
+    "paginate_for_",
+    "par_for_",
+    "read_chunks_",
+    "regex_search_",
+    "retry_",
+    "rows_for_",
+    "search_",
+    "spawn_for_",
+    "terminal_for_",
+    "try_for_",
+    "watch_for_",
+    "while_let_",
+    "worklist_for_",
+];
+
+impl VisitMut for ContinueReplacer<'_> {
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        match expr {
+            Expr::Continue(expr_continue) => {
+                if self.replaces(expr_continue) {
+                    *expr = self.replacement.clone();
+                }
+            }
+            Expr::ForLoop(ExprForLoop { expr, body, .. }) => {
+                self.visit_expr_mut(expr);
+                self.visit_nested(body);
+            }
+            Expr::While(ExprWhile { cond, body, .. }) => {
+                self.visit_expr_mut(cond);
+                self.visit_nested(body);
+            }
+            Expr::Loop(ExprLoop { body, .. }) => self.visit_nested(body),
+            Expr::Closure(_) | Expr::Async(_) => {}
+            _ => visit_mut::visit_expr_mut(self, expr),
+        }
+    }
+
+    fn visit_macro_mut(&mut self, mac: &mut Macro) {
+        let loop_macro = mac
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| LOOP_MACROS.contains(&segment.ident.to_string().as_str()));
+        mac.tokens = self.replace_in_tokens(std::mem::take(&mut mac.tokens), loop_macro);
+    }
+
+    fn visit_item_mut(&mut self, _: &mut syn::Item) {}
+}
+
+/// The `for_!` procedural macro with enhanced loop control.
+///
+/// This macro is an extension of the standard `for` loop in Rust. It allows users to
+/// have an additional `else` block that executes if the loop completed without encountering a `break` statement.
+///
+/// The `for` keyword may be kept in front of the header, so that an existing loop can be pasted
+/// into the macro as it is: `for_! { for i in 0..10 { ... } else { ... } }`.
+///
+/// # Syntax
+///
+/// ```ignore
+/// for_! { variable in expression {
+///     // loop body
+/// } else {
+///     // else block
+/// }}
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// use for_else::for_;
+///
+/// # fn some_condition(i: u32) -> bool {
+/// #     true
+/// # }
+/// # fn main() {
+/// for_! { i in 0..10 {
+///     if some_condition(i) {
+///         // Some action
+///         break;
+///     }
+/// } else {
+///     // This block executes if the loop never breaks
+/// }}
+/// # }
+/// ```
+///
+/// In the example above, if `some_condition(i)` never evaluates to `true` for any `i` in the range `0..10`,
+/// then the `else` block will be executed after the loop completes.
+///
+/// # Header modifiers
+///
+/// The iterable is any expression that does not end in a block, so it may use `.await` or `?`,
+/// which are evaluated once before the loop as in a native `for` loop. The iterable can be
+/// followed by comma-separated `skip n`, `take n` and `step n` modifiers,
+/// which apply [`Iterator::skip`], [`Iterator::take`] and [`Iterator::step_by`] in the order
+/// they are written:
+///
+/// ```rust
+/// use for_else::for_;
+///
+/// let lines = ["header", "a", "b", "c", "d"];
+/// let mut checked = vec![];
+///
+/// for_! { line in lines, skip 1, take 2 {
+///     checked.push(line);
+///     if line.is_empty() {
+///         break;
+///     }
+/// } else {
+///     assert_eq!(checked, ["a", "b"]);
+/// }}
+/// ```
+///
+/// # Borrowing the iterable
+///
+/// `in ref iterable` loops over a shared borrow of the iterable and `in mut iterable` over a
+/// mutable one, like `.iter()` and `.iter_mut()` on a collection, so the collection is not moved
+/// into the loop and stays usable afterwards.
+///
+/// ```rust
+/// use for_else::for_;
+///
+/// let mut prices = vec![120, 80, 95];
+///
+/// for_! { price in mut prices {
+///     if *price == 0 {
+///         break;
+///     }
+///     *price = *price * 9 / 10;
+/// } else {
+///     println!("Every price is discounted.");
+/// }}
+///
+/// for_! { &price in ref prices {
+///     if price > 100 {
+///         println!("{} is still expensive", price);
+///         break;
+///     }
+/// } else {
+///     println!("Everything is affordable.");
+/// }}
+///
+/// assert_eq!(prices, [108, 72, 85]);
+/// ```
+///
+/// # Lending iterators
+///
+/// With the `lending` feature, `in lend lender` loops over a
+/// [`LendingIterator`](../for_else/trait.LendingIterator.html), whose items may borrow from the
+/// iterator itself, such as the records of a zero-copy parser reusing one buffer. Header
+/// modifiers, `fold`, `checkpoint` and `else continue with` need a plain iterator.
+///
+/// ```rust
+/// use for_else::{for_, LendingIterator};
+///
+/// /// Yields every record of `data` in turn, uppercased in a buffer it reuses.
+/// struct Records<'d> {
+///     data: std::str::Split<'d, char>,
+///     buffer: String,
+/// }
+///
+/// impl LendingIterator for Records<'_> {
+///     type Item<'a> = &'a str where Self: 'a;
+///
+///     fn next(&mut self) -> Option<&str> {
+///         let record = self.data.next()?;
+///         self.buffer.clear();
+///         self.buffer.push_str(&record.to_uppercase());
+///         Some(&self.buffer)
+///     }
+/// }
+///
+/// let records = Records { data: "ok;ok;fail;ok".split(';'), buffer: String::new() };
+/// for_! { record in lend records {
+///     if record == "FAIL" {
+///         println!("A record failed.");
+///         break;
+///     }
+/// } else {
+///     println!("All records are fine.");
+/// }}
+/// ```
+///
+/// # Filtering with patterns
+///
+/// The loop pattern may be refutable, in which case items that do not match it are skipped
+/// without running the body, as if they were not in the iterable.
+///
+/// An `if guard` after the iterable skips the items for which the condition is false in the
+/// same way, after they are bound to the pattern, as in `for_! { n in numbers if n % 2 == 0 {`.
+/// Skipped items neither run the body nor count as iterations, and they cannot break the loop.
+///
+/// ```rust
+/// use for_else::for_;
+///
+/// let readings = [None, Some(12), None, Some(40)];
+///
+/// for_! { Some(value) in readings {
+///     if value > 30 {
+///         println!("Reading above threshold: {}", value);
+///         break;
+///     }
+/// } else {
+///     println!("All readings are within range.");
+/// }}
+/// ```
+///
+/// # Skipped iterations
+///
+/// An optional `skipped` block after the `else` block runs instead of it when the loop did
+/// iterate, but every iteration ended in a `continue`, so no item made it through the whole
+/// body. A loop over no items at all still runs the `else` block, or the `empty` block below.
+///
+/// ```rust
+/// use for_else::for_;
+///
+/// let readings: [Option<f64>; 3] = [None, None, None];
+///
+/// for_! { reading in readings {
+///     let Some(value) = reading else {
+///         continue;
+///     };
+///     if value > 100.0 {
+///         println!("Too hot: {}", value);
+///         break;
+///     }
+/// } else {
+///     println!("All readings are fine.");
+/// } skipped {
+///     println!("No valid readings at all.");
+/// }}
+/// ```
+///
+/// # Empty loops
+///
+/// An optional `empty` block after the `else` block runs instead of it when the body never ran,
+/// so that a loop over no items can be told apart from one that went through all of them
+/// without a `break`. Items that do not match a refutable pattern never reach the body, so a
+/// loop whose items were all filtered out by its pattern counts as empty too.
+///
+/// ```rust
+/// use for_else::for_;
+///
+/// let candidates: Vec<u32> = Vec::new();
+///
+/// for_! { candidate in candidates {
+///     if candidate % 7 == 0 {
+///         println!("Found {}", candidate);
+///         break;
+///     }
+/// } else {
+///     println!("None of the candidates matched.");
+/// } empty {
+///     println!("There were no candidates.");
+/// }}
+/// ```
+///
+/// # Counting iterations
+///
+/// `else |count|` binds the number of iterations to `count` in the `else` block, as a `usize`,
+/// which is how many times the body ran, whether it went through to the end or `continue`d.
+/// Items that do not match a refutable pattern are not counted. The counter is only kept for a
+/// loop whose `else` block binds it.
+///
+/// ```rust
+/// use for_else::for_;
+///
+/// let files = ["a.txt", "b.txt", "c.txt"];
+///
+/// for_! { file in files {
+///     if file.ends_with(".rs") {
+///         println!("Found {}", file);
+///         break;
+///     }
+/// } else |count| {
+///     println!("Looked at {} files, none of them Rust.", count);
+/// }}
+/// ```
+///
+/// # Stopping early
+///
+/// An `until` condition after the iterable is checked before every iteration, and once it
+/// holds the loop stops without taking another item. The optional `stopped` block after the
+/// `else` block runs in that case, which keeps the `else` block for a loop that ran out of items.
+///
+/// ```rust
+/// use for_else::for_;
+///
+/// let mut checked = 0;
+///
+/// for_! { n in 1..1000 until checked == 100 {
+///     checked += 1;
+///     if n * n == 625 {
+///         println!("Found the root: {}", n);
+///         break;
+///     }
+/// } else {
+///     println!("No root in range.");
+/// } stopped {
+///     println!("Checked enough numbers.");
+/// }}
+/// ```
+///
+/// A deadline does not need an `until` condition comparing [`Instant`](std::time::Instant)s,
+/// as `within` below keeps the time and has its own `timed_out` block.
+///
+/// `interrupt(source)` stops the loop in the same way once an
+/// [`Interruptible`](../for_else/trait.Interruptible.html) source is interrupted, such as an
+/// `AtomicBool` set by a signal handler or a cancellation token, so the same loop can be
+/// stopped by whatever the surrounding program uses. The source is evaluated once before the
+/// loop, and asked before every iteration.
+///
+/// ```rust
+/// use for_else::for_;
+/// use std::sync::atomic::{AtomicBool, Ordering};
+///
+/// let shutdown = AtomicBool::new(false);
+///
+/// for_! { job in ["a", "b", "c"] interrupt(&shutdown) {
+///     if job == "b" {
+///         // a signal handler would do this from another thread
+///         shutdown.store(true, Ordering::Release);
+///     }
+/// } else {
+///     println!("All jobs done.");
+/// } stopped {
+///     println!("Shutting down before all jobs were done.");
+/// }}
+/// ```
+///
+/// # Time limits
+///
+/// `within(duration)` after the iterable gives the whole loop a wall-clock budget of a
+/// [`Duration`](std::time::Duration), counted from the start of the loop. The clock is
+/// checked before every iteration, like an `until` condition, so a long iteration is not cut
+/// short, but no iteration starts once the time is up. The optional `timed_out` block after
+/// the `else` block then runs instead of it, and instead of the `stopped` block, which is
+/// left for an `until` condition or an interruption. Instead of a duration, `within` also takes
+/// a shared [`LoopBudget`](../for_else/struct.LoopBudget.html), which times the loop out once
+/// the budget runs out of time or iterations, or any other
+/// [`TimeLimit`](../for_else/trait.TimeLimit.html).
+///
+/// ```rust
+/// use for_else::for_;
+/// use std::time::Duration;
+///
+/// # let records = 0..1_000_000u64;
+/// for_! { record in records within(Duration::from_millis(50)) {
+///     if record == 42 {
+///         println!("Found the record.");
+///         break;
+///     }
+/// } else {
+///     println!("No such record.");
+/// } timed_out {
+///     println!("Gave up searching.");
+/// }}
+/// ```
+///
+/// The clause works the same after the condition of a `while` loop in [`loop_else!`], which
+/// keeps a retry loop's condition about the retries alone:
+///
+/// ```rust
+/// use for_else::loop_else;
+/// use std::time::Duration;
+///
+/// # fn connect() -> Result<(), ()> { Ok(()) }
+/// let mut attempts = 0;
+/// loop_else! { while attempts < 5 within(Duration::from_secs(5)) {
+///     attempts += 1;
+///     if connect().is_ok() {
+///         break;
+///     }
+/// } else {
+///     println!("Could not connect after {} attempts.", attempts);
+/// } timed_out {
+///     println!("Could not connect in time.");
+/// }}
+/// ```
+///
+/// # Cooling down between iterations
+///
+/// `cooldown(duration)` after the iterable sleeps for the [`Duration`](std::time::Duration)
+/// before every iteration but the first. The sleep happens once the next item has been taken,
+/// so there is no sleep after the last iteration. It can be combined with `until`. Instead of a
+/// fixed duration, `cooldown` also takes any [`Backoff`](../for_else/trait.Backoff.html)
+/// strategy, such as an exponential backoff between retries.
+///
+/// ```rust
+/// use for_else::for_;
+/// use std::time::Duration;
+///
+/// # fn try_connect(attempt: u32) -> bool { attempt == 2 }
+/// for_! { attempt in 1..=3 cooldown(Duration::from_millis(10)) {
+///     if try_connect(attempt) {
+///         println!("Connected on attempt {}", attempt);
+///         break;
+///     }
+/// } else {
+///     println!("Could not connect.");
+/// }}
+/// ```
+///
+/// # Resuming from a checkpoint
+///
+/// With the `resume` feature, `checkpoint(store, every n)` after the iterable saves the progress
+/// of the loop to a [`CheckpointStore`](../for_else/trait.CheckpointStore.html) every `n` items,
+/// and a later run of the loop with the same store skips the items walked before. The progress
+/// is the number of items taken from the iterable, or with `checkpoint(store, every n, key expr)`
+/// the value of `expr` for the last item, which may use the variables of the pattern. Keys are
+/// expected to increase along the iterable, and a resumed loop skips the items whose key is not
+/// greater than the saved one, so it copes with items that are removed between runs.
+///
+/// The store is taken by value, so pass `&mut store` to keep using it after the loop. Once the
+/// loop finishes, with or without a `break`, the checkpoint is cleared, and the `else` block
+/// runs just as without a checkpoint. A loop stopped by its `until` condition saves its final
+/// progress instead, while a loop left by a `return`, a `?` or a panic keeps the last checkpoint.
+///
+/// ```rust
+/// use for_else::for_;
+///
+/// // a run that was interrupted after the records up to id 2
+/// let mut store = Some(2);
+///
+/// for_! { (id, record) in [(1, "ok"), (2, "ok"), (3, "ok"), (4, "bad")]
+///     checkpoint(&mut store, every 100, key id)
+/// {
+///     if record == "bad" {
+///         println!("Record {} is broken", id);
+///         break;
+///     }
+/// } else {
+///     println!("All records are fine.");
+/// }}
+///
+/// assert_eq!(store, None);
+/// ```
+///
+/// # Loop-local state
+///
+/// `with name = value` after the iterable declares a variable for the body and the clauses,
+/// which is scoped to the loop instead of the surrounding code. Several variables are separated
+/// by commas, and each may be `mut` and have a type, as in `with mut best: Option<u32> = None`.
+///
+/// ```rust
+/// use for_else::for_;
+///
+/// let scores = [("ann", 71), ("bob", 93), ("cid", 88)];
+///
+/// for_! { (name, score) in scores with mut best = None, threshold = 95 {
+///     if score >= threshold {
+///         println!("{} reached the threshold", name);
+///         break;
+///     }
+///     if best.is_none_or(|(_, best)| score > best) {
+///         best = Some((name, score));
+///     }
+/// } else {
+///     println!("Nobody reached {}; the best was {:?}", threshold, best);
+/// }}
+/// ```
+///
+/// # Observing the loop
+///
+/// `observe(observer)` after the iterable reports the progress of the loop to a
+/// [`LoopObserver`](../for_else/trait.LoopObserver.html): every iteration that runs the body,
+/// and whether the loop broke, completed or never ran its body. The observer is taken by value,
+/// so pass `&mut observer` to inspect it after the loop.
+///
+/// # Clause plugins
+///
+/// Other crates can add clauses to the loop with a macro. A clause written `path! { ... }` after
+/// the `else` block is not expanded in place. Instead, the loop invokes the macro as
+/// `path! { @for_else hook state { ... } }` at each of these hooks, with the tokens of the clause:
+///
+/// - `setup`, before the loop;
+/// - `iteration`, before the body runs, for the same iterations an observer sees;
+/// - `break` after the loop if it broke, or else `complete`.
+///
+/// `state` is the same identifier in every hook of a clause. The `setup` hook can bind it to
+/// keep state for the later hooks.
+///
+/// ```rust
+/// use for_else::for_;
+///
+/// macro_rules! count {
+///     (@for_else setup $state:ident { $total:ident }) => {
+///         let mut $state = 0;
+///     };
+///     (@for_else iteration $state:ident { $total:ident }) => {
+///         $state += 1;
+///     };
+///     (@for_else $outcome:tt $state:ident { $total:ident }) => {
+///         $total += $state;
+///     };
+/// }
+///
+/// let mut total = 0;
+/// for_! { n in [1, 3, 4, 5] {
+///     if n % 2 == 0 {
+///         break;
+///     }
+/// } else {
+///     println!("No even number found.");
+/// } count! { total }}
+///
+/// assert_eq!(total, 3);
+/// ```
+///
+/// # Deferred code
+///
+/// A `defer { ... }` block right after the body runs at the end of every iteration, whether
+/// the body ran to its end or used `continue`, but not after a `break`. It suits releasing
+/// per-iteration resources or advancing a cursor without repeating that code before every
+/// `continue`.
+///
+/// ```rust
+/// use for_else::for_;
+///
+/// let lines = ["a", "", "b"];
+/// let mut line_number = 1;
+///
+/// for_! { line in lines {
+///     if line.is_empty() {
+///         continue;
+///     }
+///     println!("{}: {}", line_number, line);
+/// } defer {
+///     line_number += 1;
+/// } else {
+///     println!("Read {} lines.", line_number - 1);
+/// }}
+///
+/// assert_eq!(line_number, 4);
+/// ```
+///
+/// # `?` in the body
+///
+/// `try continue` after the iterable makes a `?` in the body go on with the next item when it
+/// meets `None` or an `Err`, and `try break` makes it break out of the loop, skipping the
+/// `else` block, instead of returning from the enclosing function. Any
+/// [`Fallible`](../for_else/trait.Fallible.html) value can be unwrapped this way; the error of
+/// a `Result` is dropped. A `?` inside a closure, an async block or a nested macro invocation
+/// keeps its usual meaning.
+///
+/// ```rust
+/// use for_else::for_;
+/// use std::collections::HashMap;
+///
+/// let emails = HashMap::from([(1, "ann@example.com"), (3, "bob")]);
+///
+/// for_! { id in [1, 2, 3] try continue {
+///     let domain = emails.get(&id)?.split_once('@')?.1;
+///     if domain != "example.com" {
+///         println!("User {} is outside.", id);
+///         break;
+///     }
+/// } else {
+///     println!("All known users are inside.");
+/// }}
+/// ```
+///
+/// # Breaking out of nested loops
+///
+/// A `break` inside a loop nested in the body only leaves that inner loop, so it never skips the
+/// `else` block. Label the `for_!` loop and use `break 'label` to leave it from an inner loop.
+/// The macro warns when the only `break`s in the body are unlabeled ones in nested loops.
+///
+/// The inner loop may be another `for_!` or any other macro invocation: a `break 'label` in its
+/// tokens still skips the `else` block of the outer loop.
+///
+/// ```rust
+/// use for_else::for_;
+///
+/// for_! { 'rows: row in [[1, 2], [3, 4]] {
+///     for cell in row {
+///         if cell == 3 {
+///             println!("Found 3 in {:?}", row);
+///             break 'rows;
+///         }
+///     }
+/// } else {
+///     println!("No 3 found.");
+/// }}
+/// ```
+///
+/// Instead of a block, the `else` clause may be a single expression ended by `;`, which suits
+/// the `continue 'outer;`, `break 'outer;` or `return None;` of a nested search.
+///
+/// ```rust
+/// use for_else::for_;
+///
+/// let groups = [vec![1, 3], vec![2, 5], vec![7]];
+/// let mut with_even = Vec::new();
+///
+/// 'groups: for (i, group) in groups.iter().enumerate() {
+///     for_! { n in group {
+///         if n % 2 == 0 {
+///             break;
+///         }
+///     } else continue 'groups; }
+///     with_even.push(i);
+/// }
+///
+/// assert_eq!(with_even, [1]);
+/// ```
+///
+/// # Breaking with a value
+///
+/// When a `break` of the loop has a value, the loop is an expression that evaluates to the
+/// value of the `break` that ended it, or to the value of the `else` block if it finished
+/// without one, so both have the same type. Every `break` of such a loop needs a value, and so
+/// do the `stopped` and `timed_out` blocks of a loop with those clauses. A `break` naming an
+/// enclosing loop keeps its value for that loop, and `collect` and `fold` loops cannot break
+/// with a value.
+///
+/// ```rust
+/// use for_else::for_;
+///
+/// let users = ["ada", "grace", "linus"];
+///
+/// let position = for_! { (i, user) in users.iter().enumerate() {
+///     if user.starts_with('g') {
+///         break i;
+///     }
+/// } else {
+///     users.len()
+/// }};
+///
+/// assert_eq!(position, 1);
+/// ```
+///
+/// # Running code after a break
+///
+/// A `then` block before the `else` block runs once the loop is left with a `break`, which
+/// tells the two outcomes apart without a flag of your own. Either of the blocks may be left
+/// out, but not both. In a `collect` loop or a loop breaking with a value, the `then` block
+/// runs before the loop evaluates to the collected values or to the value of the `break`.
+///
+/// ```rust
+/// use for_else::for_;
+///
+/// # let hosts = ["db1", "db2"];
+/// # fn ping(host: &str) -> bool { host == "db2" }
+/// for_! { host in hosts {
+///     if ping(host) {
+///         break;
+///     }
+/// } then {
+///     println!("Found a live host.");
+/// } else {
+///     println!("Every host is down.");
+/// }}
+/// ```
+///
+/// # Falling back to another source
+///
+/// `else continue with fallback;` after the body runs the same body over a second iterable
+/// once the first one is exhausted without a `break`, and the `else` block that follows only
+/// runs if that search completes without a `break` too. The fallback expression is evaluated
+/// only when it is needed, and header modifiers apply to both iterables.
+///
+/// ```rust
+/// use for_else::for_;
+///
+/// let cache = ["alpha", "beta"];
+/// let database = vec!["gamma", "delta"];
+///
+/// for_! { name in cache {
+///     if name.starts_with('d') {
+///         println!("Found {}", name);
+///         break;
+///     }
+/// } else continue with database; else {
+///     println!("No name starts with d.");
+/// }}
+/// ```
+///
+/// # Collecting values
+///
+/// Starting the header with `collect` turns the loop into an expression that evaluates to a
+/// `Vec` of the values passed to `emit value;` statements in the body. A `break` stops the loop
+/// and keeps the values emitted so far. If the loop finishes without a `break`, the `else` block,
+/// when present, is evaluated instead and its value replaces the collected values.
+///
+/// `emit` is a statement, so it always ends with a `;`, including in match arms. Inside the
+/// body, `emit` followed by a value is always an `emit` statement, even when it looks like a
+/// call of a function named `emit`.
+///
+/// ```rust
+/// use for_else::for_;
+///
+/// let lines = ["3", "1", "end", "4"];
+///
+/// let numbers = for_! { collect line in lines {
+///     if line == "end" {
+///         break;
+///     }
+///     emit line.parse::<u32>().unwrap();
+/// } else {
+///     // the terminator is missing, so the input is incomplete
+///     vec![]
+/// }};
+///
+/// assert_eq!(numbers, [3, 1]);
+/// ```
+///
+/// `collect::<C>` collects into any [`Collector`](../for_else/trait.Collector.html) `C`
+/// instead of a `Vec`, such as `heapless::Vec` with the `heapless` feature or
+/// `smallvec::SmallVec` with the `smallvec` feature, which need no allocator. Once a bounded
+/// collection is full, each further `emit` runs the `overflow value { ... }` clause with the
+/// value that did not fit, or panics when there is no such clause.
+///
+/// ```ignore
+/// use for_else::for_;
+///
+/// let readings = for_! { collect::<heapless::Vec<u32, 2>> reading in [7, 3, 9] {
+///     emit reading;
+/// } overflow reading {
+///     println!("No room for reading {}", reading);
+///     break;
+/// }};
+///
+/// assert_eq!(readings, [7, 3]);
+/// ```
+///
+/// # Internal iteration
+///
+/// Starting the header with `fold` runs the loop with [`Iterator::try_fold`] instead of a `for`
+/// loop, with the body in a closure that returns [`ControlFlow`](core::ops::ControlFlow).
+/// Iterators made of many adapters, and `chain`ed iterators in particular, often optimize
+/// much better when driven from the inside this way.
+///
+/// Since the body is a closure, it cannot `return` from the enclosing function, use `?` or
+/// `.await`, or break out of an enclosing loop. A `fold` loop only takes a `for` header, its
+/// header modifiers, a `with` clause and an `else` block or `must_break`.
+///
+/// ```rust
+/// use for_else::for_;
+///
+/// let recent = [4, 8, 15];
+/// let archived = [16, 23, 42];
+///
+/// for_! { fold n in recent.iter().chain(&archived) {
+///     if n % 2 == 1 {
+///         println!("Found an odd number: {}", n);
+///         break;
+///     }
+/// } else {
+///     println!("No odd number found.");
+/// }}
+/// ```
+///
+/// # Attributes and comments between clauses
+///
+/// Comments may appear between the body and the clauses that follow it. Attributes written
+/// before a clause, such as `#[allow(...)]` or `#[cfg(...)]`, apply to the block of that clause,
+/// and a clause removed by `#[cfg(...)]` does nothing.
+///
+/// ```rust
+/// use for_else::for_;
+///
+/// for_! { n in [1, 3, 5] {
+///     if n % 2 == 0 {
+///         break;
+///     }
+/// }
+/// // every number was odd
+/// #[cfg(debug_assertions)]
+/// else {
+///     println!("No even number found.");
+/// }}
+/// ```
+///
+/// # Must-break loops
+///
+/// When the loop is known to always `break`, the `else` block can be replaced with `must_break`,
+/// which panics if the loop finishes anyway:
+///
+/// ```rust
+/// use for_else::for_;
+///
+/// let haystack = [3, 1, 4, 1, 5];
+/// let mut position = 0;
+///
+/// for_! { (i, &x) in haystack.iter().enumerate() {
+///     if x == 4 {
+///         position = i;
+///         break;
+///     }
+/// } must_break }
+///
+/// assert_eq!(position, 2);
+/// ```
+///
+/// For hot loops where that check matters, `unsafe must_break` tells the compiler that the
+/// loop never finishes without a `break` by calling [`core::hint::unreachable_unchecked`]
+/// instead of panicking. This mode is experimental and requires the `unstable-unchecked` feature.
+///
+/// # Safety
+///
+/// With `unsafe must_break` the caller guarantees that every execution of the loop ends in a
+/// `break` (or leaves it by other means, such as `return` or a panic). A loop that runs out of
+/// items instead is undefined behavior.
+#[proc_macro]
+pub fn for_(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as ForLoop);
+    dump::finish("for_", expand_for(input))
+}
+
+/// Whether `expr` is an iterable with no items, judging by its syntax alone: an empty array
+/// or a range of integer or character literals whose start is not below its end.
+fn is_empty_literal(expr: &Expr) -> bool {
+    /// The value of an integer or character literal, possibly negated.
+    fn value(expr: &Expr) -> Option<i128> {
+        match expr {
+            Expr::Lit(ExprLit {
+                lit: Lit::Int(int), ..
+            }) => int.base10_parse().ok(),
+            Expr::Lit(ExprLit {
+                lit: Lit::Char(char),
+                ..
+            }) => Some(u32::from(char.value()).into()),
+            Expr::Unary(ExprUnary {
+                op: UnOp::Neg(_),
+                expr,
+                ..
+            }) => value(expr).map(|value| -value),
+            Expr::Paren(paren) => value(&paren.expr),
+            _ => None,
+        }
+    }
+
+    match expr {
+        Expr::Array(array) => array.elems.is_empty(),
+        Expr::Range(ExprRange {
+            start: Some(start),
+            limits,
+            end: Some(end),
+            ..
+        }) => match (value(start), value(end), limits) {
+            (Some(start), Some(end), RangeLimits::HalfOpen(_)) => start >= end,
+            (Some(start), Some(end), RangeLimits::Closed(_)) => start > end,
+            _ => false,
+        },
+        // `in ref` and `in mut` borrow the iterable
+        Expr::Reference(reference) => is_empty_literal(&reference.expr),
+        Expr::Paren(paren) => is_empty_literal(&paren.expr),
+        _ => false,
+    }
+}
+
+/// Whether `pat` matches every value, judging by its syntax alone.
+///
+/// Identifiers starting with an uppercase letter are taken to be unit structs, enum variants or
+/// constants, which may be refutable.
+fn is_irrefutable(pat: &Pat) -> bool {
+    match pat {
+        Pat::Ident(pat) => {
+            !pat.ident.to_string().starts_with(char::is_uppercase)
+                && pat
+                    .subpat
+                    .as_ref()
+                    .is_none_or(|(_, subpat)| is_irrefutable(subpat))
+        }
+        Pat::Wild(_) | Pat::Rest(_) => true,
+        Pat::Tuple(pat) => pat.elems.iter().all(is_irrefutable),
+        Pat::Reference(pat) => is_irrefutable(&pat.pat),
+        Pat::Paren(pat) => is_irrefutable(&pat.pat),
+        Pat::Type(pat) => is_irrefutable(&pat.pat),
+        _ => false,
+    }
+}
+
+/// Applies the adapters of the header modifiers to the iterable `expr`.
+fn apply_adapters(expr: Expr, adapters: &[Adapter]) -> TokenStream2 {
+    if adapters.is_empty() {
+        quote! { #expr }
+    } else {
+        adapters.iter().fold(
+            quote! { ::core::iter::IntoIterator::into_iter(#expr) },
+            |iter, adapter| adapter.apply(iter),
+        )
+    }
+}
+
+/// Builds the loop statement for `header` around `body`, adding its variables to `setup`.
+///
+/// With an `until` condition, the condition is checked before every item is taken and sets
+/// `#STOPPED` when it holds.
+fn header_loop(
+    label: &Option<Label>,
+    header: Header,
+    until: Option<&Expr>,
+    setup: &mut TokenStream2,
+    body: &TokenStream2,
+) -> TokenStream2 {
+    let Header::WhileChain(conjuncts) = header else {
+        let head = loop_head(label, header, until, setup);
+        return quote! { #head #body };
+    };
+    // the body is nested in an `if` for each conjunct, so that it and the later conjuncts see
+    // the bindings of the earlier ones, which a `while let` only allows in newer editions
+    let body = conjuncts.iter().rev().fold(body.clone(), |body, conjunct| {
+        quote! {
+            if #conjunct {
+                #body
+            } else {
+                break;
+            }
+        }
+    });
+    let until = until.map(|until| {
+        quote! {
+            if #until {
+                #STOPPED = true;
+                break;
+            }
+        }
+    });
+    quote! {
+        #label loop {
+            #until
+            #body
+        }
+    }
+}
+
+/// Builds the loop statement for any `header` but a let chain, without the body.
+fn loop_head(
+    label: &Option<Label>,
+    header: Header,
+    until: Option<&Expr>,
+    setup: &mut TokenStream2,
+) -> TokenStream2 {
+    let Some(until) = until else {
+        return match header {
+            Header::For {
+                var,
+                expr,
+                adapters,
+            } => {
+                let expr = apply_adapters(expr, &adapters);
+                quote! { #label for #var in #expr }
+            }
+            Header::While(cond) => quote! { #label while #cond },
+            Header::WhileCauses(causes) => {
+                let cond = causes.condition(setup);
+                quote! { #label while #cond }
+            }
+            Header::DoWhile(cond) => {
+                let cond = do_while_condition(&cond, setup);
+                quote! { #label while #cond }
+            }
+            Header::WhileLet(pat, expr) => quote! { #label while let #pat = #expr },
+            Header::WhileChain(_) => unreachable!("let chains are built by `header_loop`"),
+            #[cfg(feature = "lending")]
+            Header::Lend { var, expr } => {
+                setup.extend(quote! {
+                    let mut _for_else_lender = #expr;
+                });
+                quote! {
+                    #label while let ::core::option::Option::Some(#var) =
+                        ::for_else::LendingIterator::next(&mut _for_else_lender)
+                }
+            }
+        };
+    };
+    // each form yields `Some` of what its pattern is matched against to keep looping
+    let (pat, next) = match header {
+        Header::For {
+            var,
+            expr,
+            adapters,
+        } => {
+            let expr = apply_adapters(expr, &adapters);
+            setup.extend(quote! {
+                let mut _for_else_items = ::core::iter::IntoIterator::into_iter(#expr);
+            });
+            (
+                quote! { #var },
+                quote! { ::core::iter::Iterator::next(&mut _for_else_items) },
+            )
+        }
+        Header::While(cond) => (quote! { () }, while_next(&cond)),
+        Header::WhileCauses(causes) => (quote! { () }, while_next(&causes.condition(setup))),
+        Header::DoWhile(cond) => (quote! { () }, while_next(&do_while_condition(&cond, setup))),
+        Header::WhileLet(pat, expr) => (
+            quote! { #pat },
+            quote! { ::core::option::Option::Some(#expr) },
+        ),
+        Header::WhileChain(_) => unreachable!("let chains are built by `header_loop`"),
+        #[cfg(feature = "lending")]
+        Header::Lend { var, expr } => {
+            setup.extend(quote! {
+                let mut _for_else_lender = #expr;
+            });
+            (
+                quote! { #var },
+                quote! { ::for_else::LendingIterator::next(&mut _for_else_lender) },
+            )
+        }
+    };
+    quote! {
+        #label while let ::core::option::Option::Some(#pat) = if #until {
+            #STOPPED = true;
+            ::core::option::Option::None
+        } else {
+            #next
+        }
+    }
+}
+
+/// The condition of a do-while loop as a `while` condition, which holds before the first
+/// iteration without evaluating `cond`. A `continue` reaches the check just like the end of
+/// the body does.
+fn do_while_condition(cond: &Expr, setup: &mut TokenStream2) -> Expr {
+    setup.extend(quote! {
+        let mut _for_else_first = true;
+    });
+    parse_quote! {
+        {
+            if _for_else_first {
+                _for_else_first = false;
+                true
+            } else {
+                #cond
+            }
+        }
+    }
+}
+
+/// The next "item" of a `while` loop under `until`: `Some(())` while `cond` holds.
+fn while_next(cond: &Expr) -> TokenStream2 {
+    quote! {
+        if #cond {
+            ::core::option::Option::Some(())
+        } else {
+            ::core::option::Option::None
+        }
+    }
+}
+
+fn expand_for(mut input: ForLoop) -> TokenStream2 {
+    if input.fold.is_some() {
+        return fold::expand(input);
+    }
+
+    // with a `defer` clause the body is a labeled block, which unlabeled `break`s may not leave,
+    // so they name the loop instead, and `continue`s leave the block to reach the deferred code
+    // a loop whose `break`s have a value evaluates to that value, or to its `else` block when it
+    // does not break, so then every `break` of the loop records a value
+    let own_label = input.label.as_ref().map(|label| label.name.clone());
+    let valued = !input.collect && breaks_with_value(&input.body, own_label.as_ref());
+    let collect = input.collect;
+    let record_break = |expr_break: &ExprBreak, target: Option<&Lifetime>| {
+        if expr_break.label.is_some() && expr_break.label != own_label {
+            // a `break` out of an enclosing loop keeps its value for that loop
+            return quote_spanned! {expr_break.span()=>
+                {
+                    #BREAK_FLAG = true;
+                    #expr_break;
+                }
+            };
+        }
+        let value = match &expr_break.expr {
+            Some(value) if collect => quote_spanned! {value.span()=>
+                ::core::compile_error!("`collect` loops evaluate to the collected values, so their `break`s cannot have a value");
+            },
+            Some(value) => quote! { #BREAK_VALUE = ::core::option::Option::Some(#value); },
+            None if valued => quote! { #BREAK_VALUE = ::core::option::Option::Some(()); },
+            None => quote! {},
+        };
+        let break_token = expr_break.break_token;
+        quote_spanned! {expr_break.span()=>
+            {
+                #value
+                #BREAK_FLAG = true;
+                #break_token #target;
+            }
+        }
+    };
+    let mut breaks = match &input.defer {
+        Some(_) => {
+            let label = &input
+                .label
+                .get_or_insert_with(|| parse_quote! { '_for_else_loop: })
+                .name;
+            let breaks = replace_breaks(&mut input.body, &|expr_break| {
+                record_break(expr_break, Some(expr_break.label.as_ref().unwrap_or(label)))
+            });
+            let iteration = iteration_label();
+            replace_continues(&mut input.body, label, &parse_quote! { break #iteration });
+            breaks
+        }
+        None => replace_breaks(&mut input.body, &|expr_break| {
+            record_break(expr_break, expr_break.label.as_ref())
+        }),
+    };
+
+    // a `?` in the body names the loop to go on with the next item or break out of it; this
+    // comes after the `break`s are replaced, so that its own `break` is not replaced again
+    if let Some(on_try) = input.on_try {
+        let unlabeled = input.label.is_none();
+        let label = &input
+            .label
+            .get_or_insert_with(|| parse_quote! { '_for_else_loop: })
+            .name;
+        let failed = match (on_try, &input.defer) {
+            (TryClause::Continue, Some(_)) => {
+                let iteration = iteration_label();
+                quote! { break #iteration }
+            }
+            (TryClause::Continue, None) => quote! { continue #label },
+            (TryClause::Break, _) if valued => {
+                return syn::Error::new(
+                    Span::call_site(),
+                    "`try break` cannot end a loop whose `break`s have a value",
+                )
+                .to_compile_error();
+            }
+            (TryClause::Break, _) => quote! {
+                {
+                    #BREAK_FLAG = true;
+                    break #label;
+                }
+            },
+        };
+        let found = try_clause::rewrite(&mut input.body, failed);
+        if found && matches!(on_try, TryClause::Break) {
+            breaks.leaving = true;
+        } else if !found && unlabeled {
+            input.label = None;
+        }
+    }
+
+    let warning = input.warning(&breaks);
+    let body = input.body;
+    let no_break_span = input
+        .no_break
+        .as_ref()
+        .map_or_else(Span::call_site, NoBreak::span);
+    let mut setup_count = None;
+    let no_break = match (input.no_break, input.count) {
+        // the counter only exists for an `else` block that binds it, whose statements follow
+        // the binding, as a block nested in another would be linted as unnecessary braces
+        (Some(NoBreak::Else(mut else_block)), Some(count)) => {
+            setup_count = Some(quote! {
+                let mut #COUNT: ::core::primitive::usize = 0;
+            });
+            else_block.stmts.insert(
+                0,
+                parse_quote! {
+                    let #count: ::core::primitive::usize = #COUNT;
+                },
+            );
+            quote! { #else_block }
+        }
+        (Some(_), Some(_)) => unreachable!("only an `else` block binds a count"),
+        (Some(no_break), None) => quote! { #no_break },
+        (None, _) if input.collect => quote! { { #COLLECTED } },
+        (None, _) => quote! { {} },
+    };
+
+    let mut setup = warning;
+    let body = match setup_count {
+        Some(setup_count) => {
+            setup.extend(setup_count);
+            quote! {
+                {
+                    #COUNT += 1;
+                    #body
+                }
+            }
+        }
+        None => quote! { #body },
+    };
+    let entered = input.skipped.is_some() || input.empty.is_some();
+    let (body, no_break) = match input.skipped {
+        // an iteration only reaches the end of the body when it neither `continue`d nor broke;
+        // the marker is a `let` statement because attributes on expression statements are unstable
+        Some(skipped) => {
+            setup.extend(quote! {
+                let mut _for_else_completed_iteration = false;
+            });
+            (
+                quote! {
+                    {
+                        #body
+                        #[allow(unreachable_code)]
+                        let () = _for_else_completed_iteration = true;
+                    }
+                },
+                quote! {
+                    {
+                        if _for_else_iterated && !_for_else_completed_iteration
+                            #skipped
+                        else
+                            #no_break
+                    }
+                },
+            )
+        }
+        None => (quote! { #body }, no_break),
+    };
+    // the `skipped` and `empty` clauses need to know whether the body was ever entered
+    let body = if entered {
+        setup.extend(quote! {
+            let mut _for_else_iterated = false;
+        });
+        quote! {
+            {
+                _for_else_iterated = true;
+                #body
+            }
+        }
+    } else {
+        body
+    };
+    let mut no_break = match input.empty {
+        Some(empty) => quote! {
+            {
+                if !_for_else_iterated
+                    #empty
+                else
+                    #no_break
+            }
+        },
+        None => no_break,
+    };
+
+    // the deferred code runs after every iteration that reaches the end of the body or
+    // `continue`s, but not after a `break`; the loop label stays in use even when all of its
+    // `continue`s were replaced
+    let iteration = iteration_label();
+    let body = match (input.defer, &input.label) {
+        (Some(defer), Some(Label { name, .. })) => quote! {
+            {
+                #iteration: #body
+                #defer
+                if false {
+                    continue #name;
+                }
+            }
+        },
+        _ => body,
+    };
+
+    // sleeping once the next iteration has started means there is no sleep after the last one
+    let body = match input.cooldown {
+        Some(cooldown) => {
+            setup.extend(quote! {
+                let mut _for_else_cooldown = #cooldown;
+                let mut _for_else_started = false;
+            });
+            quote! {
+                {
+                    if _for_else_started {
+                        ::std::thread::sleep(::for_else::Backoff::next_delay(&mut _for_else_cooldown));
+                    } else {
+                        _for_else_started = true;
+                    }
+                    #body
+                }
+            }
+        }
+        None => body,
+    };
+
+    // the observer only sees iterations that run the body
+    let mut finish = quote! {};
+    let body = match input.observe.take() {
+        Some(observe) => {
+            setup.extend(quote! {
+                let mut _for_else_observer = #observe;
+                let mut _for_else_iterations: ::core::primitive::usize = 0;
+            });
+            finish.extend(quote! {
+                if #BREAK_FLAG {
+                    ::for_else::LoopObserver::on_break(&mut _for_else_observer, _for_else_iterations);
+                } else if _for_else_iterations == 0 {
+                    ::for_else::LoopObserver::on_empty(&mut _for_else_observer);
+                } else {
+                    ::for_else::LoopObserver::on_complete(&mut _for_else_observer, _for_else_iterations);
+                }
+            });
+            quote! {
+                {
+                    ::for_else::LoopObserver::on_iteration(&mut _for_else_observer, _for_else_iterations);
+                    _for_else_iterations += 1;
+                    #body
+                }
+            }
+        }
+        None => body,
+    };
+
+    // plugins see the same iterations as the observer
+    let body = if input.plugins.is_empty() {
+        body
+    } else {
+        setup.extend(plugin::setup(&input.plugins));
+        finish.extend(plugin::finish(&input.plugins));
+        let iteration = plugin::iteration(&input.plugins);
+        quote! {
+            {
+                #iteration
+                #body
+            }
+        }
+    };
+
+    // items before a checkpoint are skipped by key once the pattern is bound, or by count
+    // before it is matched, so that items failing the pattern still count
+    #[cfg(feature = "resume")]
+    let checkpoint = input.checkpoint.take();
+    #[cfg(feature = "resume")]
+    let body = match &checkpoint {
+        Some(checkpoint) => checkpoint.track_keys(body),
+        None => body,
+    };
+
+    // a time limit that counts iterations, such as a budget, draws one once an item is taken
+    // and passes the guard, so that finding the iterable exhausted does not use one up
+    let body = match &input.within {
+        Some(_) => quote! {
+            {
+                if !::for_else::TimeLimit::draw(&_for_else_time_limit) {
+                    _for_else_timed_out = true;
+                    #STOPPED = true;
+                    break;
+                }
+                #body
+            }
+        },
+        None => body,
+    };
+
+    // items that do not match a refutable pattern or fail the guard are skipped before the body
+    // starts, so they do not count as iterations
+    let body = match input.guard {
+        Some(guard) => quote! {
+            {
+                if !(#guard) {
+                    continue;
+                }
+                #body
+            }
+        },
+        None => body,
+    };
+    let refutable = match &mut input.header {
+        Header::For { var, .. } if !is_irrefutable(var) => Some(var),
+        #[cfg(feature = "lending")]
+        Header::Lend { var, .. } if !is_irrefutable(var) => Some(var),
+        _ => None,
+    };
+    let body = match refutable {
+        Some(var) => {
+            let pat = std::mem::replace(var, parse_quote! { _for_else_item });
+            quote! {
+                {
+                    #[allow(irrefutable_let_patterns)]
+                    let #pat = _for_else_item else {
+                        continue;
+                    };
+                    #body
+                }
+            }
+        }
+        None => body,
+    };
+    #[cfg(feature = "resume")]
+    let body = match &checkpoint {
+        Some(checkpoint) => checkpoint.count_items(body),
+        None => body,
+    };
+
+    // the `until` condition is checked before each item is taken from the iterator,
+    // so stopping does not consume an item
+    // an interruption source is asked along with the `until` condition
+    let label = input.label;
+    let until = match input.interrupt {
+        Some(interrupt) => {
+            setup.extend(quote! {
+                let _for_else_interrupt = #interrupt;
+            });
+            let interrupted: Expr = parse_quote! {
+                ::for_else::Interruptible::is_interrupted(&_for_else_interrupt)
+            };
+            Some(match input.until {
+                Some(until) => parse_quote! { (#until) || #interrupted },
+                None => interrupted,
+            })
+        }
+        None => input.until,
+    };
+    // the deadline is asked last, so that a loop stopped by its `until` condition or an
+    // interruption does not count as timed out
+    let timed = input.within.is_some();
+    let until = match input.within {
+        Some(within) => {
+            setup.extend(quote! {
+                let _for_else_time_limit = #within;
+                let _for_else_deadline = ::for_else::TimeLimit::deadline(&_for_else_time_limit);
+                let mut _for_else_timed_out = false;
+            });
+            let timed_out: Expr = parse_quote! {
+                {
+                    _for_else_timed_out = ::core::matches!(
+                        _for_else_deadline,
+                        ::core::option::Option::Some(deadline) if ::std::time::Instant::now() >= deadline
+                    );
+                    _for_else_timed_out
+                }
+            };
+            Some(match until {
+                Some(until) => parse_quote! { (#until) || #timed_out },
+                None => timed_out,
+            })
+        }
+        None => until,
+    };
+    if until.is_some() {
+        let stopped = match input.stopped {
+            Some(stopped) => quote! { #stopped },
+            None if input.collect => quote! { { #COLLECTED } },
+            None => quote! { {} },
+        };
+        setup.extend(quote! {
+            let mut #STOPPED = false;
+        });
+        no_break = quote! {
+            {
+                if #STOPPED
+                    #stopped
+                else
+                    #no_break
+            }
+        };
+    }
+    if timed {
+        let timed_out = match input.timed_out {
+            Some(timed_out) => quote! { #timed_out },
+            None if input.collect => quote! { { #COLLECTED } },
+            None => quote! { {} },
+        };
+        no_break = quote! {
+            {
+                if _for_else_timed_out
+                    #timed_out
+                else
+                    #no_break
+            }
+        };
+    }
+    let fallback = match (&input.header, input.fallback) {
+        (
+            Header::For {
+                var,
+                expr: _,
+                adapters,
+            },
+            Some(fallback),
+        ) => Some(Header::For {
+            var: var.clone(),
+            expr: fallback,
+            adapters: adapters.clone(),
+        }),
+        _ => None,
+    };
+    #[cfg(feature = "resume")]
+    if let Some(checkpoint) = &checkpoint {
+        setup.extend(checkpoint.setup());
+        if let (Header::For { adapters, .. }, Some(adapter)) =
+            (&mut input.header, checkpoint.adapter())
+        {
+            adapters.push(adapter);
+        }
+        finish.extend(checkpoint.finish(until.is_some()));
+    }
+    let looping = header_loop(&label, input.header, until.as_ref(), &mut setup, &body);
+    let looping = match fallback {
+        // the body runs again in a second loop, so that the fallback source is only evaluated
+        // once the first one runs out
+        Some(fallback) => {
+            let mut fallback_setup = quote! {};
+            let fallback_looping =
+                header_loop(&label, fallback, until.as_ref(), &mut fallback_setup, &body);
+            let stopped = if until.is_some() {
+                quote! { && !#STOPPED }
+            } else {
+                quote! {}
+            };
+            quote! {
+                #looping
+                if !#BREAK_FLAG #stopped {
+                    #fallback_setup
+                    #fallback_looping
+                }
+            }
+        }
+        None => looping,
+    };
+
+    let collected = match input.collection {
+        Some(collection) => quote! {
+            let mut #COLLECTED: #collection = ::core::default::Default::default();
+        },
+        None => quote! {
+            let mut #COLLECTED = ::std::vec::Vec::new();
+        },
+    };
+    let state = input.state;
+    let then = input.then;
+    if input.collect {
+        quote! {
+            {
+                #(#state)*
+                #collected
+                let mut #BREAK_FLAG = false;
+                #setup
+                #looping
+                #finish
+                if #BREAK_FLAG {
+                    #then
+                    #COLLECTED
+                } else
+                    #no_break
+            }
+        }
+    } else if valued {
+        // the binding is spanned like generated code, which lints about bindings leave alone
+        let value = Ident::new("_for_else_value", Span::mixed_site());
+        let no_break = quote_spanned! {no_break_span=>
+            match #BREAK_VALUE {
+                ::core::option::Option::Some(#value) => {
+                    #then
+                    #value
+                }
+                ::core::option::Option::None => #no_break,
+            }
+        };
+        quote! {
+            {
+                #(#state)*
+                let mut #BREAK_FLAG = false;
+                let mut #BREAK_VALUE = ::core::option::Option::None;
+                #setup
+                #looping
+                #finish
+                #no_break
+            }
+        }
+    } else {
+        let no_break = match then {
+            Some(then) => quote_spanned! {no_break_span=>
+                if #BREAK_FLAG
+                    #then
+                else
+                    #no_break
+            },
+            None => quote_spanned! {no_break_span=>
+                if !#BREAK_FLAG
+                    #no_break
+            },
+        };
+        // the flag is scoped to the loop, so that a loop nested in the body of another does not
+        // shadow the flag of the outer loop, and so is the state of a `with` clause
+        quote! {
+            {
+                #(#state)*
+                let mut #BREAK_FLAG = false;
+                #setup
+                #looping
+                #finish
+                #no_break
+            }
+        }
+    }
+}
+
+/// Any form of loop with for-else semantics.
+///
+/// The header starts with the keyword of the loop form: `for pattern in iterable`,
+/// `while condition`, `while let pattern = expression`, or `loop max n`, which runs the body
+/// at most `n` times. Instead of an integer, `max` also takes any
+/// [`Budget`](../for_else/trait.Budget.html), such as `&budget` for a
+/// [`LoopBudget`](../for_else/struct.LoopBudget.html) shared by several loops. The rest of the invocation is the same for every form and accepts
+/// everything [`for_!`] does after its header: a label, `fold`, `collect`, `until`, `cooldown`,
+/// `interrupt`, `within`, `observe`, `with` and `checkpoint`, and the `defer`, `then`, `else`,
+/// `must_break`, `empty`, `skipped`, `stopped` and `timed_out` clauses and clause plugins. The header modifiers such as `, take n`, `fold` and `checkpoint` only
+/// apply to the `for` form.
+///
+/// `for_! { ... }` is the same as `loop_else! { for ... }`.
+///
+/// The conjuncts of a `while` condition chained with `&&` can be named, as in
+/// `while open: !queue.is_closed() && in_time: Instant::now() < deadline`. An `else name`
+/// clause then runs instead of the `else` block when the loop ends because that conjunct is
+/// false, and a plain `else` block after the clauses runs for the other conjuncts. A
+/// conjunct holding a `||` or a block, such as a `match`, has to be wrapped in parentheses.
+///
+/// The condition of a `while` loop can also be a let chain, which mixes `let` expressions and
+/// conditions with `&&`, as in `while let Some(line) = lines.next() && !line.is_empty()`.
+/// Each conjunct sees the bindings of the `let` expressions before it, and the loop ends,
+/// running the `else` block, as soon as a pattern does not match or a condition is false. Let
+/// chains work on every edition, as the macro nests the conjuncts in `if` expressions.
+///
+/// # Syntax
+///
+/// ```ignore
+/// loop_else! { for pattern in iterable | while condition | while let pattern = expression | loop max n {
+///     // loop body
+/// } else {
+///     // else block
+/// }}
+///
+/// loop_else! { while name: condition && name: condition ... {
+///     // loop body
+/// } else name {
+///     // runs when the named conjunct ended the loop
+/// } else {
+///     // runs when another conjunct ended the loop
+/// }}
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// use for_else::loop_else;
+///
+/// let mut stack = vec![1, 3, 4, 5];
+///
+/// loop_else! { while let Some(n) = stack.pop() {
+///     if n % 2 == 0 {
+///         println!("Found an even number: {}", n);
+///         break;
+///     }
+/// } else {
+///     println!("No even number found.");
+/// }}
+///
+/// let mut attempts = 0;
+/// loop_else! { loop max 3 {
+///     attempts += 1;
+///     if attempts == 2 {
+///         break;
+///     }
+/// } else {
+///     println!("Gave up after 3 attempts.");
+/// }}
+///
+/// let mut queue = vec![4, 8, 15];
+/// let mut polls = 0;
+/// loop_else! { while pending: !queue.is_empty() && in_time: polls < 10 {
+///     polls += 1;
+///     if queue.pop() == Some(42) {
+///         break;
+///     }
+/// } else pending {
+///     println!("The queue ran dry after {} polls.", polls);
+/// } else in_time {
+///     println!("Timed out.");
+/// }}
+/// ```
+#[proc_macro]
+pub fn loop_else(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input with loop_else::parse);
+    dump::finish("loop_else", expand_for(input))
+}
+
+/// A `while let` loop with for-else semantics.
+///
+/// The header is the `pattern = expression` of a `while let` loop, and the `else` block runs
+/// once the expression no longer matches the pattern, unless the loop was left with `break`.
+/// Everything after the header is the same as for [`loop_else!`], which this is a shorthand
+/// for: `while_let_! { ... }` is the same as `loop_else! { while let ... }`, so the expression
+/// can be followed by the rest of a let chain, as in `Some(n) = stack.pop() && n > 0`. The
+/// `while let` keywords may be kept in front of the header as well.
+///
+/// # Syntax
+///
+/// ```ignore
+/// while_let_! { pattern = expression {
+///     // loop body
+/// } else {
+///     // else block
+/// }}
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// use for_else::while_let_;
+///
+/// let mut queue = vec!["retry", "ok", "fatal"];
+///
+/// while_let_! { Some(message) = queue.pop() {
+///     if message == "fatal" {
+///         println!("Giving up on a fatal error.");
+///         break;
+///     }
+/// } else {
+///     println!("The queue is empty.");
+/// }}
+/// ```
+#[proc_macro]
+pub fn while_let_(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input with loop_else::parse_while_let);
+    dump::finish("while_let_", expand_for(input))
+}
+
+/// A do-while loop with for-else semantics.
+///
+/// The body runs once before the condition is checked, and then again as long as the
+/// condition holds after it, with a `continue` going straight to the check. The `else` block
+/// runs once the condition is false, unless the loop was left with `break`. The condition is
+/// written before the body, like the header of the other macros, and everything after it is
+/// the same as for [`loop_else!`].
+///
+/// # Syntax
+///
+/// ```ignore
+/// do_while_! { condition {
+///     // loop body, which runs at least once
+/// } else {
+///     // else block
+/// }}
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// use for_else::do_while_;
+///
+/// let mut readings = [12, 17, 31, 8].into_iter();
+/// let mut reading = 0;
+///
+/// do_while_! { reading < 30 {
+///     reading = match readings.next() {
+///         Some(reading) => reading,
+///         None => break,
+///     };
+/// } else {
+///     println!("The threshold was crossed with {}.", reading);
+/// }}
+/// ```
+#[proc_macro]
+pub fn do_while_(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input with loop_else::parse_do_while);
+    dump::finish("do_while_", expand_for(input))
+}
+
+/// A loop with an iteration limit and for-else semantics.
+///
+/// The body runs at most `limit` times, and the `else` block runs if it ran that many times
+/// without a `break`. Instead of an integer, `limit` also takes any
+/// [`Budget`](../for_else/trait.Budget.html), such as a
+/// [`LoopBudget`](../for_else/struct.LoopBudget.html) limiting the wall time. Everything after
+/// the limit is the same as for [`loop_else!`], and `loop_! { limit n ... }` is the same as
+/// `loop_else! { loop max n ... }`.
+///
+/// # Syntax
+///
+/// ```ignore
+/// loop_! { limit n {
+///     // loop body
+/// } else {
+///     // else block
+/// }}
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// use for_else::loop_;
+///
+/// # fn connect(attempt: u32) -> Result<(), ()> { if attempt == 3 { Ok(()) } else { Err(()) } }
+/// let mut attempt = 0;
+///
+/// loop_! { 'connecting: limit 5 {
+///     attempt += 1;
+///     if connect(attempt).is_ok() {
+///         println!("Connected after {} attempts.", attempt);
+///         break 'connecting;
+///     }
+/// } else {
+///     println!("Gave up after 5 attempts.");
+/// }}
+/// ```
+#[proc_macro]
+pub fn loop_(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input with loop_else::parse_limited);
+    dump::finish("loop_", expand_for(input))
+}
+
+/// A C-style `for` loop with for-else semantics.
+///
+/// The header is the `let` that initializes the loop, the condition checked before each run
+/// of the body, and the step that runs after it, as in `for (init; condition; step)`. A
+/// `continue` runs the step before the condition is checked again, while a `break` leaves the
+/// loop without it and skips the `else` block. The variables of the initializer are still in
+/// scope in the `else` block, and everything after the step is the same as for
+/// [`loop_else!`].
+///
+/// # Syntax
+///
+/// ```ignore
+/// cfor_! { let mut i = 0; i < n; i += 1 {
+///     // loop body
+/// } else {
+///     // else block
+/// }}
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// use for_else::cfor_;
+///
+/// let haystack = [3, 1, 4, 1, 5];
+///
+/// cfor_! { let mut i = 0; i < haystack.len(); i += 1 {
+///     if haystack[i] == 5 {
+///         println!("Found 5 at index {}.", i);
+///         break;
+///     }
+/// } else {
+///     println!("Searched all {} elements without finding 5.", i);
+/// }}
+/// ```
+#[proc_macro]
+pub fn cfor_(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input with loop_else::parse_cfor);
+    dump::finish("cfor_", expand_for(input))
+}
+
+/// Loops over the items of a stream with for-else semantics.
+///
+/// Available with the `futures` feature; the calling crate has to depend on `futures`, and the
+/// macro can only be used in async code. The stream is pinned in place and each item is
+/// awaited with [`StreamExt::next`](https://docs.rs/futures/latest/futures/stream/trait.StreamExt.html#method.next),
+/// so it does not have to be `Unpin`. The `else` block runs once the stream is exhausted,
+/// unless the loop was left with `break`, and everything after the stream is the same as for
+/// [`loop_else!`]. A `within` deadline needs the `tokio` feature and a tokio runtime with its
+/// `time` driver: the wait for each item is raced against the deadline, so that the `timed_out`
+/// block also runs when the stream stalls, which a check between items would not notice. A
+/// `cooldown` needs the same, and awaits a tokio timer instead of sleeping the executor's thread.
+///
+/// # Syntax
+///
+/// ```ignore
+/// for_await_! { item in stream {
+///     // loop body, which may use `.await`
+/// } else {
+///     // else block
+/// }}
+/// ```
+///
+/// # Example
+///
+/// ```ignore
+/// use for_else::for_await_;
+/// use futures::stream;
+///
+/// # futures::executor::block_on(async {
+/// let replies = stream::iter(["busy", "busy", "ready"]);
+///
+/// for_await_! { reply in replies {
+///     if reply == "ready" {
+///         println!("The worker is ready.");
+///         break;
+///     }
+/// } else {
+///     println!("The worker never became ready.");
+/// }}
+/// # });
+/// ```
+#[cfg(feature = "futures")]
+#[proc_macro]
+pub fn for_await_(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input with loop_else::parse_await);
+    dump::finish("for_await_", expand_for(input))
+}
+
+/// Loops over fallible items with for-else semantics.
+///
+/// The iterable yields `Result`s, and the loop variable is bound to the value of each `Ok`.
+/// The first `Err` ends the loop and runs the `catch` block with the error bound to the
+/// given name, and the `else` block runs only if every item was `Ok` and the body never
+/// reached a `break`. The loop may be labeled like the loop of [`for_!`].
+///
+/// # Syntax
+///
+/// ```ignore
+/// try_for_! { item in expression {
+///     // loop body
+/// } catch err {
+///     // runs for the first error
+/// } else {
+///     // else block
+/// }}
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// use for_else::try_for_;
+///
+/// let lines = ["12", "7", "x", "40"];
+///
+/// try_for_! { n in lines.iter().map(|line| line.parse::<u32>()) {
+///     if n > 30 {
+///         println!("Found {}.", n);
+///         break;
+///     }
+/// } catch err {
+///     println!("Stopped at an invalid line: {}", err);
+/// } else {
+///     println!("No number is above 30.");
+/// }}
+/// ```
+#[proc_macro]
+pub fn try_for_(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as try_for::TryLoop);
+
+    dump::finish("try_for_", try_for::expand(input))
+}
+
+/// Retries the body a bounded number of times with for-else semantics.
+///
+/// The body runs up to `n` times, and a `break` means that the attempt succeeded. The `else`
+/// block runs once every attempt ran without a `break`. The number of the current attempt,
+/// starting at 1, is bound to `attempt` in the body, or to the name given before `in`. An
+/// optional `delay` waits between the attempts, but not before the first one or after the
+/// last one, and takes a [`Duration`](std::time::Duration) or any
+/// [`Backoff`](../for_else/trait.Backoff.html) strategy, like the `cooldown` clause of
+/// [`for_!`].
+///
+/// # Syntax
+///
+/// ```ignore
+/// retry_! { [name in] n times [delay duration] {
+///     // attempt, which breaks on success
+/// } else {
+///     // else block
+/// }}
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// use for_else::retry_;
+/// use std::time::Duration;
+///
+/// # fn connect(attempt: u32) -> Result<(), ()> { if attempt == 3 { Ok(()) } else { Err(()) } }
+/// retry_! { 5 times delay Duration::from_millis(10) {
+///     if connect(attempt).is_ok() {
+///         println!("Connected on attempt {}.", attempt);
+///         break;
+///     }
+/// } else {
+///     println!("Gave up after 5 attempts.");
+/// }}
+/// ```
+#[proc_macro]
+pub fn retry_(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as retry::RetryLoop);
+
+    dump::finish("retry_", retry::expand(input))
+}
+
+/// Searches an iterable, evaluating to what the search found.
+///
+/// `break value` ends the search, and the macro evaluates to `Some(value)`, or to `None` if
+/// the loop finished without a `break`; a bare `break` finds `()`. With an `else` block, the
+/// macro evaluates to the value itself, and to the value of the `else` block if nothing was
+/// found. Breaks naming the label of an enclosing loop leave the search for that loop.
+///
+/// # Syntax
+///
+/// ```ignore
+/// let found = search_! { item in expression {
+///     // loop body, which may `break value`
+/// } [else {
+///     // fallback value
+/// }]};
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// use for_else::search_;
+///
+/// let words = ["apple", "banana", "cherry"];
+///
+/// let long = search_! { word in words {
+///     if word.len() > 5 {
+///         break word;
+///     }
+/// }};
+/// assert_eq!(long, Some("banana"));
+///
+/// let index = search_! { (i, word) in words.iter().enumerate() {
+///     if word.starts_with('z') {
+///         break i;
+///     }
+/// } else {
+///     words.len()
+/// }};
+/// assert_eq!(index, 3);
+/// ```
+#[proc_macro]
+pub fn search_(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as search::SearchLoop);
+
+    dump::finish("search_", search::expand(input))
+}
+
+/// Drives a state machine with for-else semantics.
+///
+/// The state variable starts at the initial state, and each iteration evaluates the body to the
+/// next state. `break outcome` ends the machine with a terminal outcome, which is matched against
+/// the patterns of the `on` clauses in order; a bare `break` ends it with `()`. The `else` block
+/// runs if the machine reaches the iteration bound without a `break`; like the `max` of
+/// [`loop_else!`], the bound is an integer or any [`Budget`](../for_else/trait.Budget.html).
+/// The state variable stays
+/// in scope in the `on` clauses and the `else` block, and the whole invocation is an expression
+/// that evaluates to the value of the clause that ran.
+///
+/// # Syntax
+///
+/// ```ignore
+/// machine_loop_! { state = initial_state, max n {
+///     // evaluates to the next state or breaks with an outcome
+/// } on pattern => {
+///     // runs for an outcome matching the pattern
+/// } else {
+///     // else block
+/// }}
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// use for_else::machine_loop_;
+///
+/// enum State {
+///     Connecting(u32),
+///     Handshake,
+/// }
+///
+/// enum Outcome {
+///     Ready,
+///     Refused(&'static str),
+/// }
+///
+/// let message = machine_loop_! { state = State::Connecting(0), max 10 {
+///     match state {
+///         State::Connecting(attempt) if attempt < 3 => State::Connecting(attempt + 1),
+///         State::Connecting(_) => State::Handshake,
+///         State::Handshake => break Outcome::Ready,
+///     }
+/// } on Outcome::Ready => {
+///     "connected"
+/// } on Outcome::Refused(reason) => {
+///     reason
+/// } else {
+///     "timed out"
+/// }};
+///
+/// assert_eq!(message, "connected");
+/// ```
+#[proc_macro]
+pub fn machine_loop_(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as machine::MachineLoop);
+
+    dump::finish("machine_loop_", machine::expand(input))
+}
+
+/// Binary-searches a range with for-else semantics.
+///
+/// The loop variable is bound to the midpoint of the remaining half-open interval, starting
+/// with the whole range, and the body evaluates to the [`Ordering`](std::cmp::Ordering) of
+/// the probed value compared with the one searched for, as in
+/// [`slice::binary_search_by`]. The search continues in the upper half on `Less` and in the
+/// lower half on `Greater`. A `break`, or evaluating to `Equal`, ends the search. The `else`
+/// block runs if the interval empties without a `break`.
+///
+/// # Syntax
+///
+/// ```ignore
+/// bisect_loop_! { probe in lo..hi {
+///     // compares the probe, or breaks when it is found
+/// } else {
+///     // else block
+/// }}
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// use for_else::bisect_loop_;
+///
+/// let versions = [1, 4, 9, 16, 25, 36];
+///
+/// bisect_loop_! { i in 0..versions.len() {
+///     if versions[i] == 16 {
+///         println!("Found version 16 at index {}", i);
+///         break;
+///     }
+///     versions[i].cmp(&16)
+/// } else {
+///     println!("Version 16 is not available.");
+/// }}
+/// ```
+#[proc_macro]
+pub fn bisect_loop_(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as bisect::BisectLoop);
+
+    dump::finish("bisect_loop_", bisect::expand(input))
+}
+
+/// Walks two sorted iterators in lockstep with for-else semantics.
+///
+/// Each iteration compares the next items of both iterators and binds the loop variable to a
+/// [`Merge`](../for_else/enum.Merge.html): `Merge::Left` or `Merge::Right` with the lesser item,
+/// whose side then advances, or `Merge::Both` with the two equal items, after which both sides
+/// advance. The items are compared with [`Ord`], so both iterators must be sorted in ascending
+/// order. The `else` block runs if either iterator runs out without a `break`.
+///
+/// # Syntax
+///
+/// ```ignore
+/// merge_for_! { step in left, right {
+///     // loop body
+/// } else {
+///     // else block
+/// }}
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// use for_else::{merge_for_, Merge};
+///
+/// let monday = [3, 8, 15, 21];
+/// let tuesday = [2, 8, 13];
+///
+/// merge_for_! { step in monday, tuesday {
+///     if let Merge::Both(user, _) = step {
+///         println!("User {} was active on both days", user);
+///         break;
+///     }
+/// } else {
+///     println!("No user was active on both days.");
+/// }}
+/// ```
+#[proc_macro]
+pub fn merge_for_(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as merge::MergeLoop);
+
+    dump::finish("merge_for_", merge::expand(input))
+}
+
+/// Chains iterables of different item types under one loop with for-else semantics.
+///
+/// Each iterable is wrapped in a variant name of the caller's choosing, and the loop variable
+/// is bound to every item of the first iterable wrapped in its variant, then to every item of
+/// the second one, and so on. The variants belong to an enum defined by the macro and can be
+/// used unqualified in the body. The `else` block runs if all iterables are exhausted without
+/// a `break`.
+///
+/// # Syntax
+///
+/// ```ignore
+/// chain_for_! { item in First(iterable), Second(iterable) {
+///     // loop body
+/// } else {
+///     // else block
+/// }}
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// use for_else::chain_for_;
+///
+/// let config_file = vec![("color", "auto")];
+/// let env_vars = [("COLOR", String::from("never"))];
+/// let defaults = ["color=always"];
+///
+/// chain_for_! { setting in File(config_file), Env(env_vars), Default(defaults) {
+///     match setting {
+///         File(("color", value)) | Default(value) if !value.is_empty() => {
+///             println!("Color setting: {}", value);
+///             break;
+///         }
+///         Env((name, value)) if name == "COLOR" => {
+///             println!("Color setting from the environment: {}", value);
+///             break;
+///         }
+///         _ => {}
+///     }
+/// } else {
+///     println!("No color setting found.");
+/// }}
+/// ```
+#[proc_macro]
+pub fn chain_for_(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as chain::ChainLoop);
+
+    dump::finish("chain_for_", chain::expand(input))
+}
+
+/// Searches a directory with for-else semantics.
+///
+/// Available with the `fs` feature. The loop variable is bound to each
+/// [`std::fs::DirEntry`] of the directory; with `recursive` subdirectories are walked
+/// depth-first as well. Every I/O error met during the walk is passed to the `err` block,
+/// after which the walk carries on. The `else` block runs if the walk completes without
+/// a `break`, including a `break` from the `err` block.
+///
+/// With `max_errors(n[, count])` the first `n` I/O errors are skipped silently and only the
+/// following ones reach the `err` block; the optional `count` variable holds the number of
+/// skipped errors in the body and the clauses.
+///
+/// # Syntax
+///
+/// ```ignore
+/// fs_search_! { entry in [recursive] path[, max_errors(n[, count])] {
+///     // loop body
+/// } err e {
+///     // runs for every I/O error
+/// } else {
+///     // else block
+/// }}
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// use for_else::fs_search_;
+///
+/// fs_search_! { entry in recursive "." {
+///     if entry.file_name() == "Cargo.toml" {
+///         println!("Found manifest at {}", entry.path().display());
+///         break;
+///     }
+/// } err e {
+///     eprintln!("Skipping unreadable entry: {}", e);
+/// } else {
+///     println!("No manifest found.");
+/// }}
+/// ```
+#[cfg(feature = "fs")]
+#[proc_macro]
+pub fn fs_search_(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as fs_search::FsSearch);
+
+    dump::finish("fs_search_", fs_search::expand(input))
+}
+
+/// Walks the items of a paginated source with for-else semantics.
+///
+/// Available with the `paginate` feature. The header names a cursor and a fetch expression
+/// that evaluates to a `(page, next_cursor)` pair, where `page` is any `IntoIterator` and
+/// `next_cursor` is an `Option`. The cursor is `None` for the first page and the value of the
+/// previous `next_cursor` afterwards; pagination stops after a page with no next cursor.
+/// Pages are fetched only when the previous one is used up, so the fetch expression
+/// may use `.await` or `?` just like the surrounding code.
+///
+/// The `else` block runs if all pages were walked without a `break`.
+///
+/// # Syntax
+///
+/// ```ignore
+/// paginate_for_! { item in cursor => fetch_expression {
+///     // loop body
+/// } else {
+///     // else block
+/// }}
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// use for_else::paginate_for_;
+///
+/// # fn fetch_users(cursor: Option<usize>) -> (Vec<String>, Option<usize>) {
+/// #     let page = cursor.unwrap_or(0);
+/// #     let next = if page < 2 { Some(page + 1) } else { None };
+/// #     (vec![format!("user{}", page)], next)
+/// # }
+/// paginate_for_! { user in cursor => fetch_users(cursor) {
+///     if user == "admin" {
+///         println!("Found the admin");
+///         break;
+///     }
+/// } else {
+///     println!("No admin among the users.");
+/// }}
+/// ```
+#[cfg(feature = "paginate")]
+#[proc_macro]
+pub fn paginate_for_(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as paginate::PaginateLoop);
+
+    dump::finish("paginate_for_", paginate::expand(input))
+}
+
+/// Drains a work queue that the loop body can extend, with for-else semantics.
+///
+/// The header names the queue and the initial work items. The queue is a
+/// [`std::collections::VecDeque`] visible in the body (and after it), and the loop takes
+/// items from its front until it is empty. Pushing new items with `push_back` gives a
+/// breadth-first traversal, while `push_front` gives a depth-first one.
+///
+/// The `else` block runs if the queue drained without a `break`.
+///
+/// # Syntax
+///
+/// ```ignore
+/// worklist_for_! { item in queue = initial_items {
+///     // loop body, may push to `queue`
+/// } else {
+///     // else block
+/// }}
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// use for_else::worklist_for_;
+///
+/// let edges = [(1, 2), (1, 3), (2, 4), (3, 4)];
+/// let mut visited = vec![];
+///
+/// worklist_for_! { node in queue = [1] {
+///     if visited.contains(&node) {
+///         continue;
+///     }
+///     visited.push(node);
+///     if node == 5 {
+///         println!("Reached the goal");
+///         break;
+///     }
+///     for &(from, to) in &edges {
+///         if from == node {
+///             queue.push_back(to);
+///         }
+///     }
+/// } else {
+///     println!("The goal is unreachable.");
+/// }}
+/// ```
+#[proc_macro]
+pub fn worklist_for_(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as worklist::WorklistLoop);
+
+    dump::finish("worklist_for_", worklist::expand(input))
+}
+
+/// Retries a block of assertions until it passes, failing the test when it keeps failing.
+///
+/// The block is run repeatedly, sleeping `interval` between attempts, until an attempt
+/// completes without panicking. If the block is still failing once `timeout` has passed,
+/// the macro panics with the number of attempts made and the message of the last failure.
+/// `timeout` defaults to 5 seconds and `interval` to 50 milliseconds. Instead of a fixed
+/// duration, `interval` also takes any [`Backoff`](../for_else/trait.Backoff.html) strategy.
+///
+/// This is meant for tests that wait on background work; every failed attempt is
+/// reported by the panic hook as usual.
+///
+/// # Syntax
+///
+/// ```ignore
+/// eventually_! { [timeout duration,] [interval duration] {
+///     // assertions
+/// }}
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// use for_else::eventually_;
+/// use std::sync::atomic::{AtomicBool, Ordering};
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// let ready = Arc::new(AtomicBool::new(false));
+/// let worker = {
+///     let ready = Arc::clone(&ready);
+///     std::thread::spawn(move || ready.store(true, Ordering::SeqCst))
+/// };
+///
+/// eventually_! { timeout Duration::from_secs(1), interval Duration::from_millis(10) {
+///     assert!(ready.load(Ordering::SeqCst));
+/// }}
+/// # worker.join().unwrap();
+/// ```
+#[proc_macro]
+pub fn eventually_(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as eventually::Eventually);
+
+    dump::finish("eventually_", eventually::expand(input))
+}
+
+/// Scans whitespace-separated tokens or lines of input with for-else semantics.
+///
+/// Each token (with `tokens`) or line without its line terminator (with `lines`) is parsed
+/// with [`str::parse`] into the type of the loop variable, which can be given explicitly
+/// after the pattern. Input is read from standard input, or from any [`std::io::BufRead`]
+/// given with `from`, such as a [`std::io::BufReader`] or a byte slice.
+///
+/// The reader is borrowed by the loop and read one line at a time through its own buffer, so
+/// after a `break` it can go on reading from the line after the one the loop stopped in. A
+/// reader given as a variable has to be mutable.
+///
+/// A value that fails to parse is passed to the optional `err` block and the scan carries
+/// on; without an `err` block the macro panics instead. Failing to read the input panics as well.
+/// The `else` block runs if the end of the input was reached without a `break`.
+///
+/// With `max_errors(n[, count])` the first `n` values that fail to parse are skipped before
+/// the `err` block (or the panic) is reached; the optional `count` variable holds the number
+/// of skipped values in the body and the clauses.
+///
+/// # Syntax
+///
+/// ```ignore
+/// for_stdin_! { variable[: Type] in tokens|lines [from reader][, max_errors(n[, count])] {
+///     // loop body
+/// } [err e {
+///     // runs for every value that failed to parse
+/// }] else {
+///     // else block
+/// }}
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// use for_else::for_stdin_;
+///
+/// let input = "4 8 15 16 23 42";
+///
+/// for_stdin_! { n: u32 in tokens from input.as_bytes() {
+///     if n % 2 == 1 {
+///         println!("Found an odd number: {}", n);
+///         break;
+///     }
+/// } err e {
+///     eprintln!("Not a number: {}", e);
+/// } else {
+///     println!("All numbers are even.");
+/// }}
+/// ```
+#[proc_macro]
+pub fn for_stdin_(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as stdin::StdinLoop);
+
+    dump::finish("for_stdin_", stdin::expand(input))
+}
+
+/// Scans the data of any [`std::io::Read`] chunk by chunk with for-else semantics.
+///
+/// The reader is read into a buffer of `size` bytes, 8 KiB by default, and the loop variable is
+/// bound to a `&[u8]` slice of the bytes of each read, which may be shorter than the buffer.
+/// Reads interrupted by a signal are retried. Any other I/O error ends the loop and runs the
+/// `err` block with the error instead of the `else` block. The `else` block runs if the end of
+/// the data was reached without a `break`.
+///
+/// Chunks follow the reads of the reader, so a marker may be split across two chunks; wrap the
+/// reader in a [`std::io::BufReader`] or keep the tail of the previous chunk when that matters.
+///
+/// # Syntax
+///
+/// ```ignore
+/// read_chunks_! { chunk in reader[, size n] {
+///     // loop body
+/// } err e {
+///     // runs if reading failed
+/// } else {
+///     // else block
+/// }}
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// use for_else::read_chunks_;
+///
+/// let stream: &[u8] = &[0x00, 0x13, 0x47, 0x00, 0xff];
+/// let mut offset = 0;
+///
+/// read_chunks_! { chunk in stream, size 2 {
+///     if let Some(position) = chunk.iter().position(|&byte| byte == 0x47) {
+///         println!("Sync marker at byte {}", offset + position);
+///         break;
+///     }
+///     offset += chunk.len();
+/// } err e {
+///     eprintln!("Read error: {}", e);
+/// } else {
+///     println!("Stream ended without a sync marker.");
+/// }}
+/// ```
+#[proc_macro]
+pub fn read_chunks_(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as read_chunks::ReadChunks);
+
+    dump::finish("read_chunks_", read_chunks::expand(input))
+}
+
+/// Runs the loop body for every item in parallel on the rayon thread pool, with for-else
+/// semantics.
+///
+/// Available with the `rayon` feature; the calling crate has to depend on `rayon`. The
+/// expression is turned into a parallel iterator with `IntoParallelIterator`, so ranges,
+/// vectors and slices (or `par_iter()` of a collection) all work, and the body is shared by
+/// the threads, so it may only mutate what it captures through atomics or locks.
+///
+/// The first `break` on any thread ends the search, and the other threads stop taking new
+/// items, although an item already being processed runs to its end. The `else` block runs if
+/// no item reached a `break`. `continue` moves on to the next item, while labeled breaks and
+/// breaks with a value cannot leave the thread running the body and are rejected at compile
+/// time.
+///
+/// # Syntax
+///
+/// ```ignore
+/// par_for_! { item in expression {
+///     // loop body
+/// } else {
+///     // else block
+/// }}
+/// ```
+///
+/// # Example
+///
+/// ```ignore
+/// use for_else::par_for_;
+///
+/// # fn hash(nonce: u64) -> u64 { nonce.wrapping_mul(0x9e37_79b9_7f4a_7c15) }
+/// par_for_! { nonce in 0..1_000_000u64 {
+///     if hash(nonce) % 1_000 == 0 {
+///         break;
+///     }
+/// } else {
+///     println!("No nonce produced a matching hash.");
+/// }}
+/// ```
+#[cfg(feature = "rayon")]
+#[proc_macro]
+pub fn par_for_(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as par::ParLoop);
+
+    dump::finish("par_for_", par::expand(input))
+}
+
+/// Runs the loop body for every item as a concurrent tokio task, with for-else semantics.
+///
+/// Available with the `tokio` feature; the calling crate has to depend on `tokio` with its
+/// `rt` feature, and the macro can only be used in async code running on a tokio runtime.
+/// The body of each task is an `async move` block, so the items and everything it captures
+/// have to be `Send + 'static`. Values listed in the `clone` option are cloned for every task
+/// instead of being moved into the first one, which suits shared handles such as an `Arc`.
+/// An optional `limit` bounds how many tasks run at once, where a limit of 0 sets no bound.
+///
+/// The first task that reaches a `break` ends the loop and the tasks still running are
+/// aborted. The `else` block runs if every task finished without a `break`, and `continue` ends
+/// its task like reaching the end of the body does. Labeled breaks and
+/// breaks with a value cannot leave a task and are rejected at compile time, and a panicking
+/// task resumes the panic in the caller.
+///
+/// # Syntax
+///
+/// ```ignore
+/// spawn_for_! { item in expression[, limit n][, clone(a, b, ...)] {
+///     // task body
+/// } else {
+///     // else block
+/// }}
+/// ```
+///
+/// # Example
+///
+/// ```ignore
+/// use for_else::spawn_for_;
+///
+/// # async fn reachable(mirror: &str) -> bool {
+/// #     mirror.ends_with(".org")
+/// # }
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let mirrors = ["mirror.example.com", "mirror.example.org", "mirror.example.net"];
+///
+/// spawn_for_! { mirror in mirrors, limit 2 {
+///     if reachable(mirror).await {
+///         println!("Using {}", mirror);
+///         break;
+///     }
+/// } else {
+///     println!("No mirror is reachable.");
+/// }}
+/// # }
+/// ```
+#[cfg(feature = "tokio")]
+#[proc_macro]
+pub fn spawn_for_(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as spawn::SpawnLoop);
+
+    dump::finish("spawn_for_", spawn::expand(input))
+}
+
+/// Waits for filesystem events with for-else semantics, giving up after a period of inactivity.
+///
+/// Available with the `notify` feature; the calling crate has to depend on `notify`. The loop
+/// variable is bound to each [`notify::Event`](https://docs.rs/notify/latest/notify/event/struct.Event.html)
+/// for the watched path, and with `recursive` for everything below it. Errors reported by the
+/// watcher, including a failure to start watching, are passed to the optional `err` block;
+/// without one the macro panics instead.
+///
+/// The `else` block runs if no event arrived for the `idle` duration (or the watcher could not
+/// be started) without a `break`.
+///
+/// # Syntax
+///
+/// ```ignore
+/// watch_for_! { event in [recursive] path, idle duration {
+///     // loop body
+/// } [err e {
+///     // runs for every watcher error
+/// }] else {
+///     // else block
+/// }}
+/// ```
+///
+/// # Example
+///
+/// ```ignore
+/// use for_else::watch_for_;
+/// use std::time::Duration;
+///
+/// watch_for_! { event in "target", idle Duration::from_secs(30) {
+///     if event.paths.iter().any(|path| path.ends_with("app.wasm")) {
+///         println!("Build finished");
+///         break;
+///     }
+/// } err e {
+///     eprintln!("Watch error: {}", e);
+/// } else {
+///     eprintln!("No build output for 30 seconds, giving up.");
+/// }}
+/// ```
+#[cfg(feature = "notify")]
+#[proc_macro]
+pub fn watch_for_(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as watch::WatchLoop);
+
+    dump::finish("watch_for_", watch::expand(input))
+}
+
+/// Waits for terminal events with for-else semantics, giving up after a period of inactivity.
+///
+/// Available with the `crossterm` feature; the calling crate has to depend on `crossterm`. The
+/// loop variable is bound to each [`crossterm::event::Event`](https://docs.rs/crossterm/latest/crossterm/event/enum.Event.html)
+/// read from the terminal. The `else` block runs once no event arrived for the `idle` duration
+/// without a `break`; every event starts the idle period over.
+///
+/// With `tick duration`, the terminal is polled at least that often, and the optional `tick`
+/// block after the body runs whenever a poll ends without an event, which suits redrawing an
+/// animation or a countdown. An error reading the terminal ends the loop and runs the `err`
+/// block with the error instead of the `else` block; without an `err` block the macro panics.
+///
+/// # Syntax
+///
+/// ```ignore
+/// terminal_for_! { event in idle duration[, tick duration] {
+///     // loop body
+/// } [tick {
+///     // runs whenever a tick passes without an event
+/// }] [err e {
+///     // runs if reading the terminal failed
+/// }] else {
+///     // else block
+/// }}
+/// ```
+///
+/// # Example
+///
+/// ```ignore
+/// use crossterm::event::{Event, KeyCode};
+/// use for_else::terminal_for_;
+/// use std::time::Duration;
+///
+/// println!("Press q to dismiss this message.");
+/// terminal_for_! { event in idle Duration::from_secs(10), tick Duration::from_secs(1) {
+///     if let Event::Key(key) = event {
+///         if key.code == KeyCode::Char('q') {
+///             break;
+///         }
+///     }
+/// } tick {
+///     print!(".");
+/// } err e {
+///     eprintln!("Cannot read the terminal: {}", e);
+/// } else {
+///     println!("Dismissed automatically.");
+/// }}
+/// ```
+#[cfg(feature = "crossterm")]
+#[proc_macro]
+pub fn terminal_for_(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as terminal::TerminalLoop);
+
+    dump::finish("terminal_for_", terminal::expand(input))
+}
+
+/// Searches the rows of a database query with for-else semantics.
+///
+/// Available with the `fallible-rows` feature. The iterable is anything with a
+/// `next(&mut self) -> Result<Option<Row>, E>` method, such as `rusqlite::Rows` or a
+/// `fallible_iterator::FallibleIterator`. The loop variable is bound to each row, or to the
+/// value that the optional `map` closure turns the row into; the closure returns a `Result`
+/// with the same error type as the rows.
+///
+/// An error reading or mapping a row ends the loop and runs the `err` block with the error
+/// instead of the `else` block. The `else` block runs if all rows were read without a `break`.
+///
+/// With `max_errors(n)` up to `n` mapping errors are skipped before one ends the loop, which
+/// suits jobs that may drop a few bad records but should abort on more. Naming a variable
+/// with `max_errors(n, skipped)` makes the number of skipped rows available to the body and
+/// the clauses. Errors reading the rows themselves are never skipped.
+///
+/// # Syntax
+///
+/// ```ignore
+/// rows_for_! { row in rows[, map |row| mapping][, max_errors(n[, count])] {
+///     // loop body
+/// } err e {
+///     // runs if reading or mapping a row failed
+/// } else {
+///     // else block
+/// }}
+/// ```
+///
+/// # Example
+///
+/// ```ignore
+/// use for_else::rows_for_;
+/// use rusqlite::Connection;
+///
+/// # fn main() -> rusqlite::Result<()> {
+/// let conn = Connection::open_in_memory()?;
+/// conn.execute_batch("CREATE TABLE users (name TEXT, admin INTEGER);
+///                     INSERT INTO users VALUES ('alice', 0), ('bob', 0);")?;
+///
+/// let mut statement = conn.prepare("SELECT name, admin FROM users")?;
+/// rows_for_! { (name, admin) in statement.query([])?, map |row| Ok((row.get::<_, String>(0)?, row.get::<_, bool>(1)?)) {
+///     if admin {
+///         println!("{} is an admin", name);
+///         break;
+///     }
+/// } err e {
+///     return Err(e);
+/// } else {
+///     conn.execute("INSERT INTO users VALUES ('root', 1)", [])?;
+/// }}
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "fallible-rows")]
+#[proc_macro]
+pub fn rows_for_(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as rows::RowsLoop);
+
+    dump::finish("rows_for_", rows::expand(input))
+}
+
+/// Searches the matches of a regular expression with for-else semantics.
+///
+/// Available with the `regex` feature; the calling crate has to depend on `regex`. With
+/// `matches` the loop variable is bound to each [`regex::Match`] of the regex in the haystack,
+/// and with `captures` to each [`regex::Captures`]. The regex is either a `regex::Regex`
+/// (or a reference to one) or a string literal, which is compiled once on first use and
+/// panics if it is not a valid regex.
+///
+/// The `else` block runs if the haystack has no further matches without a `break`.
+///
+/// [`regex::Match`]: https://docs.rs/regex/latest/regex/struct.Match.html
+/// [`regex::Captures`]: https://docs.rs/regex/latest/regex/struct.Captures.html
+///
+/// # Syntax
+///
+/// ```ignore
+/// regex_search_! { m in matches|captures regex, haystack {
+///     // loop body
+/// } else {
+///     // else block
+/// }}
+/// ```
+///
+/// # Example
+///
+/// ```ignore
+/// use for_else::regex_search_;
+///
+/// let log = "GET /index 200\nGET /admin 403\nGET /about 200";
+///
+/// regex_search_! { caps in captures r"GET (\S+) (\d{3})", log {
+///     if &caps[2] != "200" {
+///         println!("Request to {} failed with {}", &caps[1], &caps[2]);
+///         break;
+///     }
+/// } else {
+///     println!("All requests succeeded.");
+/// }}
+/// ```
+#[cfg(feature = "regex")]
+#[proc_macro]
+pub fn regex_search_(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as regex_search::RegexSearch);
+
+    dump::finish("regex_search_", regex_search::expand(input))
+}
+
+/// Asserts what an invocation of one of this crate's macros expands to.
+///
+/// Meant for test suites that pin the code the macros generate in their crate. The invocation
+/// is expanded at compile time, and the test fails with both expansions when it differs from
+/// the expected one. The expected expansion is given either in braces or as a string literal,
+/// such as one read with `include_str!` from a snapshot file. Only the tokens are compared,
+/// so whitespace and line breaks in the expected expansion do not matter.
+///
+/// # Syntax
+///
+/// ```ignore
+/// assert_expansion!(macro_name! { ... }, { expected tokens });
+/// assert_expansion!(macro_name! { ... }, "expected tokens");
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// use for_else::assert_expansion;
+///
+/// assert_expansion!(for_! { x in 0..3 {} else {} }, {
+///     {
+///         let mut _for_else_break_occurred = false;
+///         for x in 0..3 {}
+///         if !_for_else_break_occurred {}
+///     }
+/// });
+/// ```
+#[proc_macro]
+pub fn assert_expansion(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as expansion::AssertExpansion);
+
+    dump::finish("assert_expansion", expansion::expand(input))
+}
@@ -0,0 +1,265 @@
+use syn::parse::ParseStream;
+#[cfg(all(feature = "futures", not(feature = "tokio")))]
+use syn::spanned::Spanned;
+use syn::{parse_quote, BinOp, Expr, ExprBinary, ExprLet, Pat, PatWild, Result, Stmt, Token};
+
+use crate::causes::Causes;
+#[cfg(feature = "futures")]
+use crate::errors::{parse_in, parse_iterable};
+use crate::errors::{parse_pat, ErrorCode};
+use crate::{kw, ForLoop, Header};
+
+/// Parses a `loop_else!` invocation, which starts with the keyword of the loop form.
+pub fn parse(input: ParseStream) -> Result<ForLoop> {
+    ForLoop::parse_with(input, parse_header)
+}
+
+/// Parses a `loop_!` invocation, whose header is the `limit` of iterations.
+pub fn parse_limited(input: ParseStream) -> Result<ForLoop> {
+    ForLoop::parse_with(input, |input| {
+        input.parse::<kw::limit>()?;
+        budget_header(input)
+    })
+}
+
+/// Parses a `do_while_!` invocation, whose header is the condition checked after each run of
+/// the body.
+pub fn parse_do_while(input: ParseStream) -> Result<ForLoop> {
+    ForLoop::parse_with(input, |input| {
+        Ok(Header::DoWhile(Expr::parse_without_eager_brace(input)?))
+    })
+}
+
+/// Parses a `while_let_!` invocation, whose header is the `pattern = expression` of a
+/// `while let` loop without the keywords.
+pub fn parse_while_let(input: ParseStream) -> Result<ForLoop> {
+    ForLoop::parse_with(input, |input| {
+        // a loop pasted with its keywords parses the same as one without
+        if input.peek(Token![while]) {
+            input.parse::<Token![while]>()?;
+        }
+        if input.peek(Token![let]) {
+            input.parse::<Token![let]>()?;
+        }
+        parse_while_let_header(input)
+    })
+}
+
+/// Parses a `cfor_!` invocation, whose header is the `let init; condition; step` of a C `for`
+/// loop. The initializer is declared before the loop like the state of a `with` clause, and
+/// the step runs like a `defer` block after the body, so that `continue` runs it but `break`
+/// does not.
+pub fn parse_cfor(input: ParseStream) -> Result<ForLoop> {
+    let mut init = None;
+    let mut step = None;
+    let mut for_loop = ForLoop::parse_with(input, |input| {
+        if !input.peek(Token![let]) {
+            return Err(input.error("expected a `let` statement to initialize the loop"));
+        }
+        init = Some(input.parse::<Stmt>()?);
+        let cond = input.parse()?;
+        input.parse::<Token![;]>()?;
+        step = Some(Expr::parse_without_eager_brace(input)?);
+        Ok(Header::While(cond))
+    })?;
+    for_loop.state.insert(0, init.unwrap());
+    let step = step.unwrap();
+    // the step runs after the `defer` block of the body, as it would at the end of a C loop body
+    for_loop.defer = Some(match for_loop.defer.take() {
+        Some(defer) => parse_quote! { { #defer #step; } },
+        None => parse_quote! { { #step; } },
+    });
+    Ok(for_loop)
+}
+
+/// Parses a `for_await_!` invocation, whose header is the `pattern in stream` of a loop over
+/// the items of a `futures::Stream`. The stream is pinned before the loop like the state of a
+/// `with` clause, and each item is awaited by the condition of a `while let` loop.
+///
+/// With the `tokio` feature, a `within` deadline also races the wait for each item, since the
+/// deadline checked between items would not stop a loop waiting on a stream that stalls.
+#[cfg(feature = "futures")]
+pub fn parse_await(input: ParseStream) -> Result<ForLoop> {
+    let mut stream = None;
+    let mut for_loop = ForLoop::parse_with(input, |input| {
+        let var = parse_pat(input)?;
+        parse_in(input)?;
+        stream = Some(parse_iterable(input)?);
+        Ok(Header::WhileLet(
+            parse_quote! { ::core::option::Option::Some(#var) },
+            parse_quote! { ::futures::StreamExt::next(&mut _for_else_stream).await },
+        ))
+    })?;
+    #[cfg(not(feature = "tokio"))]
+    if let Some(within) = &for_loop.within {
+        return Err(syn::Error::new(
+            within.span(),
+            "`within` needs the `tokio` feature to stop a `for_await_!` loop while it waits for the next item",
+        ));
+    }
+    #[cfg(feature = "tokio")]
+    if let (Some(_), Header::WhileLet(_, next)) = (&for_loop.within, &mut for_loop.header) {
+        *next = parse_quote! {
+            match _for_else_deadline {
+                ::core::option::Option::Some(deadline) => match ::tokio::time::timeout_at(
+                    ::tokio::time::Instant::from_std(deadline),
+                    ::futures::StreamExt::next(&mut _for_else_stream),
+                )
+                .await
+                {
+                    ::core::result::Result::Ok(item) => item,
+                    ::core::result::Result::Err(_) => {
+                        _for_else_timed_out = true;
+                        ::core::option::Option::None
+                    }
+                },
+                ::core::option::Option::None => {
+                    ::futures::StreamExt::next(&mut _for_else_stream).await
+                }
+            }
+        };
+    }
+    // a cooldown sleeps the thread, which would block the executor, so the loop awaits a tokio
+    // timer at the start of the body instead
+    #[cfg(not(feature = "tokio"))]
+    if let Some(cooldown) = &for_loop.cooldown {
+        return Err(syn::Error::new(
+            cooldown.span(),
+            "`cooldown` needs the `tokio` feature in a `for_await_!` loop, since sleeping the thread would block the executor",
+        ));
+    }
+    #[cfg(feature = "tokio")]
+    if let Some(cooldown) = for_loop.cooldown.take() {
+        for_loop.state.extend([
+            parse_quote! { let mut _for_else_cooldown = #cooldown; },
+            parse_quote! { let mut _for_else_started = false; },
+        ]);
+        for_loop.body.stmts.insert(
+            0,
+            parse_quote! {
+                if _for_else_started {
+                    ::tokio::time::sleep(::for_else::Backoff::next_delay(&mut _for_else_cooldown)).await;
+                } else {
+                    _for_else_started = true;
+                }
+            },
+        );
+    }
+    let stream = stream.unwrap();
+    for_loop.state.insert(
+        0,
+        parse_quote! { let mut _for_else_stream = ::core::pin::pin!(#stream); },
+    );
+    Ok(for_loop)
+}
+
+fn parse_header(input: ParseStream) -> Result<Header> {
+    let lookahead = input.lookahead1();
+    if lookahead.peek(Token![for]) {
+        input.parse::<Token![for]>()?;
+        Header::parse_for(input)
+    } else if lookahead.peek(Token![while]) {
+        input.parse::<Token![while]>()?;
+        if input.peek(Token![let]) {
+            input.parse::<Token![let]>()?;
+            parse_while_let_header(input)
+        } else if let Some(causes) = Causes::parse(input)? {
+            Ok(Header::WhileCauses(causes))
+        } else {
+            let cond = Expr::parse_without_eager_brace(input)?;
+            if is_let_chain(&cond) {
+                let mut conjuncts = Vec::new();
+                split_conjuncts(cond, &mut conjuncts);
+                Ok(Header::WhileChain(conjuncts))
+            } else {
+                Ok(Header::While(cond))
+            }
+        }
+    } else if lookahead.peek(Token![loop]) {
+        input.parse::<Token![loop]>()?;
+        input.parse::<kw::max>()?;
+        budget_header(input)
+    } else {
+        Err(ErrorCode::UnknownOption.tag(lookahead.error()))
+    }
+}
+
+/// Parses the budget of `loop max budget` or `loop_! { limit budget ... }` into a header that
+/// runs the body as long as it can draw from the budget, like a `for` loop over an iterator
+/// that ends once the budget runs out.
+fn budget_header(input: ParseStream) -> Result<Header> {
+    let max = Expr::parse_without_eager_brace(input)?;
+    Ok(Header::For {
+        var: Pat::Wild(PatWild {
+            attrs: Vec::new(),
+            underscore_token: Default::default(),
+        }),
+        expr: parse_quote! {
+            ::core::iter::from_fn({
+                let mut _for_else_budget = #max;
+                move || {
+                    if ::for_else::Budget::draw(&mut _for_else_budget) {
+                        ::core::option::Option::Some(())
+                    } else {
+                        ::core::option::Option::None
+                    }
+                }
+            })
+        },
+        adapters: Vec::new(),
+    })
+}
+
+/// Parses the `pattern = expression` of a `while let` loop, which `&&` may chain with further
+/// conditions and `let` expressions as in a let chain.
+fn parse_while_let_header(input: ParseStream) -> Result<Header> {
+    let pat = parse_pat(input)?;
+    input.parse::<Token![=]>()?;
+    let mut conjuncts = Vec::new();
+    split_conjuncts(Expr::parse_without_eager_brace(input)?, &mut conjuncts);
+    let expr = conjuncts.remove(0);
+    if conjuncts.is_empty() {
+        return Ok(Header::WhileLet(pat, expr));
+    }
+    conjuncts.insert(
+        0,
+        Expr::Let(ExprLet {
+            attrs: Vec::new(),
+            let_token: Default::default(),
+            pat: Box::new(pat),
+            eq_token: Default::default(),
+            expr: Box::new(expr),
+        }),
+    );
+    Ok(Header::WhileChain(conjuncts))
+}
+
+/// Whether `cond` is a `&&` chain with a `let` expression in it.
+fn is_let_chain(cond: &Expr) -> bool {
+    match cond {
+        Expr::Let(_) => true,
+        Expr::Binary(ExprBinary {
+            left,
+            op: BinOp::And(_),
+            right,
+            ..
+        }) => is_let_chain(left) || is_let_chain(right),
+        _ => false,
+    }
+}
+
+/// Adds the operands of the `&&` chain `cond` to `conjuncts`, in order.
+fn split_conjuncts(cond: Expr, conjuncts: &mut Vec<Expr>) {
+    match cond {
+        Expr::Binary(ExprBinary {
+            left,
+            op: BinOp::And(_),
+            right,
+            ..
+        }) => {
+            split_conjuncts(*left, conjuncts);
+            split_conjuncts(*right, conjuncts);
+        }
+        cond => conjuncts.push(cond),
+    }
+}
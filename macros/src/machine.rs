@@ -0,0 +1,93 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{Block, Expr, Ident, Pat, Result, Token};
+
+use crate::errors::{parse_body, parse_else, parse_pat};
+use crate::replace_breaks;
+
+mod kw {
+    syn::custom_keyword!(max);
+    syn::custom_keyword!(on);
+}
+
+pub struct MachineLoop {
+    state: Ident,
+    init: Expr,
+    max: Expr,
+    body: Block,
+    handlers: Vec<(Pat, Block)>,
+    else_block: Block,
+}
+
+impl Parse for MachineLoop {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let state: Ident = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let init = Expr::parse_without_eager_brace(input)?;
+        input.parse::<Token![,]>()?;
+        input.parse::<kw::max>()?;
+        let max = Expr::parse_without_eager_brace(input)?;
+        let body = parse_body(input)?;
+        let mut handlers = Vec::new();
+        while input.peek(kw::on) {
+            input.parse::<kw::on>()?;
+            let pat = parse_pat(input)?;
+            input.parse::<Token![=>]>()?;
+            handlers.push((pat, input.parse()?));
+        }
+        let else_block = parse_else(input)?;
+        Ok(MachineLoop {
+            state,
+            init,
+            max,
+            body,
+            handlers,
+            else_block,
+        })
+    }
+}
+
+pub fn expand(mut input: MachineLoop) -> TokenStream {
+    // `break outcome` ends the machine with that outcome, labeled breaks leave enclosing loops
+    replace_breaks(&mut input.body, &|expr_break| {
+        if expr_break.label.is_some() {
+            return quote! { { #expr_break; } };
+        }
+        let outcome = match &expr_break.expr {
+            Some(outcome) => quote! { #outcome },
+            None => quote! { () },
+        };
+        let break_token = expr_break.break_token;
+        quote! {
+            {
+                _for_else_outcome = ::std::option::Option::Some(#outcome);
+                #break_token;
+            }
+        }
+    });
+
+    let state = input.state;
+    let init = input.init;
+    let max = input.max;
+    let body = input.body;
+    let else_block = input.else_block;
+    let handlers = input.handlers.into_iter().map(|(pat, handler)| {
+        quote! { ::std::option::Option::Some(#pat) => #handler }
+    });
+
+    quote! {
+        {
+            let mut _for_else_outcome = ::std::option::Option::None;
+            let mut #state = #init;
+            let mut _for_else_budget = #max;
+            while ::for_else::Budget::draw(&mut _for_else_budget) {
+                #state = #body;
+            }
+            match _for_else_outcome {
+                #(#handlers)*
+                ::std::option::Option::None => #else_block
+            }
+        }
+    }
+}
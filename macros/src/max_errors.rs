@@ -0,0 +1,66 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::parse::{Parse, ParseStream};
+use syn::{parenthesized, Expr, Ident, Result, Token};
+
+pub mod kw {
+    syn::custom_keyword!(max_errors);
+}
+
+/// The `max_errors(limit[, count])` option of the loops with an `err` clause for errors met
+/// while iterating.
+///
+/// Up to `limit` errors are skipped before an error takes the usual error path, and the
+/// optional `count` names a `usize` variable holding the number of skipped errors, which is in
+/// scope in the body and all clauses.
+pub struct MaxErrors {
+    limit: Expr,
+    count: Option<Ident>,
+}
+
+impl MaxErrors {
+    fn count(&self) -> TokenStream {
+        match &self.count {
+            Some(count) => count.to_token_stream(),
+            None => quote! { _for_else_skipped_errors },
+        }
+    }
+
+    /// The statements declaring the counter, to put before the loop.
+    pub fn setup(&self) -> TokenStream {
+        let limit = &self.limit;
+        let count = self.count();
+        quote! {
+            let _for_else_max_errors: ::core::primitive::usize = #limit;
+            let mut #count: ::core::primitive::usize = 0;
+        }
+    }
+
+    /// The statement skipping an error while the limit is not reached, to put at the start of
+    /// the error path inside the loop.
+    pub fn tolerate(&self) -> TokenStream {
+        let count = self.count();
+        quote! {
+            if #count < _for_else_max_errors {
+                #count += 1;
+                continue;
+            }
+        }
+    }
+}
+
+impl Parse for MaxErrors {
+    fn parse(input: ParseStream) -> Result<Self> {
+        input.parse::<kw::max_errors>()?;
+        let content;
+        parenthesized!(content in input);
+        let limit = content.parse()?;
+        let count = if content.peek(Token![,]) {
+            content.parse::<Token![,]>()?;
+            Some(content.parse()?)
+        } else {
+            None
+        };
+        Ok(MaxErrors { limit, count })
+    }
+}
@@ -0,0 +1,78 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{Block, Expr, Pat, Result, Token};
+
+use crate::errors::{parse_body, parse_else, parse_in, parse_pat};
+use crate::{else_branch, modify_breaks, BREAK_FLAG};
+
+pub struct MergeLoop {
+    var: Pat,
+    left: Expr,
+    right: Expr,
+    body: Block,
+    else_block: Block,
+}
+
+impl Parse for MergeLoop {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let var = parse_pat(input)?;
+        parse_in(input)?;
+        let left = Expr::parse_without_eager_brace(input)?;
+        input.parse::<Token![,]>()?;
+        let right = Expr::parse_without_eager_brace(input)?;
+        let body = parse_body(input)?;
+        let else_block = parse_else(input)?;
+        Ok(MergeLoop {
+            var,
+            left,
+            right,
+            body,
+            else_block,
+        })
+    }
+}
+
+pub fn expand(mut input: MergeLoop) -> TokenStream {
+    modify_breaks(&mut input.body);
+
+    let var = input.var;
+    let left = input.left;
+    let right = input.right;
+    let body = input.body;
+    let else_branch = else_branch(&input.else_block);
+
+    // the lesser item is taken and the other one is put back for the next comparison
+    quote! {
+        {
+            let mut #BREAK_FLAG = false;
+            let mut _for_else_left = ::std::iter::IntoIterator::into_iter(#left);
+            let mut _for_else_right = ::std::iter::IntoIterator::into_iter(#right);
+            let mut _for_else_left_item = ::std::iter::Iterator::next(&mut _for_else_left);
+            let mut _for_else_right_item = ::std::iter::Iterator::next(&mut _for_else_right);
+            while let (::std::option::Option::Some(_for_else_l), ::std::option::Option::Some(_for_else_r)) =
+                (_for_else_left_item.take(), _for_else_right_item.take())
+            {
+                let #var = match ::std::cmp::Ord::cmp(&_for_else_l, &_for_else_r) {
+                    ::std::cmp::Ordering::Less => {
+                        _for_else_left_item = ::std::iter::Iterator::next(&mut _for_else_left);
+                        _for_else_right_item = ::std::option::Option::Some(_for_else_r);
+                        ::for_else::Merge::Left(_for_else_l)
+                    }
+                    ::std::cmp::Ordering::Greater => {
+                        _for_else_left_item = ::std::option::Option::Some(_for_else_l);
+                        _for_else_right_item = ::std::iter::Iterator::next(&mut _for_else_right);
+                        ::for_else::Merge::Right(_for_else_r)
+                    }
+                    ::std::cmp::Ordering::Equal => {
+                        _for_else_left_item = ::std::iter::Iterator::next(&mut _for_else_left);
+                        _for_else_right_item = ::std::iter::Iterator::next(&mut _for_else_right);
+                        ::for_else::Merge::Both(_for_else_l, _for_else_r)
+                    }
+                };
+                #body
+            }
+            #else_branch
+        }
+    }
+}
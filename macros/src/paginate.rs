@@ -0,0 +1,76 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{Block, Expr, Ident, Pat, Result, Token};
+
+use crate::errors::{parse_body, parse_else, parse_in, parse_pat};
+use crate::{else_branch, modify_breaks, BREAK_FLAG};
+
+pub struct PaginateLoop {
+    var: Pat,
+    cursor: Ident,
+    fetch: Expr,
+    body: Block,
+    else_block: Block,
+}
+
+impl Parse for PaginateLoop {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let var = parse_pat(input)?;
+        parse_in(input)?;
+        let cursor: Ident = input.parse()?;
+        input.parse::<Token![=>]>()?;
+        let fetch = Expr::parse_without_eager_brace(input)?;
+        let body = parse_body(input)?;
+        let else_block = parse_else(input)?;
+        Ok(PaginateLoop {
+            var,
+            cursor,
+            fetch,
+            body,
+            else_block,
+        })
+    }
+}
+
+pub fn expand(mut input: PaginateLoop) -> TokenStream {
+    modify_breaks(&mut input.body);
+
+    let var = input.var;
+    let cursor = input.cursor;
+    let fetch = input.fetch;
+    let body = input.body;
+    let else_branch = else_branch(&input.else_block);
+
+    // pages are fetched lazily from inside the loop, so there is a single loop for the user's
+    // `break` to leave and the fetch expression may use `.await` or `?` of the enclosing function
+    quote! {
+        {
+            let mut #BREAK_FLAG = false;
+            let mut _for_else_cursor = ::std::option::Option::None;
+            let mut _for_else_page = ::std::option::Option::None;
+            let mut _for_else_last_page = false;
+            loop {
+                let _for_else_item = match _for_else_page.as_mut().and_then(::std::iter::Iterator::next) {
+                    ::std::option::Option::Some(item) => item,
+                    ::std::option::Option::None => {
+                        if _for_else_last_page {
+                            break;
+                        }
+                        let (page, next) = {
+                            let #cursor = _for_else_cursor.take();
+                            #fetch
+                        };
+                        _for_else_page = ::std::option::Option::Some(::std::iter::IntoIterator::into_iter(page));
+                        _for_else_last_page = ::std::option::Option::is_none(&next);
+                        _for_else_cursor = next;
+                        continue;
+                    }
+                };
+                let #var = _for_else_item;
+                #body
+            }
+            #else_branch
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::parse::{Parse, ParseStream};
+use syn::spanned::Spanned;
+use syn::{parse_quote, Block, Expr, Pat, Result};
+
+use crate::errors::{parse_body, parse_else, parse_in, parse_iterable, parse_pat, ErrorCode};
+use crate::{else_branch, replace_breaks, replace_continues, BREAK_FLAG};
+
+pub struct ParLoop {
+    var: Pat,
+    expr: Expr,
+    body: Block,
+    else_block: Block,
+}
+
+impl Parse for ParLoop {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let var = parse_pat(input)?;
+        parse_in(input)?;
+        let expr = parse_iterable(input)?;
+        let body = parse_body(input)?;
+        let else_block = parse_else(input)?;
+        Ok(ParLoop {
+            var,
+            expr,
+            body,
+            else_block,
+        })
+    }
+}
+
+pub fn expand(mut input: ParLoop) -> TokenStream {
+    // the body is the predicate of `any`, which is true once it hits a `break`
+    replace_breaks(&mut input.body, &|expr_break| match &expr_break.label {
+        Some(label) => {
+            let message = ErrorCode::LabeledBreakInTask
+                .message("labeled breaks cannot leave a thread of `par_for_!`");
+            quote_spanned! {label.span()=>
+                {
+                    ::std::compile_error!(#message);
+                }
+            }
+        }
+        None => match &expr_break.expr {
+            Some(value) => quote_spanned! {value.span()=>
+                {
+                    ::std::compile_error!("`par_for_!` loops do not evaluate to a value, so their `break`s cannot have one");
+                }
+            },
+            None => quote! {
+                {
+                    return true;
+                }
+            },
+        },
+    });
+    // only unlabeled `continue`s are replaced, as the label is never written by the caller
+    replace_continues(
+        &mut input.body,
+        &parse_quote! { '_for_else_par },
+        &parse_quote! { return false },
+    );
+
+    let var = input.var;
+    let expr = input.expr;
+    let body = input.body;
+    let else_branch = else_branch(&input.else_block);
+
+    quote! {
+        {
+            // `any` stops handing out items on every thread once one of them returns true
+            let #BREAK_FLAG = ::rayon::iter::ParallelIterator::any(
+                ::rayon::iter::IntoParallelIterator::into_par_iter(#expr),
+                |#var| {
+                    #body
+                    #[allow(unreachable_code)]
+                    false
+                },
+            );
+            #else_branch
+        }
+    }
+}
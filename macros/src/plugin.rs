@@ -0,0 +1,82 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::parse::ParseStream;
+use syn::{braced, token, Attribute, Ident, Path, Result, Token};
+
+use crate::BREAK_FLAG;
+
+/// A clause handled by a macro of another crate, written `path! { ... }` after the `else`
+/// block of `for_!`.
+///
+/// The loop invokes the macro with `@for_else hook state { ... }` at each hook, passing the
+/// tokens of the clause along. `state` is an identifier the macro may bind in the `setup` hook
+/// to keep state for the later hooks. The hooks are `setup` before the loop, `iteration`
+/// before the body runs, and either `break` or `complete` after the loop.
+pub struct Plugin {
+    attrs: Vec<Attribute>,
+    path: Path,
+    args: TokenStream,
+}
+
+impl Plugin {
+    pub fn peek(input: ParseStream) -> bool {
+        let fork = input.fork();
+        fork.call(Path::parse_mod_style).is_ok() && fork.peek(Token![!]) && fork.peek2(token::Brace)
+    }
+
+    /// Parses the clause, whose `attrs` apply to every invocation of its macro.
+    pub fn parse(attrs: Vec<Attribute>, input: ParseStream) -> Result<Self> {
+        let path = input.call(Path::parse_mod_style)?;
+        input.parse::<Token![!]>()?;
+        let content;
+        braced!(content in input);
+        Ok(Plugin {
+            attrs,
+            path,
+            args: content.parse()?,
+        })
+    }
+
+    fn hook(&self, index: usize, hook: &str) -> TokenStream {
+        let attrs = &self.attrs;
+        let path = &self.path;
+        let args = &self.args;
+        let state = format_ident!("_for_else_plugin_{}", index);
+        let hook = Ident::new(hook, Span::call_site());
+        quote! {
+            #(#attrs)*
+            #path! { @for_else #hook #state { #args } }
+        }
+    }
+}
+
+/// The `setup` hooks of every plugin, to put before the loop.
+pub fn setup(plugins: &[Plugin]) -> TokenStream {
+    hooks(plugins, "setup")
+}
+
+/// The `iteration` hooks of every plugin, to put before the body.
+pub fn iteration(plugins: &[Plugin]) -> TokenStream {
+    hooks(plugins, "iteration")
+}
+
+/// The `break` or `complete` hooks of every plugin, to put after the loop.
+pub fn finish(plugins: &[Plugin]) -> TokenStream {
+    let broke = hooks(plugins, "break");
+    let complete = hooks(plugins, "complete");
+    quote! {
+        if #BREAK_FLAG {
+            #broke
+        } else {
+            #complete
+        }
+    }
+}
+
+fn hooks(plugins: &[Plugin], hook: &str) -> TokenStream {
+    plugins
+        .iter()
+        .enumerate()
+        .map(|(index, plugin)| plugin.hook(index, hook))
+        .collect()
+}
@@ -0,0 +1,90 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{Block, Expr, Ident, Pat, Result, Token};
+
+use crate::errors::{parse_body, parse_else, parse_in, parse_pat};
+use crate::{else_branch, modify_breaks, BREAK_FLAG};
+
+mod kw {
+    syn::custom_keyword!(size);
+    syn::custom_keyword!(err);
+}
+
+pub struct ReadChunks {
+    var: Pat,
+    reader: Expr,
+    size: Option<Expr>,
+    body: Block,
+    err_var: Ident,
+    err_block: Block,
+    else_block: Block,
+}
+
+impl Parse for ReadChunks {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let var = parse_pat(input)?;
+        parse_in(input)?;
+        let reader = Expr::parse_without_eager_brace(input)?;
+        let size = if input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+            input.parse::<kw::size>()?;
+            Some(Expr::parse_without_eager_brace(input)?)
+        } else {
+            None
+        };
+        let body = parse_body(input)?;
+        input.parse::<kw::err>()?;
+        let err_var: Ident = input.parse()?;
+        let err_block: Block = input.parse()?;
+        let else_block = parse_else(input)?;
+        Ok(ReadChunks {
+            var,
+            reader,
+            size,
+            body,
+            err_var,
+            err_block,
+            else_block,
+        })
+    }
+}
+
+pub fn expand(mut input: ReadChunks) -> TokenStream {
+    modify_breaks(&mut input.body);
+
+    let var = input.var;
+    let reader = input.reader;
+    let size = input.size.unwrap_or_else(|| syn::parse_quote!(8192));
+    let body = input.body;
+    let err_var = input.err_var;
+    let err_block = input.err_block;
+    let else_branch = else_branch(&input.else_block);
+
+    // an error ends the stream, so it is handled after the loop instead of the else block;
+    // interrupted reads are retried like `Read::read_exact` does
+    quote! {
+        {
+            let mut #BREAK_FLAG = false;
+            let mut _for_else_reader = #reader;
+            let mut _for_else_buffer = ::std::vec![0u8; #size];
+            let mut _for_else_error = ::std::option::Option::None;
+            loop {
+                let _for_else_len = match ::std::io::Read::read(&mut _for_else_reader, &mut _for_else_buffer) {
+                    ::std::result::Result::Ok(0) => break,
+                    ::std::result::Result::Ok(len) => len,
+                    ::std::result::Result::Err(err) if err.kind() == ::std::io::ErrorKind::Interrupted => continue,
+                    ::std::result::Result::Err(err) => {
+                        _for_else_error = ::std::option::Option::Some(err);
+                        break;
+                    }
+                };
+                let #var: &[u8] = &_for_else_buffer[.._for_else_len];
+                #body
+            }
+            if let ::std::option::Option::Some(#err_var) = _for_else_error
+                #err_block
+            else #else_branch
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{Block, Expr, ExprLit, Lit, Pat, Result, Token};
+
+use crate::errors::{parse_body, parse_else, parse_in, parse_pat, ErrorCode};
+use crate::{else_branch, modify_breaks, BREAK_FLAG};
+
+mod kw {
+    syn::custom_keyword!(matches);
+    syn::custom_keyword!(captures);
+}
+
+enum Search {
+    Matches,
+    Captures,
+}
+
+pub struct RegexSearch {
+    var: Pat,
+    search: Search,
+    regex: Expr,
+    haystack: Expr,
+    body: Block,
+    else_block: Block,
+}
+
+impl Parse for RegexSearch {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let var = parse_pat(input)?;
+        parse_in(input)?;
+        let lookahead = input.lookahead1();
+        let search = if lookahead.peek(kw::matches) {
+            input.parse::<kw::matches>()?;
+            Search::Matches
+        } else if lookahead.peek(kw::captures) {
+            input.parse::<kw::captures>()?;
+            Search::Captures
+        } else {
+            return Err(ErrorCode::UnknownOption.tag(lookahead.error()));
+        };
+        let regex = Expr::parse_without_eager_brace(input)?;
+        input.parse::<Token![,]>()?;
+        let haystack = Expr::parse_without_eager_brace(input)?;
+        let body = parse_body(input)?;
+        let else_block = parse_else(input)?;
+        Ok(RegexSearch {
+            var,
+            search,
+            regex,
+            haystack,
+            body,
+            else_block,
+        })
+    }
+}
+
+pub fn expand(mut input: RegexSearch) -> TokenStream {
+    modify_breaks(&mut input.body);
+
+    let var = input.var;
+    let haystack = input.haystack;
+    let body = input.body;
+    let else_branch = else_branch(&input.else_block);
+
+    // a literal pattern is compiled once, on first use; compiling it in a nested function keeps
+    // clippy from flagging a regex compiled inside a loop when the search itself is in one
+    let regex = match input.regex {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(pattern),
+            ..
+        }) => quote! {
+            {
+                fn compile() -> ::regex::Regex {
+                    ::regex::Regex::new(#pattern).unwrap()
+                }
+                static REGEX: ::std::sync::OnceLock<::regex::Regex> = ::std::sync::OnceLock::new();
+                REGEX.get_or_init(compile)
+            }
+        },
+        regex => quote! { &#regex },
+    };
+    let iter = match input.search {
+        Search::Matches => quote! { ::regex::Regex::find_iter(#regex, #haystack) },
+        Search::Captures => quote! { ::regex::Regex::captures_iter(#regex, #haystack) },
+    };
+
+    quote! {
+        {
+            let mut #BREAK_FLAG = false;
+            for #var in #iter
+                #body
+            #else_branch
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{parse_quote, Block, Expr, Ident, Label, Lifetime, Result, Token};
+
+use crate::errors::{parse_body, parse_else};
+use crate::{expand_for, ForLoop};
+
+mod kw {
+    syn::custom_keyword!(times);
+    syn::custom_keyword!(delay);
+}
+
+pub struct RetryLoop {
+    label: Option<Label>,
+    attempt: Ident,
+    times: Expr,
+    delay: Option<Expr>,
+    body: Block,
+    else_block: Block,
+}
+
+impl Parse for RetryLoop {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let label = if input.peek(Lifetime) {
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        let attempt = if input.peek(Ident) && input.peek2(Token![in]) {
+            let attempt = input.parse()?;
+            input.parse::<Token![in]>()?;
+            attempt
+        } else {
+            Ident::new("attempt", Span::call_site())
+        };
+        let times = Expr::parse_without_eager_brace(input)?;
+        input.parse::<kw::times>()?;
+        let delay = if input.peek(kw::delay) {
+            input.parse::<kw::delay>()?;
+            Some(Expr::parse_without_eager_brace(input)?)
+        } else {
+            None
+        };
+        let body = parse_body(input)?;
+        let else_block = parse_else(input)?;
+        Ok(RetryLoop {
+            label,
+            attempt,
+            times,
+            delay,
+            body,
+            else_block,
+        })
+    }
+}
+
+pub fn expand(input: RetryLoop) -> TokenStream {
+    let RetryLoop {
+        label,
+        attempt,
+        times,
+        delay,
+        body,
+        else_block,
+    } = input;
+
+    // the attempts are a `for_!` loop over their numbers, which waits between them with the
+    // `cooldown` clause
+    let cooldown = delay.map(|delay| quote! { cooldown(#delay) });
+    let looped: ForLoop = parse_quote! {
+        #label #attempt in 1..=(#times) #cooldown #body else #else_block
+    };
+    expand_for(looped)
+}
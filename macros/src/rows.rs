@@ -0,0 +1,118 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{Block, Expr, Ident, Pat, Result, Token};
+
+use crate::errors::{parse_body, parse_else, parse_in, parse_pat, ErrorCode};
+use crate::max_errors::{self, MaxErrors};
+use crate::{else_branch, modify_breaks, BREAK_FLAG};
+
+mod kw {
+    syn::custom_keyword!(map);
+    syn::custom_keyword!(err);
+}
+
+pub struct RowsLoop {
+    var: Pat,
+    rows: Expr,
+    map: Option<Expr>,
+    max_errors: Option<MaxErrors>,
+    body: Block,
+    err_var: Ident,
+    err_block: Block,
+    else_block: Block,
+}
+
+impl Parse for RowsLoop {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let var = parse_pat(input)?;
+        parse_in(input)?;
+        let rows = Expr::parse_without_eager_brace(input)?;
+        let mut map = None;
+        let mut max_errors = None;
+        while input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+            let lookahead = input.lookahead1();
+            if map.is_none() && lookahead.peek(kw::map) {
+                input.parse::<kw::map>()?;
+                map = Some(Expr::parse_without_eager_brace(input)?);
+            } else if max_errors.is_none() && lookahead.peek(max_errors::kw::max_errors) {
+                max_errors = Some(input.parse()?);
+            } else {
+                return Err(ErrorCode::UnknownOption.tag(lookahead.error()));
+            }
+        }
+        let body = parse_body(input)?;
+        input.parse::<kw::err>()?;
+        let err_var: Ident = input.parse()?;
+        let err_block: Block = input.parse()?;
+        let else_block = parse_else(input)?;
+        Ok(RowsLoop {
+            var,
+            rows,
+            map,
+            max_errors,
+            body,
+            err_var,
+            err_block,
+            else_block,
+        })
+    }
+}
+
+pub fn expand(mut input: RowsLoop) -> TokenStream {
+    modify_breaks(&mut input.body);
+
+    let var = input.var;
+    let rows = input.rows;
+    let body = input.body;
+    let err_var = input.err_var;
+    let err_block = input.err_block;
+    let else_branch = else_branch(&input.else_block);
+
+    let max_errors = input.max_errors.as_ref().map(MaxErrors::setup);
+    let tolerate = input.max_errors.as_ref().map(MaxErrors::tolerate);
+
+    // the closure is passed through a function so that its parameter type is inferred from the row
+    let map = input.map.map(|map| {
+        quote! {
+            fn _for_else_map<R, T, E>(row: R, map: impl ::std::ops::FnOnce(R) -> ::std::result::Result<T, E>) -> ::std::result::Result<T, E> {
+                map(row)
+            }
+            let _for_else_row = match _for_else_map(_for_else_row, #map) {
+                ::std::result::Result::Ok(row) => row,
+                ::std::result::Result::Err(err) => {
+                    #tolerate
+                    _for_else_error = ::std::option::Option::Some(err);
+                    break;
+                }
+            };
+        }
+    });
+
+    // an error ends the result set, so it is handled after the loop instead of the else block
+    quote! {
+        {
+            let mut #BREAK_FLAG = false;
+            let mut _for_else_rows = #rows;
+            let mut _for_else_error = ::std::option::Option::None;
+            #max_errors
+            loop {
+                let _for_else_row = match _for_else_rows.next() {
+                    ::std::result::Result::Ok(::std::option::Option::Some(row)) => row,
+                    ::std::result::Result::Ok(::std::option::Option::None) => break,
+                    ::std::result::Result::Err(err) => {
+                        _for_else_error = ::std::option::Option::Some(err);
+                        break;
+                    }
+                };
+                #map
+                let #var = _for_else_row;
+                #body
+            }
+            if let ::std::option::Option::Some(#err_var) = _for_else_error
+                #err_block
+            else #else_branch
+        }
+    }
+}
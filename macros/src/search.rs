@@ -0,0 +1,89 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{Block, Expr, Ident, Label, Lifetime, Pat, Result, Token};
+
+use crate::errors::{parse_body, parse_in, parse_iterable, parse_pat};
+use crate::{replace_breaks, Slot};
+
+/// The value the search found.
+const FOUND: Slot = Slot("_for_else_found");
+
+pub struct SearchLoop {
+    label: Option<Label>,
+    var: Pat,
+    expr: Expr,
+    body: Block,
+    else_block: Option<Block>,
+}
+
+impl Parse for SearchLoop {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let label = if input.peek(Lifetime) {
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        let var = parse_pat(input)?;
+        parse_in(input)?;
+        let expr = parse_iterable(input)?;
+        let body = parse_body(input)?;
+        let else_block = if input.peek(Token![else]) {
+            input.parse::<Token![else]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        Ok(SearchLoop {
+            label,
+            var,
+            expr,
+            body,
+            else_block,
+        })
+    }
+}
+
+pub fn expand(mut input: SearchLoop) -> TokenStream {
+    // `break value` ends the search with that value, breaks to other labels leave enclosing loops
+    let label = input.label.as_ref().map(|label| label.name.clone());
+    replace_breaks(&mut input.body, &|expr_break| {
+        if expr_break.label.is_some() && expr_break.label != label {
+            return quote! { { #expr_break; } };
+        }
+        let found = match &expr_break.expr {
+            Some(found) => quote! { #found },
+            None => quote! { () },
+        };
+        let break_token = expr_break.break_token;
+        quote! {
+            {
+                #FOUND = ::core::option::Option::Some(#found);
+                #break_token #label;
+            }
+        }
+    });
+
+    let label = input.label;
+    let var = input.var;
+    let expr = input.expr;
+    let body = input.body;
+    let value = Ident::new("_for_else_value", Span::mixed_site());
+    let found = match input.else_block {
+        Some(else_block) => quote! {
+            match #FOUND {
+                ::core::option::Option::Some(#value) => #value,
+                ::core::option::Option::None => #else_block,
+            }
+        },
+        None => quote! { #FOUND },
+    };
+
+    quote! {
+        {
+            let mut #FOUND = ::core::option::Option::None;
+            #label for #var in #expr #body
+            #found
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{parenthesized, parse_quote, Block, Expr, Ident, Pat, Result, Token};
+
+use crate::errors::{parse_body, parse_else, parse_in, parse_iterable, parse_pat, ErrorCode};
+use crate::{else_branch, replace_breaks, replace_continues, BREAK_FLAG};
+
+mod kw {
+    syn::custom_keyword!(limit);
+    syn::custom_keyword!(clone);
+}
+
+pub struct SpawnLoop {
+    var: Pat,
+    expr: Expr,
+    limit: Option<Expr>,
+    clones: Vec<Ident>,
+    body: Block,
+    else_block: Block,
+}
+
+impl Parse for SpawnLoop {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let var = parse_pat(input)?;
+        parse_in(input)?;
+        let expr = parse_iterable(input)?;
+        let mut limit = None;
+        let mut clones = Vec::new();
+        while input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+            let lookahead = input.lookahead1();
+            if lookahead.peek(kw::limit) && limit.is_none() {
+                input.parse::<kw::limit>()?;
+                limit = Some(Expr::parse_without_eager_brace(input)?);
+            } else if lookahead.peek(kw::clone) && clones.is_empty() {
+                input.parse::<kw::clone>()?;
+                let content;
+                parenthesized!(content in input);
+                clones = Punctuated::<Ident, Token![,]>::parse_separated_nonempty(&content)?
+                    .into_iter()
+                    .collect();
+            } else {
+                return Err(ErrorCode::UnknownOption.tag(lookahead.error()));
+            }
+        }
+        let body = parse_body(input)?;
+        let else_block = parse_else(input)?;
+        Ok(SpawnLoop {
+            var,
+            expr,
+            limit,
+            clones,
+            body,
+            else_block,
+        })
+    }
+}
+
+pub fn expand(mut input: SpawnLoop) -> TokenStream {
+    // every task reports whether its body hit a `break`
+    replace_breaks(&mut input.body, &|expr_break| match &expr_break.label {
+        Some(label) => {
+            let message = ErrorCode::LabeledBreakInTask
+                .message("labeled breaks cannot leave a task spawned by `spawn_for_!`");
+            quote_spanned! {label.span()=>
+                {
+                    ::std::compile_error!(#message);
+                }
+            }
+        }
+        None => match &expr_break.expr {
+            Some(value) => quote_spanned! {value.span()=>
+                {
+                    ::std::compile_error!("`spawn_for_!` loops do not evaluate to a value, so their `break`s cannot have one");
+                }
+            },
+            None => quote! {
+                {
+                    return true;
+                }
+            },
+        },
+    });
+    // a `continue` ends the task without a `break`, as the label is never written by the caller
+    replace_continues(
+        &mut input.body,
+        &parse_quote! { '_for_else_spawn },
+        &parse_quote! { return false },
+    );
+    // the item is moved into the task under a name user code cannot see, so that it does not
+    // shadow a variable of the same name listed in `clone`
+    let item = Ident::new("_for_else_item", Span::mixed_site());
+
+    let var = input.var;
+    let expr = input.expr;
+    // a limit of 0 would never spawn a task, so it means no limit, as leaving it out does
+    let limit = match input.limit {
+        Some(limit) => quote! {
+            match #limit {
+                0 => ::std::primitive::usize::MAX,
+                limit => limit,
+            }
+        },
+        None => quote! { ::std::primitive::usize::MAX },
+    };
+    let clones = input.clones;
+    let body = input.body;
+    let else_branch = else_branch(&input.else_block);
+
+    quote! {
+        {
+            let mut #BREAK_FLAG = false;
+            let _for_else_limit: ::std::primitive::usize = #limit;
+            let mut _for_else_items = ::std::iter::IntoIterator::into_iter(#expr);
+            let mut _for_else_tasks = ::tokio::task::JoinSet::new();
+            loop {
+                while _for_else_tasks.len() < _for_else_limit {
+                    match ::std::iter::Iterator::next(&mut _for_else_items) {
+                        ::std::option::Option::Some(#item) => {
+                            #(let #clones = ::std::clone::Clone::clone(&#clones);)*
+                            _for_else_tasks.spawn(async move {
+                                let #var = #item;
+                                #body
+                                #[allow(unreachable_code)]
+                                false
+                            });
+                        }
+                        ::std::option::Option::None => break,
+                    }
+                }
+                match _for_else_tasks.join_next().await {
+                    ::std::option::Option::None => break,
+                    ::std::option::Option::Some(::std::result::Result::Ok(true)) => {
+                        #BREAK_FLAG = true;
+                        break;
+                    }
+                    ::std::option::Option::Some(::std::result::Result::Ok(false)) => {}
+                    ::std::option::Option::Some(::std::result::Result::Err(err)) => {
+                        if err.is_panic() {
+                            ::std::panic::resume_unwind(err.into_panic());
+                        }
+                    }
+                }
+            }
+            // dropping the set aborts the tasks still running
+            ::std::mem::drop(_for_else_tasks);
+            #else_branch
+        }
+    }
+}
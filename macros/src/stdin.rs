@@ -0,0 +1,160 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{Block, Expr, Ident, Pat, Result, Token, Type};
+
+use crate::errors::{parse_body, parse_else, parse_in, parse_pat, ErrorCode};
+use crate::max_errors::MaxErrors;
+use crate::{else_branch, modify_breaks, BREAK_FLAG};
+
+mod kw {
+    syn::custom_keyword!(tokens);
+    syn::custom_keyword!(lines);
+    syn::custom_keyword!(from);
+    syn::custom_keyword!(err);
+}
+
+enum Unit {
+    Tokens,
+    Lines,
+}
+
+pub struct StdinLoop {
+    var: Pat,
+    ty: Option<Type>,
+    unit: Unit,
+    source: Option<Expr>,
+    max_errors: Option<MaxErrors>,
+    body: Block,
+    err_clause: Option<(Ident, Block)>,
+    else_block: Block,
+}
+
+impl Parse for StdinLoop {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let var = parse_pat(input)?;
+        let ty = if input.peek(Token![:]) {
+            input.parse::<Token![:]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        parse_in(input)?;
+        let lookahead = input.lookahead1();
+        let unit = if lookahead.peek(kw::tokens) {
+            input.parse::<kw::tokens>()?;
+            Unit::Tokens
+        } else if lookahead.peek(kw::lines) {
+            input.parse::<kw::lines>()?;
+            Unit::Lines
+        } else {
+            return Err(ErrorCode::UnknownOption.tag(lookahead.error()));
+        };
+        let source = if input.peek(kw::from) {
+            input.parse::<kw::from>()?;
+            Some(Expr::parse_without_eager_brace(input)?)
+        } else {
+            None
+        };
+        let max_errors = if input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        let body = parse_body(input)?;
+        let err_clause = if input.peek(kw::err) {
+            input.parse::<kw::err>()?;
+            Some((input.parse()?, input.parse()?))
+        } else {
+            None
+        };
+        let else_block = parse_else(input)?;
+        Ok(StdinLoop {
+            var,
+            ty,
+            unit,
+            source,
+            max_errors,
+            body,
+            err_clause,
+            else_block,
+        })
+    }
+}
+
+pub fn expand(mut input: StdinLoop) -> TokenStream {
+    modify_breaks(&mut input.body);
+
+    let var = input.var;
+    let body = input.body;
+    let else_branch = else_branch(&input.else_block);
+
+    let source = match input.source {
+        Some(source) => quote! { #source },
+        None => quote! { ::std::io::stdin().lock() },
+    };
+    let split = match input.unit {
+        Unit::Tokens => quote! {
+            _for_else_pending.extend(line.split_whitespace().map(::std::string::ToString::to_string));
+        },
+        Unit::Lines => quote! {
+            _for_else_pending.push_back(::std::string::ToString::to_string(line.trim_end_matches(&['\n', '\r'][..])));
+        },
+    };
+    let parsed = match input.ty {
+        Some(ty) => quote! { ::std::primitive::str::parse::<#ty>(&_for_else_unit) },
+        None => quote! { ::std::primitive::str::parse(&_for_else_unit) },
+    };
+    let max_errors = input.max_errors.as_ref().map(MaxErrors::setup);
+    let tolerate = input.max_errors.as_ref().map(MaxErrors::tolerate);
+    let on_err = match input.err_clause {
+        Some((err_var, mut err_block)) => {
+            modify_breaks(&mut err_block);
+            quote! {
+                ::std::result::Result::Err(#err_var) => {
+                    #tolerate
+                    #err_block
+                }
+            }
+        }
+        None => quote! {
+            ::std::result::Result::Err(err) => {
+                #tolerate
+                ::std::panic!("for_stdin_!: failed to parse {:?}: {:?}", _for_else_unit, err)
+            }
+        },
+    };
+
+    quote! {
+        {
+            let mut #BREAK_FLAG = false;
+            // the reader is borrowed rather than wrapped, so that nothing past the last line read
+            // is lost to a buffer of its own when the loop ends early
+            let _for_else_reader = &mut #source;
+            let mut _for_else_pending = ::std::collections::VecDeque::<::std::string::String>::new();
+            #max_errors
+            loop {
+                let _for_else_unit = match _for_else_pending.pop_front() {
+                    ::std::option::Option::Some(unit) => unit,
+                    ::std::option::Option::None => {
+                        let mut line = ::std::string::String::new();
+                        match ::std::io::BufRead::read_line(_for_else_reader, &mut line) {
+                            ::std::result::Result::Ok(0) => break,
+                            ::std::result::Result::Ok(_) => {
+                                #split
+                                continue;
+                            }
+                            ::std::result::Result::Err(err) => ::std::panic!("for_stdin_!: failed to read input: {}", err),
+                        }
+                    }
+                };
+                match #parsed {
+                    ::std::result::Result::Ok(#var) => #body
+                    #on_err
+                }
+            }
+            #else_branch
+        }
+    }
+}
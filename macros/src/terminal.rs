@@ -0,0 +1,134 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{Block, Expr, Ident, Pat, Result, Token};
+
+use crate::errors::{parse_body, parse_else, parse_in, parse_pat};
+use crate::{else_branch, modify_breaks, BREAK_FLAG};
+
+mod kw {
+    syn::custom_keyword!(idle);
+    syn::custom_keyword!(tick);
+    syn::custom_keyword!(err);
+}
+
+pub struct TerminalLoop {
+    var: Pat,
+    idle: Expr,
+    tick: Option<Expr>,
+    body: Block,
+    tick_block: Option<Block>,
+    err_clause: Option<(Ident, Block)>,
+    else_block: Block,
+}
+
+impl Parse for TerminalLoop {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let var = parse_pat(input)?;
+        parse_in(input)?;
+        input.parse::<kw::idle>()?;
+        let idle = Expr::parse_without_eager_brace(input)?;
+        let tick = if input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+            input.parse::<kw::tick>()?;
+            Some(Expr::parse_without_eager_brace(input)?)
+        } else {
+            None
+        };
+        let body = parse_body(input)?;
+        let tick_block = if input.peek(kw::tick) {
+            input.parse::<kw::tick>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        let err_clause = if input.peek(kw::err) {
+            input.parse::<kw::err>()?;
+            Some((input.parse()?, input.parse()?))
+        } else {
+            None
+        };
+        let else_block = parse_else(input)?;
+        Ok(TerminalLoop {
+            var,
+            idle,
+            tick,
+            body,
+            tick_block,
+            err_clause,
+            else_block,
+        })
+    }
+}
+
+pub fn expand(mut input: TerminalLoop) -> TokenStream {
+    modify_breaks(&mut input.body);
+    if let Some(tick_block) = &mut input.tick_block {
+        modify_breaks(tick_block);
+    }
+
+    let var = input.var;
+    let idle = input.idle;
+    let body = input.body;
+    let else_branch = else_branch(&input.else_block);
+
+    // without a tick, each poll waits for the rest of the idle period
+    let timeout = match input.tick {
+        Some(tick) => quote! {
+            ::std::cmp::min(#tick, _for_else_idle - _for_else_quiet)
+        },
+        None => quote! { _for_else_idle - _for_else_quiet },
+    };
+    let tick_block = input.tick_block.map(|tick_block| quote! { #tick_block });
+    let on_err = match input.err_clause {
+        Some((err_var, err_block)) => quote! {
+            if let ::std::option::Option::Some(#err_var) = _for_else_error
+                #err_block
+        },
+        None => quote! {
+            if let ::std::option::Option::Some(err) = _for_else_error {
+                ::std::panic!("terminal_for_!: failed to read terminal events: {}", err);
+            }
+        },
+    };
+
+    // an error reading events ends the loop, so it is handled after the loop instead of the
+    // else block
+    quote! {
+        {
+            let mut #BREAK_FLAG = false;
+            let _for_else_idle: ::std::time::Duration = #idle;
+            let mut _for_else_last_event = ::std::time::Instant::now();
+            let mut _for_else_error = ::std::option::Option::None;
+            loop {
+                let _for_else_quiet = _for_else_last_event.elapsed();
+                if _for_else_quiet >= _for_else_idle {
+                    break;
+                }
+                let _for_else_event = match ::crossterm::event::poll(#timeout) {
+                    ::std::result::Result::Ok(true) => match ::crossterm::event::read() {
+                        ::std::result::Result::Ok(event) => event,
+                        ::std::result::Result::Err(err) => {
+                            _for_else_error = ::std::option::Option::Some(err);
+                            break;
+                        }
+                    },
+                    ::std::result::Result::Ok(false) => {
+                        #tick_block
+                        continue;
+                    }
+                    ::std::result::Result::Err(err) => {
+                        _for_else_error = ::std::option::Option::Some(err);
+                        break;
+                    }
+                };
+                _for_else_last_event = ::std::time::Instant::now();
+                let #var = _for_else_event;
+                #body
+            }
+            if _for_else_error.is_some() {
+                #on_err
+            } else #else_branch
+        }
+    }
+}
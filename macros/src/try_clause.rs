@@ -0,0 +1,88 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote_spanned;
+use syn::parse::{Parse, ParseStream};
+use syn::spanned::Spanned;
+use syn::visit_mut::{self, VisitMut};
+use syn::{parse2, Block, Expr, ExprTry, Ident, Item, Result, Token};
+
+/// The `try continue` or `try break` clause, which makes a `?` in the loop body go on with the
+/// next item or break out of the loop when it meets `None` or an `Err`, instead of returning
+/// from the enclosing function.
+#[derive(Clone, Copy)]
+pub enum TryClause {
+    Continue,
+    Break,
+}
+
+impl TryClause {
+    pub fn peek(input: ParseStream) -> bool {
+        input.peek(Token![try]) && (input.peek2(Token![continue]) || input.peek2(Token![break]))
+    }
+}
+
+impl Parse for TryClause {
+    fn parse(input: ParseStream) -> Result<Self> {
+        input.parse::<Token![try]>()?;
+        if input.peek(Token![continue]) {
+            input.parse::<Token![continue]>()?;
+            Ok(TryClause::Continue)
+        } else {
+            input.parse::<Token![break]>()?;
+            Ok(TryClause::Break)
+        }
+    }
+}
+
+/// Rewrites every `?` of the loop `body` into a `match` that evaluates to the value on success
+/// and to `failed` otherwise, returning whether there was any.
+///
+/// A `?` inside a closure, an async block, a try block or a nested item belongs to that
+/// expression rather than to the loop, so it is left alone, as is any `?` inside a macro
+/// invocation, whose tokens are not parsed.
+pub fn rewrite(body: &mut Block, failed: TokenStream) -> bool {
+    let mut rewriter = Rewriter {
+        failed,
+        found: false,
+    };
+    rewriter.visit_block_mut(body);
+    rewriter.found
+}
+
+struct Rewriter {
+    failed: TokenStream,
+    found: bool,
+}
+
+impl VisitMut for Rewriter {
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        if let Expr::Closure(_) | Expr::Async(_) | Expr::TryBlock(_) = expr {
+            return;
+        }
+        visit_mut::visit_expr_mut(self, expr);
+        if let Expr::Try(ExprTry {
+            expr: operand,
+            question_token,
+            ..
+        }) = expr
+        {
+            self.found = true;
+            // the operand becomes a function argument, where its parentheses are unneeded
+            let operand = match &**operand {
+                Expr::Paren(paren) => &paren.expr,
+                operand => operand,
+            };
+            let failed = &self.failed;
+            // the binding is spanned like generated code, which lints about bindings leave alone
+            let value = Ident::new("_for_else_value", Span::mixed_site());
+            *expr = parse2(quote_spanned! {question_token.span()=>
+                match ::for_else::Fallible::into_option(#operand) {
+                    ::core::option::Option::Some(#value) => #value,
+                    ::core::option::Option::None => #failed,
+                }
+            })
+            .unwrap();
+        }
+    }
+
+    fn visit_item_mut(&mut self, _: &mut Item) {}
+}
@@ -0,0 +1,85 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{parse_quote, Block, Expr, Ident, Label, Lifetime, Pat, Result};
+
+use crate::errors::{parse_body, parse_else, parse_in, parse_iterable, parse_pat};
+use crate::{expand_for, ForLoop};
+
+mod kw {
+    syn::custom_keyword!(catch);
+}
+
+pub struct TryLoop {
+    label: Option<Label>,
+    var: Pat,
+    expr: Expr,
+    body: Block,
+    err_var: Ident,
+    catch_block: Block,
+    else_block: Block,
+}
+
+impl Parse for TryLoop {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let label = if input.peek(Lifetime) {
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        let var = parse_pat(input)?;
+        parse_in(input)?;
+        let expr = parse_iterable(input)?;
+        let body = parse_body(input)?;
+        input.parse::<kw::catch>()?;
+        let err_var = input.parse()?;
+        let catch_block = input.parse()?;
+        let else_block = parse_else(input)?;
+        Ok(TryLoop {
+            label,
+            var,
+            expr,
+            body,
+            err_var,
+            catch_block,
+            else_block,
+        })
+    }
+}
+
+pub fn expand(input: TryLoop) -> TokenStream {
+    let TryLoop {
+        label,
+        var,
+        expr,
+        body,
+        err_var,
+        catch_block,
+        else_block,
+    } = input;
+
+    // the loop is a `for_!` loop over the results, whose first error is kept and breaks it,
+    // so that the `else` block runs only if every item was `Ok` and the body never broke; the
+    // `match` is a statement of its own, where the `break`s of its arms are rewritten
+    let looped: ForLoop = parse_quote! {
+        #label _for_else_result in #expr {
+            match _for_else_result {
+                ::core::result::Result::Ok(#var) => #body
+                ::core::result::Result::Err(_for_else_err) => {
+                    _for_else_error = ::core::option::Option::Some(_for_else_err);
+                    break;
+                }
+            }
+        } else #else_block
+    };
+    let looped = expand_for(looped);
+
+    quote! {
+        {
+            let mut _for_else_error = ::core::option::Option::None;
+            #looped
+            if let ::core::option::Option::Some(#err_var) = _for_else_error
+                #catch_block
+        }
+    }
+}
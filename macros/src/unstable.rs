@@ -0,0 +1,48 @@
+use proc_macro2::Span;
+use syn::Result;
+
+use crate::errors::ErrorCode;
+
+/// Experimental syntax, each piece gated behind its own `unstable-*` cargo feature
+/// so that it can change without breaking users of the stable syntax.
+#[derive(Clone, Copy)]
+pub enum Unstable {
+    /// `unsafe must_break` in `for_!`.
+    UncheckedMustBreak,
+}
+
+impl Unstable {
+    fn feature(self) -> &'static str {
+        match self {
+            Unstable::UncheckedMustBreak => "unstable-unchecked",
+        }
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            Unstable::UncheckedMustBreak => "`unsafe must_break`",
+        }
+    }
+
+    fn enabled(self) -> bool {
+        match self {
+            Unstable::UncheckedMustBreak => cfg!(feature = "unstable-unchecked"),
+        }
+    }
+
+    /// Fails with an error at `span` naming the feature to enable, unless it is enabled.
+    pub fn require(self, span: Span) -> Result<()> {
+        if self.enabled() {
+            Ok(())
+        } else {
+            Err(ErrorCode::Unstable.error(
+                span,
+                format!(
+                    "{} is experimental and requires the `{}` feature of `for-else`",
+                    self.description(),
+                    self.feature()
+                ),
+            ))
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use std::cell::Cell;
+
+use proc_macro2::{Span, TokenStream};
+#[cfg(not(for_else_nightly))]
+use quote::{format_ident, quote_spanned};
+
+thread_local! {
+    static SILENCED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `expand` without reporting warnings, for expansions that are compared rather than
+/// compiled, since the tokens of a warning depend on the compiler.
+pub fn silenced<T>(expand: impl FnOnce() -> T) -> T {
+    SILENCED.with(|silenced| silenced.set(true));
+    let expansion = expand();
+    SILENCED.with(|silenced| silenced.set(false));
+    expansion
+}
+
+/// The non-fatal problems the macros report, explained in the crate documentation.
+///
+/// Stable Rust gives procedural macros no way to emit warnings, so a warning is the use of a
+/// deprecated item whose note is the message, which `#[allow(deprecated)]` silences. On a
+/// nightly compiler, which the build script detects, it is a compiler warning instead.
+#[derive(Clone, Copy)]
+pub enum Warning {
+    /// The iterable has no items, so the body never runs and the `else` block always does.
+    EmptyIterable,
+    /// The only `break`s of the loop are unlabeled ones in nested loops.
+    BreakInInnerLoop,
+    /// The loop never breaks, so its `else` block always runs.
+    NeverBreaks,
+    /// The `else` block is empty and no clause needs the macro.
+    EmptyElse,
+}
+
+impl Warning {
+    fn name(self) -> &'static str {
+        match self {
+            Warning::EmptyIterable => "empty_iterable",
+            Warning::BreakInInnerLoop => "break_in_inner_loop",
+            Warning::NeverBreaks => "loop_never_breaks",
+            Warning::EmptyElse => "empty_else",
+        }
+    }
+
+    fn message(self) -> &'static str {
+        match self {
+            Warning::EmptyIterable => {
+                "this iterable has no items, so the body never runs and the `else` block \
+                 always does"
+            }
+            Warning::BreakInInnerLoop => {
+                "this `break` only leaves the inner loop, so it never skips the `else` block; \
+                 label the `for_!` loop (`'outer: x in ...`) and use `break 'outer`"
+            }
+            Warning::NeverBreaks => {
+                "this loop never breaks, so its `else` block always runs; \
+                 move the block after a plain loop"
+            }
+            Warning::EmptyElse => "this `else` block is empty, so a plain loop does the same",
+        }
+    }
+
+    /// Reports the warning at `span`, returning the statements to put in the expansion.
+    #[cfg(not(for_else_nightly))]
+    pub fn emit(self, span: Span) -> TokenStream {
+        if SILENCED.with(Cell::get) {
+            return TokenStream::new();
+        }
+        let name = format_ident!("{}", self.name(), span = span);
+        let message = self.message();
+        quote_spanned! {span=>
+            {
+                #[deprecated(note = #message)]
+                #[allow(non_camel_case_types)]
+                struct #name;
+                let _ = #name;
+            }
+        }
+    }
+
+    /// Reports the warning at `span`, returning the statements to put in the expansion.
+    #[cfg(for_else_nightly)]
+    pub fn emit(self, span: Span) -> TokenStream {
+        if SILENCED.with(Cell::get) {
+            return TokenStream::new();
+        }
+        proc_macro::Diagnostic::spanned(span.unwrap(), proc_macro::Level::Warning, self.message())
+            .help(format!(
+                "this is the `{}` warning of `for-else`",
+                self.name()
+            ))
+            .emit();
+        TokenStream::new()
+    }
+}
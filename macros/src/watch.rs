@@ -0,0 +1,130 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{Block, Expr, Ident, Pat, Result, Token};
+
+use crate::errors::{parse_body, parse_else, parse_in, parse_pat};
+use crate::{else_branch, modify_breaks, BREAK_FLAG};
+
+mod kw {
+    syn::custom_keyword!(recursive);
+    syn::custom_keyword!(idle);
+    syn::custom_keyword!(err);
+}
+
+pub struct WatchLoop {
+    var: Pat,
+    recursive: bool,
+    path: Expr,
+    idle: Expr,
+    body: Block,
+    err_clause: Option<(Ident, Block)>,
+    else_block: Block,
+}
+
+impl Parse for WatchLoop {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let var = parse_pat(input)?;
+        parse_in(input)?;
+        let recursive = if input.peek(kw::recursive) {
+            input.parse::<kw::recursive>()?;
+            true
+        } else {
+            false
+        };
+        let path = Expr::parse_without_eager_brace(input)?;
+        input.parse::<Token![,]>()?;
+        input.parse::<kw::idle>()?;
+        let idle = Expr::parse_without_eager_brace(input)?;
+        let body = parse_body(input)?;
+        let err_clause = if input.peek(kw::err) {
+            input.parse::<kw::err>()?;
+            Some((input.parse()?, input.parse()?))
+        } else {
+            None
+        };
+        let else_block = parse_else(input)?;
+        Ok(WatchLoop {
+            var,
+            recursive,
+            path,
+            idle,
+            body,
+            err_clause,
+            else_block,
+        })
+    }
+}
+
+pub fn expand(mut input: WatchLoop) -> TokenStream {
+    modify_breaks(&mut input.body);
+
+    let var = input.var;
+    let path = input.path;
+    let idle = input.idle;
+    let body = input.body;
+    let else_branch = else_branch(&input.else_block);
+
+    let mode = if input.recursive {
+        quote! { ::notify::RecursiveMode::Recursive }
+    } else {
+        quote! { ::notify::RecursiveMode::NonRecursive }
+    };
+    let on_err = match input.err_clause {
+        Some((err_var, mut err_block)) => {
+            modify_breaks(&mut err_block);
+            quote! { ::std::result::Result::Err(#err_var) => #err_block }
+        }
+        None => quote! {
+            ::std::result::Result::Err(err) => ::std::panic!("watch_for_!: failed to watch for changes: {}", err),
+        },
+    };
+
+    // errors setting up the watcher go through the same path as errors delivered with events,
+    // after which the loop ends because there is nothing to wait for
+    quote! {
+        {
+            let mut #BREAK_FLAG = false;
+            let _for_else_idle: ::std::time::Duration = #idle;
+            let (_for_else_sender, _for_else_events) = ::std::sync::mpsc::channel();
+            let mut _for_else_setup_error = ::std::option::Option::None;
+            let _for_else_watcher = match ::notify::recommended_watcher(_for_else_sender) {
+                ::std::result::Result::Ok(mut watcher) => {
+                    match ::notify::Watcher::watch(
+                        &mut watcher,
+                        ::std::convert::AsRef::<::std::path::Path>::as_ref(&#path),
+                        #mode,
+                    ) {
+                        ::std::result::Result::Ok(()) => ::std::option::Option::Some(watcher),
+                        ::std::result::Result::Err(err) => {
+                            _for_else_setup_error = ::std::option::Option::Some(err);
+                            ::std::option::Option::None
+                        }
+                    }
+                }
+                ::std::result::Result::Err(err) => {
+                    _for_else_setup_error = ::std::option::Option::Some(err);
+                    ::std::option::Option::None
+                }
+            };
+            loop {
+                let _for_else_item = if let ::std::option::Option::Some(err) = _for_else_setup_error.take() {
+                    ::std::result::Result::Err(err)
+                } else if _for_else_watcher.is_none() {
+                    break;
+                } else {
+                    match _for_else_events.recv_timeout(_for_else_idle) {
+                        ::std::result::Result::Ok(item) => item,
+                        ::std::result::Result::Err(_) => break,
+                    }
+                };
+                match _for_else_item {
+                    ::std::result::Result::Ok(#var) => #body
+                    #on_err
+                }
+            }
+            ::std::mem::drop(_for_else_watcher);
+            #else_branch
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{Block, Expr, Ident, Pat, Result, Token};
+
+use crate::errors::{parse_body, parse_else, parse_in, parse_pat};
+use crate::{else_branch, modify_breaks, BREAK_FLAG};
+
+pub struct WorklistLoop {
+    var: Pat,
+    queue: Ident,
+    init: Expr,
+    body: Block,
+    else_block: Block,
+}
+
+impl Parse for WorklistLoop {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let var = parse_pat(input)?;
+        parse_in(input)?;
+        let queue: Ident = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let init = Expr::parse_without_eager_brace(input)?;
+        let body = parse_body(input)?;
+        let else_block = parse_else(input)?;
+        Ok(WorklistLoop {
+            var,
+            queue,
+            init,
+            body,
+            else_block,
+        })
+    }
+}
+
+pub fn expand(mut input: WorklistLoop) -> TokenStream {
+    modify_breaks(&mut input.body);
+
+    let var = input.var;
+    let queue = input.queue;
+    let init = input.init;
+    let body = input.body;
+    let else_branch = else_branch(&input.else_block);
+
+    quote! {
+        {
+            let mut #BREAK_FLAG = false;
+            let mut #queue: ::std::collections::VecDeque<_> = ::std::iter::FromIterator::from_iter(#init);
+            while let ::std::option::Option::Some(#var) = #queue.pop_front()
+                #body
+            #else_branch
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use core::time::Duration;
+
+/// How long to wait between the iterations of a loop, given to the `cooldown` clause of
+/// [`for_!`](crate::for_) and the `interval` of [`eventually_!`](crate::eventually_).
+///
+/// A [`Duration`] waits the same time every time; [`Linear`] and [`Exponential`] wait longer
+/// after every wait, and [`jitter`](Backoff::jitter) adjusts the delays of any strategy.
+///
+/// # Example
+///
+/// ```rust
+/// use for_else::{for_, Backoff, Exponential};
+/// use std::time::Duration;
+///
+/// # fn try_connect(attempt: u32) -> bool { attempt == 3 }
+/// let backoff = Exponential::new(Duration::from_millis(1), 2).max(Duration::from_millis(4));
+///
+/// for_! { attempt in 1..=5 cooldown(backoff) {
+///     if try_connect(attempt) {
+///         println!("Connected on attempt {}", attempt);
+///         break;
+///     }
+/// } else {
+///     println!("Could not connect.");
+/// }}
+/// ```
+pub trait Backoff {
+    /// Returns how long to wait before the next iteration.
+    fn next_delay(&mut self) -> Duration;
+
+    /// Starts over from the first delay.
+    fn reset(&mut self);
+
+    /// Passes every delay through `jitter`, which typically adds or subtracts a random amount
+    /// to keep many loops from waking up at the same time.
+    fn jitter<F: FnMut(Duration) -> Duration>(self, jitter: F) -> Jittered<Self, F>
+    where
+        Self: Sized,
+    {
+        Jittered {
+            backoff: self,
+            jitter,
+        }
+    }
+}
+
+/// Waits the same time every time.
+impl Backoff for Duration {
+    fn next_delay(&mut self) -> Duration {
+        *self
+    }
+
+    fn reset(&mut self) {}
+}
+
+impl<B: Backoff + ?Sized> Backoff for &mut B {
+    fn next_delay(&mut self) -> Duration {
+        B::next_delay(self)
+    }
+
+    fn reset(&mut self) {
+        B::reset(self)
+    }
+}
+
+/// Waits `step` longer after every wait.
+#[derive(Debug, Clone)]
+pub struct Linear {
+    initial: Duration,
+    step: Duration,
+    next: Duration,
+}
+
+impl Linear {
+    /// Creates a backoff whose first delay is `initial`, growing by `step` after every wait.
+    pub fn new(initial: Duration, step: Duration) -> Self {
+        Linear {
+            initial,
+            step,
+            next: initial,
+        }
+    }
+}
+
+impl Backoff for Linear {
+    fn next_delay(&mut self) -> Duration {
+        let delay = self.next;
+        self.next = self.next.saturating_add(self.step);
+        delay
+    }
+
+    fn reset(&mut self) {
+        self.next = self.initial;
+    }
+}
+
+/// Waits `factor` times longer after every wait, up to an optional maximum.
+#[derive(Debug, Clone)]
+pub struct Exponential {
+    initial: Duration,
+    factor: u32,
+    max: Duration,
+    next: Duration,
+}
+
+impl Exponential {
+    /// Creates a backoff whose first delay is `initial`, multiplied by `factor` after every wait.
+    pub fn new(initial: Duration, factor: u32) -> Self {
+        Exponential {
+            initial,
+            factor,
+            max: Duration::MAX,
+            next: initial,
+        }
+    }
+
+    /// Caps every delay at `max`.
+    pub fn max(mut self, max: Duration) -> Self {
+        self.max = max;
+        self
+    }
+}
+
+impl Backoff for Exponential {
+    fn next_delay(&mut self) -> Duration {
+        let delay = self.next.min(self.max);
+        self.next = self.next.saturating_mul(self.factor);
+        delay
+    }
+
+    fn reset(&mut self) {
+        self.next = self.initial;
+    }
+}
+
+/// A backoff whose delays are adjusted by a function, created by [`Backoff::jitter`].
+#[derive(Debug, Clone)]
+pub struct Jittered<B, F> {
+    backoff: B,
+    jitter: F,
+}
+
+impl<B: Backoff, F: FnMut(Duration) -> Duration> Backoff for Jittered<B, F> {
+    fn next_delay(&mut self) -> Duration {
+        (self.jitter)(self.backoff.next_delay())
+    }
+
+    fn reset(&mut self) {
+        self.backoff.reset();
+    }
+}
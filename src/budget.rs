@@ -0,0 +1,217 @@
+#[cfg(target_has_atomic = "64")]
+use core::sync::atomic::{AtomicU64, Ordering};
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
+
+/// A bound on the iterations of a loop, given to the `max` clause of
+/// [`loop_else!`](crate::loop_else) and [`machine_loop_!`](crate::machine_loop_).
+///
+/// Every iteration first draws from the budget, and the loop finishes without a `break` once a
+/// draw fails. Integers are budgets of that many iterations, local to the loop, while a shared
+/// [`LoopBudget`] caps the total work of all loops that draw from it.
+pub trait Budget {
+    /// Takes one iteration from the budget, returning whether there was one left.
+    fn draw(&mut self) -> bool;
+}
+
+macro_rules! impl_budget {
+    ($($int:ty),*) => {
+        $(
+            /// Allows that many iterations, or none when not positive.
+            impl Budget for $int {
+                fn draw(&mut self) -> bool {
+                    if *self > 0 {
+                        *self -= 1;
+                        true
+                    } else {
+                        false
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_budget!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// A budget of iterations and, with the `std` feature, wall time that several loops can share.
+///
+/// Loops draw from the budget through a shared reference, so one budget created at the start of
+/// a request handler bounds all the loops that run while handling it, on any thread or task: the
+/// iterations are counted with an atomic. A budget without any limit never runs out.
+///
+/// Besides the `max` clause, the `within` clause of [`for_!`](crate::for_) takes a budget, which
+/// draws an iteration before every item and times the loop out once the budget runs out.
+///
+/// # Example
+///
+/// ```rust
+/// use for_else::{for_, loop_else, LoopBudget};
+///
+/// let budget = LoopBudget::new().iterations(5);
+///
+/// let mut first = 0;
+/// loop_else! { loop max &budget {
+///     first += 1;
+///     if first == 3 {
+///         break;
+///     }
+/// } else {
+///     println!("Ran out of budget before the third run.");
+/// }}
+///
+/// let mut second = 0;
+/// for_! { _ in 0..10 within(&budget) {
+///     second += 1;
+/// } else {
+///     println!("Done in time.");
+/// } timed_out {
+///     println!("Ran out of budget.");
+/// }}
+///
+/// assert_eq!((first, second), (3, 2));
+/// ```
+#[cfg(target_has_atomic = "64")]
+#[derive(Debug, Default)]
+pub struct LoopBudget {
+    /// The iterations left, counted only when `limited`.
+    iterations: AtomicU64,
+    limited: bool,
+    #[cfg(feature = "std")]
+    deadline: Option<Instant>,
+}
+
+/// Copies the iterations left at the time of the clone into a budget of its own.
+#[cfg(target_has_atomic = "64")]
+impl Clone for LoopBudget {
+    fn clone(&self) -> Self {
+        Self {
+            iterations: AtomicU64::new(self.iterations.load(Ordering::Relaxed)),
+            limited: self.limited,
+            #[cfg(feature = "std")]
+            deadline: self.deadline,
+        }
+    }
+}
+
+#[cfg(target_has_atomic = "64")]
+impl LoopBudget {
+    /// Creates a budget without any limit.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits the budget to `n` iterations in total.
+    pub fn iterations(mut self, n: u64) -> Self {
+        self.iterations = AtomicU64::new(n);
+        self.limited = true;
+        self
+    }
+
+    /// Limits the budget to the given time from now.
+    #[cfg(feature = "std")]
+    pub fn within(self, duration: Duration) -> Self {
+        self.until(Instant::now() + duration)
+    }
+
+    /// Limits the budget to the time before `deadline`.
+    #[cfg(feature = "std")]
+    pub fn until(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Takes one iteration from the budget, returning whether there was one left.
+    pub fn draw(&self) -> bool {
+        #[cfg(feature = "std")]
+        if self.is_past_deadline() {
+            return false;
+        }
+        // the count publishes no other memory, so the draws only need to be atomic
+        !self.limited
+            || self
+                .iterations
+                .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1))
+                .is_ok()
+    }
+
+    /// The number of iterations left, or `None` when iterations are not limited.
+    pub fn remaining_iterations(&self) -> Option<u64> {
+        self.limited
+            .then(|| self.iterations.load(Ordering::Relaxed))
+    }
+
+    #[cfg(feature = "std")]
+    fn is_past_deadline(&self) -> bool {
+        self.deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
+    /// Whether the next draw would fail.
+    pub fn is_exhausted(&self) -> bool {
+        #[cfg(feature = "std")]
+        if self.is_past_deadline() {
+            return true;
+        }
+        self.remaining_iterations() == Some(0)
+    }
+}
+
+#[cfg(target_has_atomic = "64")]
+impl Budget for LoopBudget {
+    fn draw(&mut self) -> bool {
+        LoopBudget::draw(self)
+    }
+}
+
+#[cfg(target_has_atomic = "64")]
+impl Budget for &LoopBudget {
+    fn draw(&mut self) -> bool {
+        LoopBudget::draw(self)
+    }
+}
+
+/// A time limit on a loop, given to the `within` clause of [`for_!`](crate::for_).
+///
+/// A [`Duration`] is counted from the start of the loop. A [`LoopBudget`] keeps its own
+/// deadline and also ends the loop once it runs out of iterations, drawing one before every
+/// item, so the loops sharing it stay within one total.
+#[cfg(feature = "std")]
+pub trait TimeLimit {
+    /// The time by which the loop starting now has to stop, if there is one.
+    fn deadline(&self) -> Option<Instant>;
+
+    /// Takes one iteration before an item, returning whether there was one left.
+    fn draw(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(feature = "std")]
+impl TimeLimit for Duration {
+    fn deadline(&self) -> Option<Instant> {
+        Instant::now().checked_add(*self)
+    }
+}
+
+#[cfg(all(feature = "std", target_has_atomic = "64"))]
+impl TimeLimit for LoopBudget {
+    fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    fn draw(&self) -> bool {
+        LoopBudget::draw(self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: TimeLimit + ?Sized> TimeLimit for &T {
+    fn deadline(&self) -> Option<Instant> {
+        T::deadline(self)
+    }
+
+    fn draw(&self) -> bool {
+        T::draw(self)
+    }
+}
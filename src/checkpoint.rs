@@ -0,0 +1,46 @@
+/// Where a [`for_!`](crate::for_) loop with a `checkpoint` clause keeps its progress between
+/// runs.
+///
+/// The checkpoint `K` is the number of items already walked, or the key of the last walked
+/// item when the clause names a `key`. The loop loads the checkpoint once before it starts,
+/// saves a new one periodically and after stopping on its `until` condition, and clears it once
+/// it finishes, whether or not it broke.
+pub trait CheckpointStore<K> {
+    /// Returns the checkpoint of a previous run, if there is one.
+    fn load(&mut self) -> Option<K>;
+
+    /// Replaces the stored checkpoint with `checkpoint`.
+    fn save(&mut self, checkpoint: &K);
+
+    /// Removes the stored checkpoint, so that the next run starts from the beginning.
+    fn clear(&mut self);
+}
+
+/// Keeps the checkpoint in memory, which mostly suits tests.
+impl<K: Clone> CheckpointStore<K> for Option<K> {
+    fn load(&mut self) -> Option<K> {
+        self.clone()
+    }
+
+    fn save(&mut self, checkpoint: &K) {
+        *self = Some(checkpoint.clone());
+    }
+
+    fn clear(&mut self) {
+        *self = None;
+    }
+}
+
+impl<K, S: CheckpointStore<K> + ?Sized> CheckpointStore<K> for &mut S {
+    fn load(&mut self) -> Option<K> {
+        S::load(self)
+    }
+
+    fn save(&mut self, checkpoint: &K) {
+        S::save(self, checkpoint)
+    }
+
+    fn clear(&mut self) {
+        S::clear(self)
+    }
+}
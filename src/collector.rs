@@ -0,0 +1,40 @@
+use alloc::vec::Vec;
+
+/// A collection that the `emit` statements of a collecting [`for_!`](crate::for_) loop push
+/// values onto.
+///
+/// The default collection is a [`Vec`]; `collect::<C>` in the loop header collects into `C`
+/// instead. Bounded collections hand values back once they are full, which runs the
+/// `overflow` clause of the loop.
+pub trait Collector<T>: Default {
+    /// Adds `value` to the collection, or hands it back if the collection is full.
+    fn push(&mut self, value: T) -> Result<(), T>;
+}
+
+impl<T> Collector<T> for Vec<T> {
+    fn push(&mut self, value: T) -> Result<(), T> {
+        Vec::push(self, value);
+        Ok(())
+    }
+}
+
+/// Full at its capacity `N`.
+#[cfg(feature = "heapless")]
+impl<T, const N: usize> Collector<T> for heapless::Vec<T, N> {
+    fn push(&mut self, value: T) -> Result<(), T> {
+        heapless::Vec::push(self, value)
+    }
+}
+
+/// Full at its inline capacity, so that it never allocates.
+#[cfg(feature = "smallvec")]
+impl<A: smallvec::Array> Collector<A::Item> for smallvec::SmallVec<A> {
+    fn push(&mut self, value: A::Item) -> Result<(), A::Item> {
+        if self.len() < self.inline_size() {
+            smallvec::SmallVec::push(self, value);
+            Ok(())
+        } else {
+            Err(value)
+        }
+    }
+}
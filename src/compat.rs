@@ -0,0 +1,18 @@
+//! Alternative names for the macros, for codebases that prefer them to the trailing underscore.
+//!
+//! The aliases are re-exports, so they behave exactly like the macros they name.
+//!
+//! ```rust
+//! use for_else::compat::forelse;
+//!
+//! forelse! { n in [1, 3, 5] {
+//!     if n % 2 == 0 {
+//!         break;
+//!     }
+//! } else {
+//!     println!("No even number found.");
+//! }}
+//! ```
+
+/// Alias of [`for_!`](crate::for_).
+pub use for_else_macros::for_ as forelse;
@@ -0,0 +1,59 @@
+use core::ops::ControlFlow;
+
+/// Helpers for a [`ControlFlow`] that is the outcome of a search, such as the result of
+/// [`Iterator::try_for_each`] or [`Iterator::try_fold`].
+///
+/// `Break` is read as a loop that broke with a value, and `Continue` as a loop that finished
+/// without a `break`, which is where a [`for_!`](crate::for_) loop would run its `else` block.
+///
+/// # Example
+///
+/// ```rust
+/// use core::ops::ControlFlow;
+/// use for_else::ControlFlowExt;
+///
+/// let first_even = [1, 3, 4, 5].into_iter().try_for_each(|n| {
+///     if n % 2 == 0 {
+///         ControlFlow::Break(n)
+///     } else {
+///         ControlFlow::Continue(())
+///     }
+/// });
+///
+/// assert_eq!(first_even.break_value_or(0), 4);
+/// ```
+pub trait ControlFlowExt<B, C> {
+    /// Returns the break value, or `default` if the loop finished without a `break`.
+    fn break_value_or(self, default: B) -> B;
+
+    /// Returns the break value, or computes one from the continue value if the loop finished
+    /// without a `break`, like an `else` block evaluating to a value.
+    fn break_value_or_else<F: FnOnce(C) -> B>(self, else_block: F) -> B;
+
+    /// Converts the outcome to a [`Result`] that is `Ok` with the break value when the loop
+    /// broke and `Err` with the continue value otherwise.
+    fn into_result(self) -> Result<B, C>;
+}
+
+impl<B, C> ControlFlowExt<B, C> for ControlFlow<B, C> {
+    fn break_value_or(self, default: B) -> B {
+        match self {
+            ControlFlow::Break(value) => value,
+            ControlFlow::Continue(_) => default,
+        }
+    }
+
+    fn break_value_or_else<F: FnOnce(C) -> B>(self, else_block: F) -> B {
+        match self {
+            ControlFlow::Break(value) => value,
+            ControlFlow::Continue(value) => else_block(value),
+        }
+    }
+
+    fn into_result(self) -> Result<B, C> {
+        match self {
+            ControlFlow::Break(value) => Ok(value),
+            ControlFlow::Continue(value) => Err(value),
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use core::ops::ControlFlow;
+
+/// A value that a `?` in the body of a [`for_!`](crate::for_) loop with a `try continue` or
+/// `try break` clause can unwrap.
+///
+/// When the value holds no output, such as `None` or an `Err`, the loop goes on with the next
+/// item or breaks instead of returning from the enclosing function. The error of a `Result` is
+/// dropped, so match on it in the body when it matters.
+pub trait Fallible {
+    /// The value a successful `?` evaluates to.
+    type Output;
+
+    /// Returns the output, or `None` if there is none.
+    fn into_option(self) -> Option<Self::Output>;
+}
+
+impl<T> Fallible for Option<T> {
+    type Output = T;
+
+    fn into_option(self) -> Option<T> {
+        self
+    }
+}
+
+impl<T, E> Fallible for Result<T, E> {
+    type Output = T;
+
+    fn into_option(self) -> Option<T> {
+        self.ok()
+    }
+}
+
+impl<B, C> Fallible for ControlFlow<B, C> {
+    type Output = C;
+
+    fn into_option(self) -> Option<C> {
+        match self {
+            ControlFlow::Continue(value) => Some(value),
+            ControlFlow::Break(_) => None,
+        }
+    }
+}
@@ -0,0 +1,53 @@
+#[cfg(target_has_atomic = "ptr")]
+use alloc::sync::Arc;
+use core::cell::Cell;
+#[cfg(not(for_else_loom))]
+use core::sync::atomic::{AtomicBool, Ordering};
+// the model-checking tests check the orderings with the atomics of loom
+#[cfg(for_else_loom)]
+use loom::sync::atomic::{AtomicBool, Ordering};
+
+/// A source of requests to stop a loop early, given to the `interrupt` clause of
+/// [`for_!`](crate::for_).
+///
+/// The loop asks the source before every iteration and stops without taking another item once
+/// it is interrupted, just like with an `until` condition. Flags set by signal handlers, such as
+/// the `Arc<AtomicBool>` registered with `signal_hook::flag::register` or set from a `ctrlc`
+/// handler, are sources, and so is a `tokio_util::sync::CancellationToken` with the
+/// `tokio-util` feature.
+pub trait Interruptible {
+    /// Whether the loop should stop now.
+    fn is_interrupted(&self) -> bool;
+}
+
+impl Interruptible for AtomicBool {
+    fn is_interrupted(&self) -> bool {
+        self.load(Ordering::Acquire)
+    }
+}
+
+impl Interruptible for Cell<bool> {
+    fn is_interrupted(&self) -> bool {
+        self.get()
+    }
+}
+
+impl<T: Interruptible + ?Sized> Interruptible for &T {
+    fn is_interrupted(&self) -> bool {
+        T::is_interrupted(self)
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<T: Interruptible + ?Sized> Interruptible for Arc<T> {
+    fn is_interrupted(&self) -> bool {
+        T::is_interrupted(self)
+    }
+}
+
+#[cfg(feature = "tokio-util")]
+impl Interruptible for tokio_util::sync::CancellationToken {
+    fn is_interrupted(&self) -> bool {
+        self.is_cancelled()
+    }
+}
@@ -0,0 +1,65 @@
+/// An iterator whose items may borrow from the iterator itself, looped over by
+/// [`for_!`](crate::for_) with `in lend`.
+///
+/// Unlike an [`Iterator`], a lending iterator can hand out items that point into a buffer it
+/// reuses, such as the records of a zero-copy parser, so an item has to be dropped before the
+/// next one is taken. The `for_!` loop does exactly that, one iteration at a time.
+///
+/// # Example
+///
+/// ```rust
+/// use for_else::{for_, LendingIterator};
+///
+/// /// Yields the lines of a buffer with the trailing whitespace trimmed in place.
+/// struct TrimmedLines {
+///     buffer: Vec<u8>,
+///     line: Vec<u8>,
+///     position: usize,
+/// }
+///
+/// impl LendingIterator for TrimmedLines {
+///     type Item<'a> = &'a [u8];
+///
+///     fn next(&mut self) -> Option<&[u8]> {
+///         if self.position >= self.buffer.len() {
+///             return None;
+///         }
+///         let rest = &self.buffer[self.position..];
+///         let end = rest.iter().position(|&b| b == b'\n').unwrap_or(rest.len());
+///         self.line.clear();
+///         self.line.extend_from_slice(rest[..end].trim_ascii_end());
+///         self.position += end + 1;
+///         Some(&self.line)
+///     }
+/// }
+///
+/// let lines = TrimmedLines { buffer: b"a  \nb\t\nc".to_vec(), line: Vec::new(), position: 0 };
+///
+/// for_! { line in lend lines {
+///     if line.is_empty() {
+///         break;
+///     }
+/// } else {
+///     println!("No empty lines.");
+/// }}
+/// ```
+pub trait LendingIterator {
+    /// The type of the items, which may borrow from the iterator for `'a`.
+    type Item<'a>
+    where
+        Self: 'a;
+
+    /// Returns the next item, or `None` once there are no more.
+    fn next(&mut self) -> Option<Self::Item<'_>>;
+}
+
+impl<L: LendingIterator + ?Sized> LendingIterator for &mut L {
+    type Item<'a>
+        = L::Item<'a>
+    where
+        Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        L::next(self)
+    }
+}
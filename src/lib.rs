@@ -43,171 +43,136 @@
 //! In this example, the program searches for the first prime number in the range [2100, 2110]. If a prime is found, it prints out the number. If no prime is found in the range, the `else` block within the `for_!` macro is executed, notifying the user.
 //!
 //! See the `for_!` macro documentation for more detailed examples and usage information.
+//!
+//! Code that cannot use the macros can get the same behavior from the [`Looped`] guard, whose
+//! `else` closure runs when it is dropped unless the loop called [`Looped::broke`].
+//! Searches written with [`Iterator::try_for_each`] can turn their
+//! [`ControlFlow`](core::ops::ControlFlow) outcome into a value with [`ControlFlowExt`].
+//!
+//! # Dumping expansions
+//!
+//! With the `dump` feature, setting the `FOR_ELSE_DUMP` environment variable to a directory
+//! during a build makes every macro invocation write its prettified expansion, preceded by the
+//! location of the invocation, to `<directory>/<crate name>.rs`, which each build of the crate
+//! starts over. Cargo does not rebuild a crate when only this variable changes, so touch or clean
+//! the crates whose expansions are wanted.
+//!
+//! ```bash
+//! FOR_ELSE_DUMP=target/for-else-dump cargo build --features for-else/dump
+//! ```
+//!
+//! # Error codes
+//!
+//! Errors reported by the macros start with a stable code in square brackets, such as
+//! `[FE0003]`, which stays the same across releases.
+//!
+//! ## FE0001
+//!
+//! The loop pattern is not followed by `in`. The header of every loop macro reads
+//! `pattern in expression`, as in a plain `for` loop.
+//!
+//! ## FE0002
+//!
+//! The loop body is missing. The header has to be followed by the body in braces; when the
+//! header expression itself ends with a block, wrap the expression in parentheses. The error
+//! quotes the tokens that were read as the header, which shows where the macro expected the
+//! body to start.
+//!
+//! ## FE0003
+//!
+//! The loop body is not followed by `else` and a block. `for_!` also accepts `must_break`
+//! in its place. When the `else` is missing right after the body, the error quotes the tokens
+//! that were read as the header and the body, since a header ending in a struct literal is
+//! usually split at the braces of the literal.
+//!
+//! ## FE0004
+//!
+//! The header contains an option that the macro does not know, such as a misspelled
+//! modifier. The error lists the options expected at that point. The same code is reported for
+//! tokens after the last clause of a loop, such as a misspelled clause name.
+//!
+//! ## FE0005
+//!
+//! The invocation uses experimental syntax without enabling the `unstable-*` feature that
+//! the error names.
+//!
+//! ## FE0006
+//!
+//! A labeled `break` was used in the body of `spawn_for_!` or `par_for_!`. The body runs as a
+//! separate task or on another thread, so a `break` can only end the search, not leave an
+//! enclosing loop.
+//!
+//! ## FE0007
+//!
+//! The expression after `in` could not be parsed. The error points at the first token that
+//! does not fit the expression and quotes the tokens that were read as the header.
+//!
+//! # Warnings
+//!
+//! `for_!`, `loop_else!`, `loop_!`, `while_let_!`, `do_while_!`, `cfor_!`, `for_await_!`,
+//! `try_for_!` and `retry_!` warn about loops that are probably not doing what they seem to:
+//!
+//! - `empty_iterable`: the iterable is an empty array or a range of literals such as `5..2`,
+//!   so the body never runs and the `else` block always does.
+//! - `break_in_inner_loop`: the only `break`s are unlabeled ones in loops nested in the body,
+//!   which never skip the `else` block.
+//! - `loop_never_breaks`: the body never breaks and nothing else stops the loop early, so the
+//!   `else` block always runs.
+//! - `empty_else`: the `else` block is empty.
+//!
+//! The last two only apply to loops that use nothing a plain loop lacks besides the `else`
+//! block, since they suggest a plain loop instead.
+//!
+//! Stable Rust has no way for a macro to emit a warning, so each warning is reported as the
+//! use of a deprecated item named after it, with the explanation as its note.
+//! `#[allow(deprecated)]` on the enclosing function or block silences it. On a nightly
+//! compiler, which the build script of the macros detects, they are reported as plain compiler
+//! warnings instead.
 
-extern crate proc_macro;
-
-use proc_macro::TokenStream;
-use quote::quote;
-use syn::parse::{Parse, ParseStream};
-use syn::{
-    parse2, parse_macro_input, Block, Expr, ExprBlock, ExprBreak, ExprIf, ExprMatch, Pat, Result,
-    Stmt, Token,
-};
-
-struct ForLoop {
-    var: Pat,
-    expr: Expr,
-    body: Block,
-    else_block: Block,
-}
-
-impl Parse for ForLoop {
-    fn parse(input: ParseStream) -> Result<Self> {
-        let var = Pat::parse_single(input)?;
-        input.parse::<Token![in]>()?;
-        let expr: Expr = input.parse()?;
-        let body: Block = input.parse()?;
-        input.parse::<Token![else]>()?;
-        let else_block: Block = input.parse()?;
-        Ok(ForLoop {
-            var,
-            expr,
-            body,
-            else_block,
-        })
-    }
-}
-
-fn modify_breaks(body: &mut Block) {
-    for stmt in &mut body.stmts {
-        match stmt {
-            Stmt::Expr(Expr::Break(ExprBreak { label, .. }), _) => {
-                // we need to replace a stement with another statement, but we have two statements instead,
-                // so we put them into a block to make it a single statement
-                let replacement = if let Some(label) = label {
-                    quote! {
-                        {
-                            _for_else_break_occurred = true;
-                            break #label;
-                        }
-                    }
-                } else {
-                    quote! {
-                        {
-                            _for_else_break_occurred = true;
-                            break;
-                        }
-                    }
-                };
-
-                *stmt = parse2(replacement).unwrap();
-            }
-            Stmt::Expr(Expr::Block(ExprBlock { block, .. }), _) => {
-                modify_breaks(block);
-            }
-            Stmt::Expr(
-                Expr::If(ExprIf {
-                    then_branch,
-                    else_branch,
-                    ..
-                }),
-                _,
-            ) => {
-                modify_breaks(then_branch);
-                if let Some((_, else_block)) = else_branch {
-                    if let Expr::Block(ExprBlock { block, .. }) = &mut **else_block {
-                        modify_breaks(block);
-                    }
-                }
-            }
-            Stmt::Expr(Expr::Match(ExprMatch { arms, .. }), _) => {
-                for arm in arms {
-                    match &mut *arm.body {
-                        Expr::Break(ExprBreak { label, .. }) => {
-                            let replacement = if let Some(label) = label {
-                                quote! {
-                                    {
-                                        _for_else_break_occurred = true;
-                                        break #label;
-                                    }
-                                }
-                            } else {
-                                quote! {
-                                    {
-                                        _for_else_break_occurred = true;
-                                        break;
-                                    }
-                                }
-                            };
-
-                            arm.body = Box::new(syn::parse2(replacement).unwrap());
-                        }
-                        Expr::Block(ExprBlock { block, .. }) => modify_breaks(block),
-                        _ => {}
-                    }
-                }
-            }
-            _ => {}
-        }
-    }
-}
-
-/// The `for_!` procedural macro with enhanced loop control.
-///
-/// This macro is an extension of the standard `for` loop in Rust. It allows users to
-/// have an additional `else` block that executes if the loop completed without encountering a `break` statement.
-///
-/// # Syntax
-///
-/// ```ignore
-/// for_! { variable in expression {
-///     // loop body
-/// } else {
-///     // else block
-/// }}
-/// ```
-///
-/// # Example
-///
-/// ```rust
-/// use for_else::for_;
-///
-/// # fn some_condition(i: u32) -> bool {
-/// #     true
-/// # }
-/// # fn main() {
-/// for_! { i in 0..10 {
-///     if some_condition(i) {
-///         // Some action
-///         break;
-///     }
-/// } else {
-///     // This block executes if the loop never breaks
-/// }}
-/// # }
-/// ```
-///
-/// In the example above, if `some_condition(i)` never evaluates to `true` for any `i` in the range `0..10`,
-/// then the `else` block will be executed after the loop completes.
-#[proc_macro]
-pub fn for_(input: TokenStream) -> TokenStream {
-    let mut input = parse_macro_input!(input as ForLoop);
-
-    modify_breaks(&mut input.body);
-
-    let var = input.var;
-    let expr = input.expr;
-    let body = input.body;
-    let else_block = input.else_block;
+#![no_std]
 
-    let expanded = quote! {
-        let mut _for_else_break_occurred = false;
-        for #var in #expr
-            #body
-        if !_for_else_break_occurred
-            #else_block
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
-    };
+mod backoff;
+mod budget;
+#[cfg(feature = "resume")]
+mod checkpoint;
+mod collector;
+pub mod compat;
+mod control_flow;
+mod fallible;
+mod interruptible;
+#[cfg(feature = "lending")]
+mod lending;
+mod looped;
+mod merge;
+#[cfg(feature = "metrics")]
+mod metrics;
+mod observer;
+#[cfg(feature = "std")]
+mod retry;
 
-    expanded.into()
-}
+pub use backoff::{Backoff, Exponential, Jittered, Linear};
+pub use budget::Budget;
+#[cfg(target_has_atomic = "64")]
+pub use budget::LoopBudget;
+#[cfg(feature = "std")]
+pub use budget::TimeLimit;
+#[cfg(feature = "resume")]
+pub use checkpoint::CheckpointStore;
+pub use collector::Collector;
+pub use control_flow::ControlFlowExt;
+pub use fallible::Fallible;
+pub use for_else_macros::*;
+pub use interruptible::Interruptible;
+#[cfg(feature = "lending")]
+pub use lending::LendingIterator;
+pub use looped::Looped;
+pub use merge::Merge;
+#[cfg(feature = "metrics")]
+pub use metrics::LoopMetrics;
+pub use observer::LoopObserver;
+#[cfg(feature = "std")]
+pub use retry::{poll_until, retry_with};
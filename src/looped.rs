@@ -0,0 +1,61 @@
+/// A guard that runs an `else` closure when dropped, unless [`broke`](Looped::broke) was called.
+///
+/// This is the runtime form of [`for_!`](crate::for_): create the guard before the loop, call
+/// `broke` where the loop breaks, and let the guard go out of scope after the loop. The closure
+/// then runs when the guard is dropped without a call to `broke`.
+///
+/// A guard cannot tell how the loop was left, so the closure also runs when a `return` or `?`
+/// leaves the loop and drops the guard; call `broke` before those as well when they should skip
+/// it. With the `std` feature, the closure is skipped when the guard is dropped while a panic
+/// unwinds.
+///
+/// # Example
+///
+/// ```rust
+/// use for_else::Looped;
+///
+/// let mut guard = Looped::new(|| println!("No even number found."));
+/// for n in [1, 3, 4, 5] {
+///     if n % 2 == 0 {
+///         println!("Found an even number: {}", n);
+///         guard.broke();
+///         break;
+///     }
+/// }
+/// drop(guard);
+/// ```
+#[must_use = "the `else` closure runs as soon as the guard is dropped"]
+pub struct Looped<F: FnOnce()> {
+    else_block: Option<F>,
+}
+
+impl<F: FnOnce()> Looped<F> {
+    /// Creates a guard that runs `else_block` when dropped.
+    pub fn new(else_block: F) -> Self {
+        Looped {
+            else_block: Some(else_block),
+        }
+    }
+
+    /// Records that the loop broke, so the `else` closure does not run.
+    pub fn broke(&mut self) {
+        self.else_block = None;
+    }
+
+    /// Whether [`broke`](Looped::broke) has been called.
+    pub fn has_broken(&self) -> bool {
+        self.else_block.is_none()
+    }
+}
+
+impl<F: FnOnce()> Drop for Looped<F> {
+    fn drop(&mut self) {
+        #[cfg(feature = "std")]
+        if std::thread::panicking() {
+            return;
+        }
+        if let Some(else_block) = self.else_block.take() {
+            else_block();
+        }
+    }
+}
@@ -0,0 +1,13 @@
+/// One step of [`merge_for_!`](crate::merge_for_) through two sorted iterators.
+///
+/// Each step compares the next items of both sides and takes the lesser one, or both when
+/// they are equal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Merge<T> {
+    /// The item of the left side is less than the next item of the right side.
+    Left(T),
+    /// The item of the right side is less than the next item of the left side.
+    Right(T),
+    /// Both sides have an equal item.
+    Both(T, T),
+}
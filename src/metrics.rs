@@ -0,0 +1,80 @@
+use alloc::format;
+use core::panic::Location;
+
+use metrics::{counter, SharedString};
+
+use crate::LoopObserver;
+
+/// A [`LoopObserver`] that counts the outcomes of a loop with the `metrics` crate, given to
+/// the `observe` clause of [`for_!`](crate::for_).
+///
+/// Every loop that is over increments the `for_else_loop_outcomes_total` counter, labeled with
+/// the name of the loop as `loop`, the `file:line:column` it was created at as `location`, and
+/// `broke`, `completed` or `empty` as `outcome`, so a dashboard can show how often the `else`
+/// block of a loop runs and tell apart loops sharing a name. The counters go to whatever
+/// recorder is installed, and nowhere if there is none.
+///
+/// # Example
+///
+/// ```rust
+/// use for_else::{for_, LoopMetrics};
+///
+/// # fn lookup(mirror: &str) -> bool { mirror.ends_with(".org") }
+/// for_! { mirror in ["eu.example.com", "us.example.org"] observe(LoopMetrics::new("mirrors")) {
+///     if lookup(mirror) {
+///         break;
+///     }
+/// } else {
+///     println!("No mirror is reachable.");
+/// }}
+/// ```
+pub struct LoopMetrics {
+    name: SharedString,
+    location: SharedString,
+}
+
+impl LoopMetrics {
+    /// The name of the counter incremented once a loop is over.
+    pub const COUNTER: &'static str = "for_else_loop_outcomes_total";
+
+    /// Counts the outcomes of a loop under `name`, at the location of the caller, which is the
+    /// loop itself when called in its `observe` clause.
+    #[track_caller]
+    pub fn new(name: impl Into<SharedString>) -> Self {
+        let location = Location::caller();
+        LoopMetrics {
+            name: name.into(),
+            location: format!(
+                "{}:{}:{}",
+                location.file(),
+                location.line(),
+                location.column()
+            )
+            .into(),
+        }
+    }
+
+    fn count(&self, outcome: &'static str) {
+        counter!(
+            Self::COUNTER,
+            "loop" => self.name.clone(),
+            "location" => self.location.clone(),
+            "outcome" => outcome,
+        )
+        .increment(1);
+    }
+}
+
+impl LoopObserver for LoopMetrics {
+    fn on_break(&mut self, _iterations: usize) {
+        self.count("broke");
+    }
+
+    fn on_complete(&mut self, _iterations: usize) {
+        self.count("completed");
+    }
+
+    fn on_empty(&mut self) {
+        self.count("empty");
+    }
+}
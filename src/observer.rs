@@ -0,0 +1,79 @@
+/// Callbacks about the progress of a loop, given to the `observe` clause of
+/// [`for_!`](crate::for_) to instrument it without changing its body.
+///
+/// Every callback does nothing by default, so an observer only implements the ones it needs.
+/// Exactly one of [`on_break`](LoopObserver::on_break), [`on_complete`](LoopObserver::on_complete)
+/// and [`on_empty`](LoopObserver::on_empty) is called once the loop is over, unless the loop is
+/// left by a `return`, a `?`, a panic or a `break` to an enclosing loop.
+///
+/// # Example
+///
+/// ```rust
+/// use for_else::{for_, LoopObserver};
+///
+/// #[derive(Default)]
+/// struct Stats {
+///     iterations: usize,
+///     broke: bool,
+/// }
+///
+/// impl LoopObserver for Stats {
+///     fn on_iteration(&mut self, _index: usize) {
+///         self.iterations += 1;
+///     }
+///
+///     fn on_break(&mut self, _iterations: usize) {
+///         self.broke = true;
+///     }
+/// }
+///
+/// let mut stats = Stats::default();
+/// for_! { n in [1, 3, 4, 5] observe(&mut stats) {
+///     if n % 2 == 0 {
+///         break;
+///     }
+/// } else {
+///     println!("No even number found.");
+/// }}
+///
+/// assert_eq!(stats.iterations, 3);
+/// assert!(stats.broke);
+/// ```
+pub trait LoopObserver {
+    /// Called before the body runs for the iteration with the given index, starting at 0.
+    fn on_iteration(&mut self, index: usize) {
+        let _ = index;
+    }
+
+    /// Called when the loop was left by a `break` after the given number of iterations.
+    fn on_break(&mut self, iterations: usize) {
+        let _ = iterations;
+    }
+
+    /// Called when the loop finished without a `break` after at least one iteration, whether
+    /// it ran out of items or was stopped by an `until` condition or an interruption.
+    fn on_complete(&mut self, iterations: usize) {
+        let _ = iterations;
+    }
+
+    /// Called when the loop finished without running its body at all.
+    fn on_empty(&mut self) {}
+}
+
+impl<O: LoopObserver + ?Sized> LoopObserver for &mut O {
+    fn on_iteration(&mut self, index: usize) {
+        O::on_iteration(self, index)
+    }
+
+    fn on_break(&mut self, iterations: usize) {
+        O::on_break(self, iterations)
+    }
+
+    fn on_complete(&mut self, iterations: usize) {
+        O::on_complete(self, iterations)
+    }
+
+    fn on_empty(&mut self) {
+        O::on_empty(self)
+    }
+}
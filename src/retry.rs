@@ -0,0 +1,82 @@
+use std::time::Instant;
+
+use crate::{Backoff, Budget};
+
+/// Calls `f` until it succeeds, waiting between the calls as `backoff` says, and returns the
+/// last error once `attempts` runs out.
+///
+/// Every call takes one attempt from the budget, except that `f` is always called at least
+/// once, since there would be no error to return otherwise. The budget is any [`Budget`]: an
+/// integer for a number of attempts, or a [`LoopBudget`](crate::LoopBudget) to bound the
+/// attempts by time or share them with loops.
+///
+/// # Example
+///
+/// ```rust
+/// use for_else::{retry_with, Exponential};
+/// use std::time::Duration;
+///
+/// let mut calls = 0;
+/// let connected = retry_with(Exponential::new(Duration::from_millis(1), 2), 5, || {
+///     calls += 1;
+///     if calls < 3 {
+///         Err("connection refused")
+///     } else {
+///         Ok(calls)
+///     }
+/// });
+///
+/// assert_eq!(connected, Ok(3));
+/// ```
+pub fn retry_with<T, E>(
+    mut backoff: impl Backoff,
+    mut attempts: impl Budget,
+    mut f: impl FnMut() -> Result<T, E>,
+) -> Result<T, E> {
+    attempts.draw();
+    loop {
+        match f() {
+            Ok(value) => return Ok(value),
+            Err(err) if !attempts.draw() => return Err(err),
+            Err(_) => std::thread::sleep(backoff.next_delay()),
+        }
+    }
+}
+
+/// Calls `f` until it returns `Some`, waiting between the calls as `interval` says, and
+/// returns `None` once `deadline` has passed.
+///
+/// `f` is always called at least once, and once more when the deadline comes, since the waits
+/// end at the deadline instead of going past it.
+///
+/// # Example
+///
+/// ```rust
+/// use for_else::poll_until;
+/// use std::sync::mpsc;
+/// use std::time::{Duration, Instant};
+///
+/// let (sender, receiver) = mpsc::channel();
+/// std::thread::spawn(move || sender.send("ready").unwrap());
+///
+/// let deadline = Instant::now() + Duration::from_secs(1);
+/// let message = poll_until(deadline, Duration::from_millis(5), || receiver.try_recv().ok());
+///
+/// assert_eq!(message, Some("ready"));
+/// ```
+pub fn poll_until<T>(
+    deadline: Instant,
+    mut interval: impl Backoff,
+    mut f: impl FnMut() -> Option<T>,
+) -> Option<T> {
+    loop {
+        if let Some(value) = f() {
+            return Some(value);
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return None;
+        }
+        std::thread::sleep(interval.next_delay().min(deadline - now));
+    }
+}
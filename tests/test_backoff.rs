@@ -0,0 +1,66 @@
+use for_else::{eventually_, for_, Backoff, Exponential, Linear};
+use std::cell::Cell;
+use std::time::Duration;
+
+fn ms(n: u64) -> Duration {
+    Duration::from_millis(n)
+}
+
+#[test]
+fn test_fixed() {
+    let mut backoff = ms(3);
+    assert_eq!(backoff.next_delay(), ms(3));
+    assert_eq!(backoff.next_delay(), ms(3));
+}
+
+#[test]
+fn test_linear() {
+    let mut backoff = Linear::new(ms(1), ms(2));
+    let delays: Vec<_> = (0..3).map(|_| backoff.next_delay()).collect();
+    assert_eq!(delays, [ms(1), ms(3), ms(5)]);
+
+    backoff.reset();
+    assert_eq!(backoff.next_delay(), ms(1));
+}
+
+#[test]
+fn test_exponential_capped() {
+    let mut backoff = Exponential::new(ms(1), 3).max(ms(5));
+    let delays: Vec<_> = (0..4).map(|_| backoff.next_delay()).collect();
+    assert_eq!(delays, [ms(1), ms(3), ms(5), ms(5)]);
+
+    backoff.reset();
+    assert_eq!(backoff.next_delay(), ms(1));
+}
+
+#[test]
+fn test_jitter() {
+    let mut backoff = Linear::new(ms(10), ms(10)).jitter(|delay| delay / 2);
+    assert_eq!(backoff.next_delay(), ms(5));
+    assert_eq!(backoff.next_delay(), ms(10));
+}
+
+#[test]
+fn test_cooldown_draws_delays() {
+    let mut backoff = Exponential::new(ms(1), 2);
+    let mut runs = 0;
+    for_! { _n in 0..4 cooldown(&mut backoff) {
+        runs += 1;
+    } else {
+    }}
+
+    assert_eq!(runs, 4);
+    // three sleeps, between the four iterations
+    assert_eq!(backoff.next_delay(), ms(8));
+}
+
+#[test]
+fn test_eventually_interval() {
+    let attempts = Cell::new(0);
+    eventually_! { timeout Duration::from_secs(1), interval Linear::new(ms(1), ms(1)) {
+        attempts.set(attempts.get() + 1);
+        assert!(attempts.get() >= 3);
+    }}
+
+    assert_eq!(attempts.get(), 3);
+}
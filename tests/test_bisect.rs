@@ -0,0 +1,65 @@
+use for_else::bisect_loop_;
+
+#[test]
+fn test_bisect_finds_every_value() {
+    let values = [1, 4, 9, 16, 25, 36, 49];
+    for target in values {
+        let mut found = None;
+        bisect_loop_! { i in 0..values.len() {
+            if values[i] == target {
+                found = Some(i);
+                break;
+            }
+            values[i].cmp(&target)
+        } else {
+            panic!("{} is in the slice", target);
+        }}
+
+        assert_eq!(values.get(found.unwrap()), Some(&target));
+    }
+}
+
+#[test]
+fn test_bisect_not_found() {
+    let values = [1, 4, 9, 16];
+    for target in [0, 5, 17] {
+        let mut probes = 0;
+        let mut else_ran = false;
+        bisect_loop_! { i in 0..values.len() {
+            probes += 1;
+            values[i].cmp(&target)
+        } else {
+            else_ran = true;
+        }}
+
+        assert!(else_ran);
+        assert!(probes <= 3);
+    }
+}
+
+#[test]
+fn test_bisect_empty_range() {
+    let mut probes = 0;
+    let mut else_ran = false;
+    bisect_loop_! { _i in 5..5 {
+        probes += 1;
+        std::cmp::Ordering::Less
+    } else {
+        else_ran = true;
+    }}
+
+    assert_eq!(probes, 0);
+    assert!(else_ran);
+}
+
+#[test]
+fn test_bisect_equal_ends_search() {
+    let mut else_ran = false;
+    bisect_loop_! { n in 0u64..1 << 32 {
+        (n * n).cmp(&1_000_000)
+    } else {
+        else_ran = true;
+    }}
+
+    assert!(!else_ran);
+}
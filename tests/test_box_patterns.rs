@@ -0,0 +1,16 @@
+#![cfg(for_else_nightly)]
+#![feature(box_patterns)]
+
+use for_else::for_;
+
+#[test]
+fn test_box_pattern() {
+    let mut sum = 0;
+    for_! { box (a, b) in [Box::new((1, 2)), Box::new((3, 4))] {
+        sum += a * b;
+    } else {
+        sum += 100;
+    }}
+
+    assert_eq!(sum, 114);
+}
@@ -0,0 +1,100 @@
+use for_else::for_;
+
+#[test]
+fn test_break_in_let_initializer() {
+    let mut seen = Vec::new();
+    let mut else_ran = false;
+    for_! { n in 1..10 {
+        let doubled = if n == 3 { break } else { n * 2 };
+        let halved = match doubled {
+            20 => break,
+            doubled => doubled / 2,
+        };
+        seen.push(halved);
+    } else {
+        else_ran = true;
+    }}
+
+    assert_eq!(seen, [1, 2]);
+    assert!(!else_ran);
+}
+
+#[test]
+fn test_break_in_call_argument_and_binary_expression() {
+    let mut seen = Vec::new();
+    let mut else_ran = false;
+    for_! { n in 1..10 {
+        seen.push(if n > 2 { break } else { n });
+    } else {
+        else_ran = true;
+    }}
+
+    assert_eq!(seen, [1, 2]);
+    assert!(!else_ran);
+
+    let mut total = 0;
+    let mut else_ran = false;
+    for_! { n in 1..10 {
+        total += if n == 4 { break } else { n };
+    } else {
+        else_ran = true;
+    }}
+
+    assert_eq!(total, 6);
+    assert!(!else_ran);
+}
+
+#[test]
+fn test_break_in_match_guard_and_let_else() {
+    let mut seen = Vec::new();
+    let mut else_ran = false;
+    for_! { n in [Some(1), Some(2), None, Some(3)] {
+        let Some(n) = n else {
+            break;
+        };
+        match n {
+            n if n > 5 && { seen.push(0); break } => seen.push(n),
+            n => seen.push(n),
+        }
+    } else {
+        else_ran = true;
+    }}
+
+    assert_eq!(seen, [1, 2]);
+    assert!(!else_ran);
+
+    let mut else_ran = false;
+    for_! { n in [2, 4, 6] {
+        match n {
+            n if n % 2 == 1 || { if n > 4 { break } false } => {}
+            _ => {}
+        }
+    } else {
+        else_ran = true;
+    }}
+
+    assert!(!else_ran);
+}
+
+#[test]
+fn test_break_out_of_labeled_block_is_not_the_loop() {
+    let mut seen = Vec::new();
+    let mut else_ran = false;
+    for_! { n in 1..4 {
+        'check: {
+            if n == 2 {
+                break 'check;
+            }
+            seen.push(n);
+        }
+        if n > 5 {
+            break;
+        }
+    } else {
+        else_ran = true;
+    }}
+
+    // the `break` only leaves the block, so the `else` block runs
+    assert_eq!(seen, [1, 3]);
+    assert!(else_ran);
+}
@@ -0,0 +1,146 @@
+use for_else::{for_, loop_else, while_let_};
+
+#[test]
+fn test_evaluates_to_break_value_or_else() {
+    let numbers = [3, 8, 5];
+    let even = for_! { n in numbers {
+        if n % 2 == 0 {
+            break Some(n);
+        }
+    } else {
+        None
+    }};
+
+    assert_eq!(even, Some(8));
+
+    let negative = for_! { n in numbers {
+        if n < 0 {
+            break n;
+        }
+    } else {
+        -1
+    }};
+
+    assert_eq!(negative, -1);
+}
+
+#[test]
+fn test_labels_and_defer() {
+    let grid = [[1, 2], [3, 4]];
+    let mut visited = 0;
+    let cell = for_! { 'rows: (r, row) in grid.iter().enumerate() {
+        for (c, &value) in row.iter().enumerate() {
+            if value == 3 {
+                break 'rows (r, c);
+            }
+        }
+    } defer {
+        visited += 1;
+    } else {
+        (usize::MAX, usize::MAX)
+    }};
+
+    assert_eq!(cell, (1, 0));
+    // the deferred code does not run after a `break`
+    assert_eq!(visited, 1);
+
+    // a `break` naming an enclosing loop keeps its value for that loop
+    let outer = 'outer: loop {
+        for_! { n in 0..10 {
+            if n == 4 {
+                break 'outer n * 10;
+            }
+        } else {
+            break 'outer 0;
+        }}
+    };
+
+    assert_eq!(outer, 40);
+}
+
+#[test]
+fn test_other_loop_forms() {
+    let mut n = 0;
+    let found = loop_else! { while n < 100 {
+        n += 7;
+        if n % 5 == 0 {
+            break n;
+        }
+    } else {
+        0
+    }};
+
+    assert_eq!(found, 35);
+
+    let mut stack = vec!["a", "bb", "ccc"];
+    let len = while_let_! { Some(item) = stack.pop() until stack.is_empty() {
+        if item.len() == 4 {
+            break item.len();
+        }
+    } else {
+        0
+    } stopped {
+        usize::MAX
+    }};
+
+    assert_eq!(len, usize::MAX);
+}
+
+#[test]
+fn test_unit_loops_are_unchanged() {
+    let mut seen = Vec::new();
+    for_! { n in 0..3 {
+        if n == 5 {
+            break;
+        }
+        seen.push(n);
+    } else {
+        seen.push(99);
+    }}
+
+    assert_eq!(seen, [0, 1, 2, 99]);
+}
+
+#[test]
+fn test_break_to_outer_label_from_nested_valued_loop() {
+    let grid = [[1, 2], [3, 4]];
+    let found = for_! { 'outer: row in grid {
+        let inner = for_! { cell in row {
+            if cell == 4 {
+                break 'outer 100;
+            }
+            if cell == 9 {
+                break 9;
+            }
+        } else {
+            -1
+        }};
+        if inner == 9 {
+            break inner;
+        }
+    } else {
+        -1
+    }};
+
+    // the value goes to the outer loop, not to the inner loop it passes through
+    assert_eq!(found, 100);
+
+    let mut emitted = Vec::new();
+    let sums = for_! { 'rows: collect row in grid {
+        let total = for_! { cell in row {
+            if cell == 3 {
+                break 'rows;
+            }
+            if cell == 9 {
+                break 0;
+            }
+        } else |count| {
+            count
+        }};
+        emitted.push(total);
+        emit total;
+    }};
+
+    assert_eq!(sums, [2]);
+    assert_eq!(emitted, [2]);
+}
@@ -0,0 +1,153 @@
+use for_else::{for_, loop_else, machine_loop_, Budget, LoopBudget};
+
+#[test]
+fn test_integer_budget() {
+    let mut budget = 2u8;
+    assert!(budget.draw());
+    assert!(budget.draw());
+    assert!(!budget.draw());
+
+    let mut negative = -1;
+    assert!(!negative.draw());
+}
+
+#[test]
+fn test_shared_across_loops() {
+    let budget = LoopBudget::new().iterations(5);
+    let mut runs = 0;
+    loop_else! { loop max &budget {
+        runs += 1;
+        if runs == 3 {
+            break;
+        }
+    } else {
+        panic!("budget ran out too early");
+    }}
+
+    let mut flag = false;
+    loop_else! { loop max &budget {
+        runs += 1;
+        // the shared budget runs out first
+        if runs > 5 {
+            break;
+        }
+    } else {
+        flag = true;
+    }}
+
+    assert!(flag);
+    assert_eq!(runs, 5);
+    assert!(budget.is_exhausted());
+    assert_eq!(budget.remaining_iterations(), Some(0));
+}
+
+#[test]
+fn test_machine_budget() {
+    let budget = LoopBudget::new().iterations(3);
+    let state = machine_loop_! { n = 0, max &budget {
+        if n == 10 {
+            break;
+        }
+        n + 1
+    } on () => {
+        n
+    } else {
+        n
+    }};
+
+    assert_eq!(state, 3);
+    assert!(!budget.draw());
+}
+
+#[test]
+fn test_unlimited() {
+    let budget = LoopBudget::new();
+    assert!((0..1000).all(|_| budget.draw()));
+    assert_eq!(budget.remaining_iterations(), None);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_wall_time() {
+    use std::time::Duration;
+
+    let budget = LoopBudget::new().within(Duration::from_millis(20));
+    let mut runs = 0;
+    let mut flag = false;
+    loop_else! { loop max &budget {
+        runs += 1;
+        // the time runs out first
+        if runs == 100 {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(5));
+    } else {
+        flag = true;
+    }}
+
+    assert!(flag);
+    assert!(runs >= 1);
+    assert!(budget.is_exhausted());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_within_takes_a_budget() {
+    let budget = LoopBudget::new().iterations(5);
+    let mut seen = Vec::new();
+    let mut outcome = "";
+    for_! { n in 0..3 within(&budget) {
+        seen.push(n);
+    } else {
+        outcome = "else";
+    } timed_out {
+        outcome = "timed out";
+    }}
+
+    assert_eq!(outcome, "else");
+    assert_eq!(budget.remaining_iterations(), Some(2));
+
+    for_! { n in 10..20 within(&budget) {
+        seen.push(n);
+    } else {
+        outcome = "else";
+    } timed_out {
+        outcome = "timed out";
+    }}
+
+    assert_eq!(outcome, "timed out");
+    assert_eq!(seen, [0, 1, 2, 10, 11]);
+    assert!(budget.is_exhausted());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_shared_across_threads() {
+    let budget = LoopBudget::new().iterations(1000);
+    let runs = std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..4)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut runs = 0;
+                    loop_else! { loop max &budget {
+                        runs += 1;
+                        if runs > 1000 {
+                            break;
+                        }
+                    } else {
+                        runs += 0;
+                    }}
+                    runs
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .map(|worker| worker.join().unwrap())
+            .sum::<usize>()
+    });
+
+    // every iteration of every thread drew once, and no more than the budget allowed
+    assert_eq!(runs, 1000);
+    assert!(budget.is_exhausted());
+}
@@ -0,0 +1,105 @@
+use for_else::cfor_;
+
+#[test]
+fn test_else_runs_when_condition_fails() {
+    let values = [3, 1, 4];
+    let mut seen = Vec::new();
+    let mut end = None;
+    cfor_! { let mut i = 0; i < values.len(); i += 1 {
+        if values[i] == 9 {
+            break;
+        }
+        seen.push(values[i]);
+    } else {
+        end = Some(i);
+    }}
+
+    assert_eq!(seen, [3, 1, 4]);
+    assert_eq!(end, Some(3));
+}
+
+#[test]
+fn test_break_skips_step_and_else() {
+    let mut found = None;
+    let mut else_ran = false;
+    cfor_! { let mut i = 10; i > 0; i -= 2 {
+        if i % 3 == 0 {
+            found = Some(i);
+            break;
+        }
+    } else {
+        else_ran = true;
+    }}
+
+    assert_eq!(found, Some(6));
+    assert!(!else_ran);
+}
+
+#[test]
+fn test_continue_runs_the_step() {
+    let mut odd = Vec::new();
+    let mut steps = 0;
+    cfor_! { let mut i = 0; i < 6; { i += 1; steps += 1; } {
+        if i % 2 == 0 {
+            continue;
+        }
+        if i == 9 {
+            break;
+        }
+        odd.push(i);
+    } else {
+        odd.push(0);
+    }}
+
+    assert_eq!(odd, [1, 3, 5, 0]);
+    assert_eq!(steps, 6);
+}
+
+#[test]
+fn test_label_defer_and_collect() {
+    let grid = [[1, 2], [3, 4], [5, 6]];
+    let mut order = Vec::new();
+    let mut found = None;
+    cfor_! { 'rows: let mut row = 0; row < grid.len(); row += 1 {
+        for cell in grid[row] {
+            if cell == 4 {
+                found = Some(row);
+                break 'rows;
+            }
+        }
+    } defer {
+        order.push(row);
+    } else {
+        found = None;
+    }}
+
+    assert_eq!(found, Some(1));
+    // the `defer` block runs before the step, and neither runs after a `break`
+    assert_eq!(order, [0]);
+
+    let squares = cfor_! { collect let mut n = 1; n <= 4; n += 1 {
+        emit n * n;
+    }};
+
+    assert_eq!(squares, [1, 4, 9, 16]);
+}
+
+#[test]
+fn test_continue_in_else_if_runs_the_step() {
+    let mut seen = Vec::new();
+    let mut else_ran = false;
+    cfor_! { let mut i = 0; i < 6; i += 1 {
+        if i == 9 {
+            break;
+        } else if i % 3 == 0 {
+            continue;
+        }
+        let double = if i == 4 { continue } else { i * 2 };
+        seen.push(double);
+    } else {
+        else_ran = true;
+    }}
+
+    assert_eq!(seen, [2, 4, 10]);
+    assert!(else_ran);
+}
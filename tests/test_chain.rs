@@ -0,0 +1,54 @@
+use for_else::chain_for_;
+
+#[test]
+fn test_chain_visits_sources_in_order() {
+    let mut seen = Vec::new();
+    chain_for_! { item in Number([1, 2]), Text(vec!["a"]), Flag(Some(true)) {
+        seen.push(match item {
+            Number(n) => n.to_string(),
+            Text(s) => s.to_string(),
+            Flag(b) => b.to_string(),
+        });
+    } else {
+        seen.push(String::from("else"));
+    }}
+
+    assert_eq!(seen, ["1", "2", "a", "true", "else"]);
+}
+
+#[test]
+fn test_chain_break_skips_later_sources() {
+    let mut visited = 0;
+    chain_for_! { item in Primary(0..3), Fallback(["x", "y"]) {
+        visited += 1;
+        if let Primary(2) = item {
+            break;
+        }
+    } else {
+        panic!("the loop was broken");
+    }}
+
+    assert_eq!(visited, 3);
+}
+
+#[test]
+fn test_chain_single_source() {
+    let mut else_ran = false;
+    chain_for_! { item in Only(Vec::<u8>::new()) {
+        let Only(_) = item;
+    } else {
+        else_ran = true;
+    }}
+
+    assert!(else_ran);
+}
+
+#[test]
+fn test_chain_items_are_debug() {
+    let mut rendered = Vec::new();
+    chain_for_! { item in Left([1]), Right(['r']) {
+        rendered.push(format!("{:?}", item));
+    } else {}}
+
+    assert_eq!(rendered, ["Left(1)", "Right('r')"]);
+}
@@ -0,0 +1,163 @@
+#![cfg(feature = "resume")]
+
+use for_else::{for_, loop_else, CheckpointStore};
+
+#[test]
+fn test_fresh_run_clears() {
+    let mut store: Option<usize> = None;
+    let mut seen = Vec::new();
+    let mut flag = false;
+    for_! { n in 0..5 checkpoint(&mut store, every 2) {
+        seen.push(n);
+    } else {
+        flag = true;
+    }}
+
+    assert!(flag);
+    assert_eq!(seen, [0, 1, 2, 3, 4]);
+    assert_eq!(store, None);
+}
+
+#[test]
+fn test_resume_by_count() {
+    let mut store = Some(3);
+    let mut seen = Vec::new();
+    for_! { n in 10..15 checkpoint(&mut store, every 2) {
+        seen.push(n);
+    } else {
+    }}
+
+    assert_eq!(seen, [13, 14]);
+    assert_eq!(store, None);
+}
+
+#[test]
+fn test_panic_keeps_checkpoint() {
+    let mut store = None;
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        for_! { n in 0..10 checkpoint(&mut store, every 3) {
+            if n == 7 {
+                panic!("killed");
+            }
+        } else {
+        }}
+    }));
+
+    assert!(result.is_err());
+    assert_eq!(store, Some(6));
+}
+
+struct Recorder {
+    saved: Vec<usize>,
+    cleared: bool,
+}
+
+impl CheckpointStore<usize> for Recorder {
+    fn load(&mut self) -> Option<usize> {
+        None
+    }
+
+    fn save(&mut self, checkpoint: &usize) {
+        self.saved.push(*checkpoint);
+    }
+
+    fn clear(&mut self) {
+        self.cleared = true;
+    }
+}
+
+#[test]
+fn test_save_positions() {
+    let mut recorder = Recorder {
+        saved: Vec::new(),
+        cleared: false,
+    };
+    for_! { _n in 0..7 checkpoint(&mut recorder, every 3) {
+    } else {
+    }}
+
+    assert_eq!(recorder.saved, [3, 6]);
+    assert!(recorder.cleared);
+}
+
+#[test]
+fn test_refutable_items_count() {
+    let mut store = Some(2);
+    let mut seen = Vec::new();
+    for_! { Some(n) in [Some(1), None, Some(3), None, Some(5)] checkpoint(&mut store, every 1) {
+        seen.push(n);
+    } else {
+    }}
+
+    assert_eq!(seen, [3, 5]);
+}
+
+#[test]
+fn test_resume_by_key() {
+    let mut store = Some(20);
+    let mut seen = Vec::new();
+    let mut flag = false;
+    for_! { (id, name) in [(10, "a"), (30, "c"), (40, "d")] checkpoint(&mut store, every 1, key id) {
+        seen.push(name);
+    } else {
+        flag = true;
+    }}
+
+    assert!(flag);
+    assert_eq!(seen, ["c", "d"]);
+    assert_eq!(store, None);
+}
+
+#[test]
+fn test_until_saves_progress() {
+    let mut store = None;
+    let mut seen = Vec::new();
+    for_! { n in 0..10 until seen.len() == 4 checkpoint(&mut store, every 100) {
+        seen.push(n);
+    } else {
+        panic!("not stopped");
+    } stopped {
+    }}
+    assert_eq!(store, Some(4));
+
+    let mut rest = Vec::new();
+    for_! { n in 0..10 checkpoint(&mut store, every 100) {
+        rest.push(n);
+    } else {
+    }}
+    assert_eq!(rest, [4, 5, 6, 7, 8, 9]);
+}
+
+#[test]
+fn test_until_saves_last_key() {
+    let mut store = None;
+    let mut count = 0;
+    for_! { id in ["a", "b", "c"] until count == 2 checkpoint(&mut store, every 100, key id) {
+        count += 1;
+    } else {
+    }}
+
+    assert_eq!(store, Some("b"));
+}
+
+#[test]
+fn test_loop_else() {
+    let mut store = Some(1);
+    let mut seen = Vec::new();
+    loop_else! { for n in [1, 2, 3] checkpoint(&mut store, every 1) {
+        seen.push(n);
+    } else {
+    }}
+
+    assert_eq!(seen, [2, 3]);
+}
+
+#[test]
+fn test_collect() {
+    let mut store = Some(1);
+    let doubled = for_! { collect n in [1, 2, 3] checkpoint(&mut store, every 1) {
+        emit n * 2;
+    }};
+
+    assert_eq!(doubled, [4, 6]);
+}
@@ -0,0 +1,107 @@
+use for_else::{for_, machine_loop_};
+
+#[test]
+fn test_comments_before_else() {
+    let mut flag = false;
+    for_! { x in 0..3 {
+        if x == 5 {
+            break;
+        }
+    }
+    // runs when nothing matched
+    /// doc comments are accepted as well
+    else {
+        flag = true;
+    }}
+
+    assert!(flag);
+}
+
+#[test]
+fn test_lint_attribute_on_else() {
+    let mut flag = false;
+    for_! { x in 0..3 {
+        if x == 5 {
+            break;
+        }
+    } #[allow(unused_variables)] else {
+        let unused = 1;
+        flag = true;
+    }}
+
+    assert!(flag);
+}
+
+#[test]
+fn test_cfg_on_else() {
+    let mut runs = 0;
+    for_! { x in 0..1 {
+        if x == 5 {
+            break;
+        }
+    } #[cfg(any())] else {
+        runs += 10;
+    }}
+    for_! { x in 0..1 {
+        if x == 5 {
+            break;
+        }
+    } #[cfg(all())] else {
+        runs += 1;
+    }}
+
+    assert_eq!(runs, 1);
+}
+
+#[test]
+fn test_attributes_on_later_clauses() {
+    let mut outcome = "";
+    let mut taken = 0;
+    for_! { _x in 0..5 until taken == 2 {
+        taken += 1;
+    } else {
+        outcome = "else";
+    }
+    // only reached once the `until` condition holds
+    #[allow(unused_assignments)]
+    stopped {
+        outcome = "stopped";
+    }}
+    assert_eq!(outcome, "stopped");
+
+    let mut flag = false;
+    for_! { _x in 0..2 {
+        continue;
+    } else {
+    } #[cfg(all())] skipped {
+        flag = true;
+    }}
+    assert!(flag);
+}
+
+#[test]
+fn test_attributes_in_collect_mode() {
+    let values = for_! { collect x in 0..3 {
+        emit x;
+    } #[allow(unreachable_code)] else {
+        vec![7]
+    }};
+
+    assert_eq!(values, [7]);
+}
+
+#[test]
+fn test_attributes_before_else_of_other_macros() {
+    let result = machine_loop_! { n = 0, max 3 {
+        n + 1
+    } on () => {
+        0
+    }
+    // the machine never breaks
+    #[allow(unused_variables)]
+    else {
+        n
+    }};
+
+    assert_eq!(result, 3);
+}
@@ -0,0 +1,65 @@
+use for_else::for_;
+
+#[test]
+fn test_collect_into_vec() {
+    let values = for_! { collect::<Vec<u8>> x in 0..3 {
+        emit x;
+    }};
+
+    assert_eq!(values, [0, 1, 2]);
+}
+
+#[cfg(feature = "heapless")]
+#[test]
+fn test_collect_into_heapless() {
+    let mut rejected = Vec::new();
+    let values = for_! { collect::<heapless::Vec<u8, 2>> x in 0..4 {
+        emit x;
+    } overflow x {
+        rejected.push(x);
+    }};
+
+    assert_eq!(values, [0, 1]);
+    assert_eq!(rejected, [2, 3]);
+}
+
+#[cfg(feature = "heapless")]
+#[test]
+fn test_overflow_can_break() {
+    let mut iterations = 0;
+    let values = for_! { collect::<heapless::Vec<u8, 1>> x in 0..10 {
+        iterations += 1;
+        emit x;
+    } else {
+        heapless::Vec::new()
+    } overflow _ {
+        break;
+    }};
+
+    assert_eq!(values, [0]);
+    assert_eq!(iterations, 2);
+}
+
+#[cfg(feature = "heapless")]
+#[test]
+#[should_panic(expected = "collection is full")]
+fn test_overflow_without_clause_panics() {
+    let _values = for_! { collect::<heapless::Vec<u8, 1>> x in 0..2 {
+        emit x;
+    }};
+}
+
+#[cfg(feature = "smallvec")]
+#[test]
+fn test_collect_into_smallvec_without_spilling() {
+    let mut overflowed = 0;
+    let values = for_! { collect::<smallvec::SmallVec<[u8; 2]>> x in 0..3 {
+        emit x;
+    } overflow _ {
+        overflowed += 1;
+    }};
+
+    assert!(!values.spilled());
+    assert_eq!(values.as_slice(), [0, 1]);
+    assert_eq!(overflowed, 1);
+}
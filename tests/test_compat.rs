@@ -0,0 +1,29 @@
+use for_else::compat::forelse;
+
+#[test]
+fn test_forelse_alias() {
+    let mut else_ran = false;
+    forelse! { x in 0..3 {
+        if x == 5 {
+            break;
+        }
+    } else {
+        else_ran = true;
+    }}
+
+    assert!(else_ran);
+}
+
+#[test]
+fn test_forelse_alias_break() {
+    let mut else_ran = false;
+    forelse! { x in 0..3 {
+        if x == 1 {
+            break;
+        }
+    } else {
+        else_ran = true;
+    }}
+
+    assert!(!else_ran);
+}
@@ -0,0 +1,172 @@
+use for_else::for_;
+
+#[test]
+fn test_continue_in_else_if_branch() {
+    let mut seen = Vec::new();
+    let mut deferred = 0;
+    for_! { n in 0..6 {
+        if n == 5 {
+            break;
+        } else if n % 2 == 0 {
+            continue;
+        }
+        seen.push(n);
+    } defer {
+        deferred += 1;
+    } else {
+        seen.push(0);
+    }}
+
+    assert_eq!(seen, [1, 3]);
+    // the `continue`s still run the `defer` block, the `break` does not
+    assert_eq!(deferred, 5);
+}
+
+#[test]
+fn test_continue_in_let_initializer() {
+    let mut seen = Vec::new();
+    let mut deferred = 0;
+    for_! { n in 0..5 {
+        let half = if n % 2 == 0 { n / 2 } else { continue };
+        if half == 9 {
+            break;
+        }
+        seen.push(half);
+    } defer {
+        deferred += 1;
+    } else {
+        seen.push(10);
+    }}
+
+    assert_eq!(seen, [0, 1, 2, 10]);
+    assert_eq!(deferred, 5);
+}
+
+#[test]
+fn test_continue_in_call_argument_and_match_guard() {
+    let mut seen = Vec::new();
+    let mut deferred = 0;
+    for_! { n in 0..6 {
+        match n {
+            n if n == 1 || { if n == 3 { continue } false } => seen.push(n * 10),
+            9 => break,
+            _ => seen.push(n.max(if n == 4 { continue } else { 0 })),
+        }
+    } defer {
+        deferred += 1;
+    } else {
+        seen.push(100);
+    }}
+
+    assert_eq!(seen, [0, 10, 2, 5, 100]);
+    assert_eq!(deferred, 6);
+}
+
+#[test]
+fn test_continue_of_nested_loop_and_closure() {
+    let mut seen = Vec::new();
+    let mut deferred = 0;
+    for_! { 'outer: n in 0..3 {
+        for m in 0..3 {
+            if m == 1 {
+                continue;
+            }
+            if n == 1 {
+                continue 'outer;
+            }
+            seen.push((n, m));
+        }
+        let skip = |x: i32| x == 9;
+        if skip(n) {
+            break;
+        }
+    } defer {
+        deferred += 1;
+    } else {
+        seen.push((9, 9));
+    }}
+
+    assert_eq!(seen, [(0, 0), (0, 2), (2, 0), (2, 2), (9, 9)]);
+    assert_eq!(deferred, 3);
+}
+
+#[test]
+fn test_labeled_continue_in_nested_for() {
+    let mut seen = Vec::new();
+    let mut deferred = 0;
+    let mut inner_deferred = 0;
+    for_! { 'outer: n in 0..3 {
+        for_! { m in 0..3 {
+            if m == 1 {
+                continue 'outer;
+            }
+            seen.push((n, m));
+        } defer {
+            inner_deferred += 1;
+        } else {
+            seen.push((n, 9));
+        }}
+    } defer {
+        deferred += 1;
+    } else {
+        seen.push((9, 9));
+    }}
+
+    assert_eq!(seen, [(0, 0), (1, 0), (2, 0), (9, 9)]);
+    assert_eq!(deferred, 3);
+    // the `continue 'outer` leaves the nested loop, so it skips the nested `defer` block
+    assert_eq!(inner_deferred, 3);
+}
+
+#[test]
+fn test_unlabeled_continue_in_nested_for() {
+    let mut seen = Vec::new();
+    let mut deferred = 0;
+    for_! { n in 0..4 {
+        for_! { m in 0..3 {
+            if m == 1 {
+                // only leaves the nested loop
+                continue;
+            }
+            if m == n {
+                break;
+            }
+        } else {
+            continue;
+        }}
+        seen.push(n);
+    } defer {
+        deferred += 1;
+    } else {
+        seen.push(9);
+    }}
+
+    assert_eq!(seen, [0, 2, 9]);
+    assert_eq!(deferred, 4);
+}
+
+#[test]
+fn test_continue_in_vec_macro() {
+    let mut seen = Vec::new();
+    let mut deferred = 0;
+    for_! { 'outer: n in 0..5 {
+        let row = vec![n, if n == 1 { continue } else { n * 10 }];
+        let pair = vec![n, if n == 3 { continue 'outer } else { 0 }];
+        seen.push((row, pair));
+    } defer {
+        deferred += 1;
+    } else {
+        seen.push((vec![], vec![]));
+    }}
+
+    assert_eq!(
+        seen,
+        [
+            (vec![0, 0], vec![0, 0]),
+            (vec![2, 20], vec![2, 0]),
+            (vec![4, 40], vec![4, 0]),
+            (vec![], vec![]),
+        ]
+    );
+    assert_eq!(deferred, 5);
+}
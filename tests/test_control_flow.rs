@@ -0,0 +1,34 @@
+use core::ops::ControlFlow;
+use for_else::ControlFlowExt;
+
+fn first_even(numbers: &[u32]) -> ControlFlow<u32, usize> {
+    let mut checked = 0;
+    for &n in numbers {
+        if n % 2 == 0 {
+            return ControlFlow::Break(n);
+        }
+        checked += 1;
+    }
+    ControlFlow::Continue(checked)
+}
+
+#[test]
+fn test_break_value_or() {
+    assert_eq!(first_even(&[1, 4, 6]).break_value_or(0), 4);
+    assert_eq!(first_even(&[1, 3]).break_value_or(0), 0);
+}
+
+#[test]
+fn test_break_value_or_else() {
+    assert_eq!(first_even(&[2]).break_value_or_else(|_| unreachable!()), 2);
+    assert_eq!(
+        first_even(&[1, 3, 5]).break_value_or_else(|checked| checked as u32 * 100),
+        300
+    );
+}
+
+#[test]
+fn test_into_result() {
+    assert_eq!(first_even(&[3, 8]).into_result(), Ok(8));
+    assert_eq!(first_even(&[3]).into_result(), Err(1));
+}
@@ -0,0 +1,86 @@
+use for_else::do_while_;
+
+#[test]
+fn test_body_runs_before_the_condition() {
+    let mut runs = 0;
+    let mut else_ran = false;
+    do_while_! { false {
+        runs += 1;
+        if runs == 5 {
+            break;
+        }
+    } else {
+        else_ran = true;
+    }}
+
+    assert_eq!(runs, 1);
+    assert!(else_ran);
+}
+
+#[test]
+fn test_break_skips_else() {
+    let mut n = 0;
+    let mut else_ran = false;
+    do_while_! { n < 10 {
+        n += 1;
+        if n == 4 {
+            break;
+        }
+    } else {
+        else_ran = true;
+    }}
+
+    assert_eq!(n, 4);
+    assert!(!else_ran);
+}
+
+#[test]
+fn test_continue_checks_the_condition() {
+    let mut n = 0;
+    let mut odd = Vec::new();
+    let mut checks = 0;
+    do_while_! { { checks += 1; n < 6 } {
+        n += 1;
+        if n % 2 == 0 {
+            continue;
+        }
+        if n == 9 {
+            break;
+        }
+        odd.push(n);
+    } else {
+        odd.push(0);
+    }}
+
+    assert_eq!(odd, [1, 3, 5, 0]);
+    // the condition is not checked before the first run of the body
+    assert_eq!(checks, 6);
+}
+
+#[test]
+fn test_clauses() {
+    let mut attempts = 0;
+    let mut outcome = "";
+    do_while_! { 'retry: attempts < 3 until attempts == 2 with mut delays = Vec::new() {
+        attempts += 1;
+        delays.push(attempts * 10);
+        if delays.len() > 5 {
+            break 'retry;
+        }
+    } else {
+        outcome = "gave up";
+    } stopped {
+        outcome = "stopped";
+        assert_eq!(delays, [10, 20]);
+    }}
+
+    assert_eq!(outcome, "stopped");
+
+    let mut n = 0;
+    let squares = do_while_! { collect n < 4 {
+        n += 1;
+        emit n * n;
+    }};
+
+    assert_eq!(squares, [1, 4, 9, 16]);
+}
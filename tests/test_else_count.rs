@@ -0,0 +1,90 @@
+use for_else::{for_, loop_else};
+
+#[test]
+fn test_count_of_iterations() {
+    let mut counted = None;
+    for_! { n in [1, 3, 5, 7] {
+        if n % 2 == 0 {
+            break;
+        }
+    } else |count| {
+        counted = Some(count);
+    }}
+
+    assert_eq!(counted, Some(4));
+
+    for_! { n in Vec::<u32>::new() {
+        if n == 0 {
+            break;
+        }
+    } else |count| {
+        counted = Some(count);
+    }}
+
+    assert_eq!(counted, Some(0));
+}
+
+#[test]
+fn test_continue_counts_and_patterns_do_not() {
+    let mut counted = 0;
+    for_! { Some(n) in [Some(1), None, Some(2), None, Some(3)] {
+        if n == 2 {
+            continue;
+        }
+        if n == 9 {
+            break;
+        }
+    } else |count| {
+        counted = count;
+    }}
+
+    assert_eq!(counted, 3);
+
+    let mut n = 0;
+    loop_else! { while n < 6 {
+        n += 1;
+        if n == 10 {
+            break;
+        }
+    } else |iterations| {
+        counted = iterations;
+    }}
+
+    assert_eq!(counted, 6);
+}
+
+#[test]
+fn test_count_with_clauses() {
+    let mut outcome = String::new();
+    let mut seen = 0;
+    for_! { n in 0..10 until seen == 3 {
+        seen += 1;
+        if n == 20 {
+            break;
+        }
+    } else |count| {
+        outcome = format!("else after {}", count);
+    } stopped {
+        outcome = format!("stopped after {}", seen);
+    }}
+
+    assert_eq!(outcome, "stopped after 3");
+
+    let value = for_! { n in [2, 4, 6] {
+        if n > 10 {
+            break n;
+        }
+    } else |count| {
+        count * 100
+    }};
+
+    assert_eq!(value, 300);
+
+    let collected = for_! { collect n in 0..4 {
+        emit n;
+    } else |count| {
+        vec![count]
+    }};
+
+    assert_eq!(collected, [4]);
+}
@@ -0,0 +1,83 @@
+use for_else::{for_, loop_else};
+
+#[test]
+fn test_else_continue_and_break() {
+    let groups = [vec![1, 3], vec![2, 5], vec![7, 8]];
+    let mut with_even = Vec::new();
+    'groups: for (i, group) in groups.iter().enumerate() {
+        for_! { n in group {
+            if n % 2 == 0 {
+                break;
+            }
+        } else continue 'groups; }
+        with_even.push(i);
+    }
+
+    assert_eq!(with_even, [1, 2]);
+
+    let mut checked = 0;
+    'rows: loop {
+        for_! { n in 0..3 {
+            checked += 1;
+            if n == 5 {
+                break;
+            }
+        } else break 'rows; }
+    }
+
+    assert_eq!(checked, 3);
+}
+
+fn first_negative(numbers: &[i32]) -> Option<i32> {
+    let mut found = None;
+    for_! { &n in numbers {
+        if n < 0 {
+            found = Some(n);
+            break;
+        }
+    } else return None; }
+    found
+}
+
+#[test]
+fn test_else_return_and_call() {
+    assert_eq!(first_negative(&[3, -2, 5]), Some(-2));
+    assert_eq!(first_negative(&[3, 5]), None);
+
+    let mut log = Vec::new();
+    let mut n = 0;
+    loop_else! { while n < 3 {
+        n += 1;
+        if n == 7 {
+            break;
+        }
+    } else log.push("done"); }
+
+    assert_eq!(log, ["done"]);
+}
+
+#[test]
+fn test_else_continue_is_not_a_fallback() {
+    let mut found = Vec::new();
+    for round in 0..3 {
+        for_! { n in [round, round + 10] {
+            if n == 11 {
+                break;
+            }
+        } else continue; }
+        found.push(round);
+    }
+
+    assert_eq!(found, [1]);
+
+    // `else continue with` still falls back to another source
+    let mut seen = Vec::new();
+    for_! { n in [1, 2] {
+        seen.push(n);
+        if n == 4 {
+            break;
+        }
+    } else continue with [3, 4]; else seen.push(0); }
+
+    assert_eq!(seen, [1, 2, 3, 4]);
+}
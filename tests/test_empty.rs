@@ -0,0 +1,111 @@
+use for_else::{for_, loop_else};
+
+#[test]
+fn test_empty_runs_instead_of_else() {
+    let mut outcomes = Vec::new();
+    for items in [vec![], vec![1, 3], vec![1, 4]] {
+        for_! { n in items {
+            if n % 2 == 0 {
+                break;
+            }
+        } else {
+            outcomes.push("none matched");
+        } empty {
+            outcomes.push("empty");
+        }}
+    }
+
+    assert_eq!(outcomes, ["empty", "none matched"]);
+}
+
+#[test]
+fn test_refutable_pattern_and_while() {
+    let mut outcome = "";
+    for_! { Some(n) in [None::<u32>, None] {
+        if n == 1 {
+            break;
+        }
+    } else {
+        outcome = "else";
+    } empty {
+        outcome = "empty";
+    }}
+
+    assert_eq!(outcome, "empty");
+
+    let mut n = 10;
+    loop_else! { while n < 5 {
+        n += 1;
+        if n == 3 {
+            break;
+        }
+    } else {
+        outcome = "else";
+    } empty {
+        outcome = "never entered";
+    }}
+
+    assert_eq!(outcome, "never entered");
+}
+
+#[test]
+fn test_with_skipped_and_stopped() {
+    let mut outcomes = Vec::new();
+    for items in [vec![], vec![Some(1), None], vec![None]] {
+        for_! { item in items {
+            let Some(n) = item else {
+                continue;
+            };
+            if n > 5 {
+                break;
+            }
+        } else {
+            outcomes.push("else");
+        } empty {
+            outcomes.push("empty");
+        } skipped {
+            outcomes.push("skipped");
+        }}
+    }
+
+    assert_eq!(outcomes, ["empty", "else", "skipped"]);
+
+    // stopping before the first item is a stop, not an empty loop
+    let mut outcome = "";
+    for_! { n in 0..3 until true {
+        if n == 9 {
+            break;
+        }
+    } else {
+        outcome = "else";
+    } empty {
+        outcome = "empty";
+    } stopped {
+        outcome = "stopped";
+    }}
+
+    assert_eq!(outcome, "stopped");
+}
+
+#[test]
+fn test_collect_and_break_value() {
+    let values = for_! { collect n in Vec::<u32>::new() {
+        emit n;
+    } empty {
+        vec![0]
+    }};
+
+    assert_eq!(values, [0]);
+
+    let first_even = for_! { n in Vec::<u32>::new() {
+        if n % 2 == 0 {
+            break Some(n);
+        }
+    } else {
+        None
+    } empty {
+        Some(u32::MAX)
+    }};
+
+    assert_eq!(first_even, Some(u32::MAX));
+}
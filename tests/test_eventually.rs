@@ -0,0 +1,39 @@
+use for_else::eventually_;
+use std::cell::Cell;
+use std::time::Duration;
+
+#[test]
+fn test_passes_after_retries() {
+    let attempts = Cell::new(0);
+    eventually_! { timeout Duration::from_secs(1), interval Duration::from_millis(1) {
+        attempts.set(attempts.get() + 1);
+        assert!(attempts.get() >= 3);
+    }}
+
+    assert_eq!(attempts.get(), 3);
+}
+
+#[test]
+fn test_passes_first_time_with_defaults() {
+    let attempts = Cell::new(0);
+    eventually_! {{
+        attempts.set(attempts.get() + 1);
+    }}
+
+    assert_eq!(attempts.get(), 1);
+}
+
+#[test]
+fn test_times_out() {
+    let result = std::panic::catch_unwind(|| {
+        eventually_! { interval Duration::from_millis(1), timeout Duration::from_millis(20) {
+            assert_eq!(1 + 1, 3, "arithmetic is broken");
+        }}
+    });
+
+    let payload = result.unwrap_err();
+    let message = payload.downcast_ref::<String>().unwrap();
+    assert!(message.starts_with("eventually_! block at tests/test_eventually.rs:29:"));
+    assert!(message.contains(" still failing after "));
+    assert!(message.contains("arithmetic is broken"));
+}
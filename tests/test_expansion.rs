@@ -0,0 +1,61 @@
+use for_else::assert_expansion;
+
+#[test]
+fn test_for_expansion() {
+    assert_expansion!(
+        for_! { x in 0..3 {
+            if x == 1 {
+                break;
+            }
+        } else {
+            println!("no break");
+        }},
+        {
+            {
+                let mut _for_else_break_occurred = false;
+                for x in 0..3 {
+                    if x == 1 {
+                        {
+                            _for_else_break_occurred = true;
+                            break;
+                        }
+                    }
+                }
+                if !_for_else_break_occurred {
+                    println!("no break");
+                }
+            }
+        }
+    );
+}
+
+#[test]
+fn test_expansion_from_string() {
+    assert_expansion!(
+        for_! { x in items {} else {} },
+        "{
+             let mut _for_else_break_occurred = false;
+             for x in items {}
+             if !_for_else_break_occurred {}
+         }"
+    );
+}
+
+#[test]
+fn test_worklist_expansion() {
+    assert_expansion!(worklist_for_! { n in queue = [1] {} else {} }, {
+        {
+            let mut _for_else_break_occurred = false;
+            let mut queue: ::std::collections::VecDeque<_> =
+                ::std::iter::FromIterator::from_iter([1]);
+            while let ::std::option::Option::Some(n) = queue.pop_front() {}
+            if !_for_else_break_occurred {}
+        }
+    });
+}
+
+#[test]
+#[should_panic(expected = "unexpected expansion of `for_!`")]
+fn test_expansion_mismatch() {
+    assert_expansion!(for_! { x in 0..3 {} else {} }, { for x in 0..3 {} });
+}
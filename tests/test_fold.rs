@@ -0,0 +1,118 @@
+use for_else::{for_, loop_else};
+
+#[test]
+fn test_break() {
+    let mut seen = vec![];
+    let mut flag = true;
+    for_! { fold n in (1..4).chain(10..14) {
+        seen.push(n);
+        if n > 10 {
+            break;
+        }
+    } else {
+        flag = false;
+    }}
+
+    assert!(flag);
+    assert_eq!(seen, [1, 2, 3, 10, 11]);
+}
+
+#[test]
+fn test_no_break() {
+    let mut sum = 0;
+    let mut flag = false;
+    for_! { fold n in [1, 2, 3].iter().chain(&[4, 5]) {
+        sum += n;
+    } else {
+        flag = true;
+    }}
+
+    assert!(flag);
+    assert_eq!(sum, 15);
+}
+
+#[test]
+fn test_continue_and_nested_loops() {
+    let mut seen = vec![];
+    let mut flag = true;
+    for_! { 'outer: fold row in [[1, 2], [3, 4], [5, 6]] {
+        if row[0] == 1 {
+            continue;
+        }
+        for cell in row {
+            if cell == 4 {
+                continue 'outer;
+            }
+            if cell == 6 {
+                break 'outer;
+            }
+            seen.push(cell);
+        }
+    } else {
+        flag = false;
+    }}
+
+    assert!(flag);
+    assert_eq!(seen, [3, 5]);
+}
+
+#[test]
+fn test_refutable_pattern_and_modifiers() {
+    let readings = [None, Some(1), None, Some(2), Some(3), Some(4)];
+    let mut seen = vec![];
+    let mut flag = false;
+    for_! { fold Some(reading) in readings, skip 1, take 4 {
+        seen.push(reading);
+    } else {
+        flag = true;
+    }}
+
+    assert!(flag);
+    assert_eq!(seen, [1, 2, 3]);
+}
+
+#[test]
+fn test_with_state() {
+    let mut longest = 0;
+    for_! { fold word in ["a", "abc", "ab"] with mut current = 0 {
+        current += 1;
+        longest = longest.max(word.len() * current);
+    } else {
+    }}
+
+    assert_eq!(longest, 6);
+}
+
+#[test]
+fn test_loop_else() {
+    let mut flag = false;
+    loop_else! { fold for n in 0..5 {
+        if n == 10 {
+            break;
+        }
+    } else {
+        flag = true;
+    }}
+
+    assert!(flag);
+}
+
+#[test]
+fn test_continue_in_else_if_and_let_initializer() {
+    let mut seen = Vec::new();
+    let mut flag = false;
+    for_! { fold n in 0..8 {
+        if n == 9 {
+            break;
+        } else if n % 3 == 0 {
+            continue;
+        }
+        let half = if n % 2 == 0 { n / 2 } else { continue };
+        seen.push(half);
+    } else {
+        flag = true;
+    }}
+
+    assert!(flag);
+    assert_eq!(seen, [1, 2]);
+}
@@ -0,0 +1,141 @@
+#![cfg(feature = "futures")]
+
+use for_else::for_await_;
+use futures::executor::block_on;
+use futures::stream::{self, StreamExt};
+
+#[test]
+fn test_else_runs_when_stream_is_exhausted() {
+    let mut seen = Vec::new();
+    let mut else_ran = false;
+    block_on(async {
+        for_await_! { n in stream::iter([1, 3, 5]) {
+            if n % 2 == 0 {
+                break;
+            }
+            seen.push(n);
+        } else {
+            else_ran = true;
+        }}
+    });
+
+    assert_eq!(seen, [1, 3, 5]);
+    assert!(else_ran);
+}
+
+#[test]
+fn test_break_skips_else() {
+    let mut found = None;
+    block_on(async {
+        for_await_! { n in stream::iter(1..10).then(|n| async move { n * n }) {
+            if n > 20 {
+                found = Some(n);
+                break;
+            }
+        } else {
+            found = None;
+        }}
+    });
+
+    assert_eq!(found, Some(25));
+}
+
+#[test]
+fn test_body_awaits_and_labeled_break() {
+    let pages = [vec!["a", "b"], vec!["needle", "c"], vec!["d"]];
+    let mut read = Vec::new();
+    let mut outcome = "";
+    block_on(async {
+        for_await_! { 'pages: page in stream::iter(&pages) {
+            for word in page {
+                let word = async { *word }.await;
+                if word == "needle" {
+                    break 'pages;
+                }
+                read.push(word);
+            }
+        } else {
+            outcome = "not found";
+        }}
+    });
+
+    assert_eq!(outcome, "");
+    assert_eq!(read, ["a", "b"]);
+}
+
+#[test]
+fn test_until_and_collect() {
+    let mut outcome = "";
+    let mut seen = 0;
+    let doubled = block_on(async {
+        for_await_! { n in stream::iter(1..100) until seen == 3 {
+            seen = n;
+        } else {
+            outcome = "else";
+        } stopped {
+            outcome = "stopped";
+        }}
+
+        for_await_! { collect (i, n) in stream::iter([4, 5, 6]).enumerate() {
+            emit (i, n * 2);
+        }}
+    });
+
+    assert_eq!(outcome, "stopped");
+    assert_eq!(seen, 3);
+    assert_eq!(doubled, [(0, 8), (1, 10), (2, 12)]);
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn test_within_stops_a_stalled_stream() {
+    use std::time::Duration;
+
+    let mut seen = Vec::new();
+    let mut outcome = "";
+    // the stream stalls after its second item, which only the deadline can end
+    let readings = stream::iter([1, 2]).chain(stream::pending());
+    let waited = tokio::time::timeout(Duration::from_secs(2), async {
+        for_await_! { reading in readings within(Duration::from_millis(50)) {
+            seen.push(reading);
+        } else {
+            outcome = "else";
+        } timed_out {
+            outcome = "timed out";
+        }}
+    })
+    .await;
+
+    assert!(waited.is_ok());
+    assert_eq!(seen, [1, 2]);
+    assert_eq!(outcome, "timed out");
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test(flavor = "current_thread")]
+async fn test_cooldown_does_not_block_the_executor() {
+    use std::cell::Cell;
+    use std::time::Duration;
+
+    let ticks = Cell::new(0);
+    let mut seen = Vec::new();
+    let ticker = async {
+        for _ in 0..5 {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+            ticks.set(ticks.get() + 1);
+        }
+    };
+    let searching = async {
+        for_await_! { n in stream::iter([1, 2, 3]) cooldown(Duration::from_millis(20)) {
+            seen.push((n, ticks.get()));
+        } else {
+            seen.push((0, ticks.get()));
+        }}
+    };
+    tokio::join!(ticker, searching);
+
+    // the ticker ran while the loop was cooling down
+    assert_eq!(seen[0], (1, 0));
+    assert!(seen[1].1 > 0);
+    assert_eq!(seen.len(), 4);
+}
@@ -0,0 +1,81 @@
+#![cfg(feature = "fs")]
+
+use for_else::fs_search_;
+
+#[test]
+fn test_found() {
+    let mut found = false;
+    fs_search_! { entry in concat!(env!("CARGO_MANIFEST_DIR"), "/src") {
+        if entry.file_name() == "lib.rs" {
+            found = true;
+            break;
+        }
+    } err e {
+        panic!("{}", e);
+    } else {
+        panic!("lib.rs not found");
+    }}
+
+    assert!(found);
+}
+
+#[test]
+fn test_not_recursive() {
+    let mut flag = false;
+    fs_search_! { entry in env!("CARGO_MANIFEST_DIR") {
+        if entry.file_name() == "test_fs_search.rs" {
+            break;
+        }
+    } err e {
+        panic!("{}", e);
+    } else {
+        flag = true;
+    }}
+
+    assert!(flag);
+}
+
+#[test]
+fn test_recursive() {
+    let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests");
+    let mut flag = true;
+    fs_search_! { entry in recursive root.parent().unwrap() {
+        if entry.file_name() == "test_fs_search.rs" {
+            break;
+        }
+    } err _e {
+    } else {
+        flag = false;
+    }}
+
+    assert!(flag);
+}
+
+#[test]
+fn test_err() {
+    let mut errors = 0;
+    let mut flag = false;
+    fs_search_! { _entry in concat!(env!("CARGO_MANIFEST_DIR"), "/does-not-exist") {
+        break;
+    } err _e {
+        errors += 1;
+    } else {
+        flag = true;
+    }}
+
+    assert_eq!(errors, 1);
+    assert!(flag);
+}
+
+#[test]
+fn test_break_from_err() {
+    let mut flag = true;
+    fs_search_! { _entry in concat!(env!("CARGO_MANIFEST_DIR"), "/does-not-exist") {
+    } err _e {
+        break;
+    } else {
+        flag = false;
+    }}
+
+    assert!(flag);
+}
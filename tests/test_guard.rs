@@ -0,0 +1,92 @@
+use for_else::{for_, loop_else};
+
+#[test]
+fn test_guard_skips_items() {
+    let mut seen = Vec::new();
+    let mut else_ran = false;
+    for_! { n in 0..10 if n % 3 == 0 {
+        seen.push(n);
+        if n > 20 {
+            break;
+        }
+    } else {
+        else_ran = true;
+    }}
+
+    assert_eq!(seen, [0, 3, 6, 9]);
+    assert!(else_ran);
+}
+
+#[test]
+fn test_guarded_items_do_not_count() {
+    let mut counted = 0;
+    let mut outcome = "";
+    for_! { Some(word) in [Some("a"), None, Some("bb"), Some("ccc")] if word.len() > 1 {
+        if word.len() > 5 {
+            break;
+        }
+    } else |count| {
+        counted = count;
+    }}
+
+    assert_eq!(counted, 2);
+
+    for_! { n in [1, 3, 5] if n % 2 == 0 {
+        if n > 0 {
+            break;
+        }
+    } else {
+        outcome = "else";
+    } empty {
+        outcome = "empty";
+    }}
+
+    assert_eq!(outcome, "empty");
+}
+
+#[test]
+fn test_guard_with_modifiers_and_clauses() {
+    let mut seen = Vec::new();
+    let mut deferred = 0;
+    for_! { n in 0..20, step 2 if n % 3 != 0 until seen.len() == 3 {
+        seen.push(n);
+    } defer {
+        deferred += 1;
+    } else {
+        seen.push(0);
+    } stopped {
+        seen.push(99);
+    }}
+
+    assert_eq!(seen, [2, 4, 8, 99]);
+    assert_eq!(deferred, 3);
+
+    let mut visited = Vec::new();
+    loop_else! { fold for n in 1..10 if n % 4 == 0 {
+        visited.push(n);
+        if n == 8 {
+            break;
+        }
+    } else {
+        visited.clear();
+    }}
+
+    assert_eq!(visited, [4, 8]);
+}
+
+#[test]
+fn test_guard_in_expressions() {
+    let evens = for_! { collect n in 0..7 if n % 2 == 0 {
+        emit n * 10;
+    }};
+
+    assert_eq!(evens, [0, 20, 40, 60]);
+
+    let first = for_! { (i, c) in "a1b2".chars().enumerate() if c.is_ascii_digit() {
+        break (i, c);
+    } else {
+        (0, ' ')
+    }};
+
+    assert_eq!(first, (1, '1'));
+}
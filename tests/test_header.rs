@@ -0,0 +1,311 @@
+use for_else::{for_, loop_else};
+
+async fn fetch_candidates() -> Vec<u32> {
+    vec![3, 8, 5]
+}
+
+async fn has_more(remaining: &mut u32) -> bool {
+    if *remaining == 0 {
+        false
+    } else {
+        *remaining -= 1;
+        true
+    }
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_await_in_iterable() {
+    let mut found = None;
+    for_! { x in fetch_candidates().await {
+        if x % 2 == 0 {
+            found = Some(x);
+            break;
+        }
+    } else {
+        panic!("no even candidate");
+    }}
+
+    assert_eq!(found, Some(8));
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_await_in_iterable_with_modifiers() {
+    let mut seen = Vec::new();
+    for_! { x in fetch_candidates().await, skip 1 {
+        // the 3 is skipped
+        if x == 3 {
+            break;
+        }
+        seen.push(x);
+    } else {
+        seen.push(0);
+    }}
+
+    assert_eq!(seen, [8, 5, 0]);
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_await_in_while_condition() {
+    let mut remaining = 3;
+    let mut runs = 0;
+    let mut flag = false;
+    loop_else! { while has_more(&mut remaining).await {
+        runs += 1;
+        if runs > 3 {
+            break;
+        }
+    } else {
+        flag = true;
+    }}
+
+    assert!(flag);
+    assert_eq!(runs, 3);
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_await_in_until() {
+    let mut remaining = 2;
+    let mut seen = Vec::new();
+    for_! { x in fetch_candidates().await until !has_more(&mut remaining).await {
+        seen.push(x);
+    } else {
+    } stopped {
+        seen.push(0);
+    }}
+
+    assert_eq!(seen, [3, 8, 0]);
+}
+
+fn load_rows(fail: bool) -> Result<Vec<u32>, String> {
+    if fail {
+        Err("connection refused".to_string())
+    } else {
+        Ok(vec![1, 2, 3])
+    }
+}
+
+fn sum_rows(fail: bool) -> Result<u32, String> {
+    let mut sum = 0;
+    for_! { row in load_rows(fail)? {
+        if row == 0 {
+            break;
+        }
+        sum += row;
+    } else {
+        sum += 100;
+    }}
+    Ok(sum)
+}
+
+#[test]
+fn test_try_in_iterable() {
+    assert_eq!(sum_rows(false), Ok(106));
+    assert_eq!(sum_rows(true), Err("connection refused".to_string()));
+}
+
+fn first_even_row(fail: bool) -> Option<Result<u32, String>> {
+    let rows = for_! { collect row in load_rows(fail).ok()?, step 1 {
+        if row % 2 == 0 {
+            emit Ok(row);
+        }
+    }};
+    rows.into_iter().next()
+}
+
+#[test]
+fn test_try_option_with_modifiers() {
+    assert_eq!(first_even_row(false), Some(Ok(2)));
+    assert_eq!(first_even_row(true), None);
+}
+
+fn ready(fail: bool) -> Result<bool, String> {
+    load_rows(fail).map(|rows| rows.is_empty())
+}
+
+fn count_until_ready(fail: bool) -> Result<usize, String> {
+    let mut count = 0;
+    loop_else! { while !ready(fail)? {
+        count += 1;
+        if count == 3 {
+            break;
+        }
+    } else {
+        count = 0;
+    }}
+    Ok(count)
+}
+
+#[test]
+fn test_try_in_while_condition() {
+    assert_eq!(count_until_ready(false), Ok(3));
+    assert!(count_until_ready(true).is_err());
+}
+
+trait Lookup {
+    fn candidates<K: Into<u64>>(key: K) -> Vec<u64>;
+}
+
+struct MyMap;
+
+impl Lookup for MyMap {
+    fn candidates<K: Into<u64>>(key: K) -> Vec<u64> {
+        let key = key.into();
+        vec![key, key * 2, key * 3]
+    }
+}
+
+#[test]
+fn test_qualified_path_with_turbofish() {
+    let mut seen = Vec::new();
+    for_! { x in <MyMap as Lookup>::candidates::<u32>(5) {
+        if x == 0 {
+            break;
+        }
+        seen.push(x);
+    } else {
+        seen.push(0);
+    }}
+
+    assert_eq!(seen, [5, 10, 15, 0]);
+}
+
+#[test]
+fn test_turbofish_collect() {
+    let mut found = false;
+    for_! { x in "1,2,3".split(',').map(str::parse::<u8>).collect::<Result<Vec<_>, _>>().unwrap(), take 2 {
+        if x == 2 {
+            found = true;
+            break;
+        }
+    } else {
+        panic!("no 2 in the first two items");
+    }}
+
+    assert!(found);
+}
+
+#[test]
+fn test_qualified_path_in_while_let() {
+    let mut stack = <Vec<u64> as From<[u64; 2]>>::from([1, 2]);
+    let mut popped = Vec::new();
+    loop_else! { while let Some(x) = <Vec<u64>>::pop(&mut stack) {
+        if x == 0 {
+            break;
+        }
+        popped.push(x);
+    } else {
+        popped.push(0);
+    }}
+
+    assert_eq!(popped, [2, 1, 0]);
+}
+
+#[test]
+fn test_in_ref() {
+    let names = vec!["alice".to_string(), "bob".to_string()];
+    let mut found = None;
+    for_! { name in ref names {
+        if name.starts_with('b') {
+            found = Some(name.len());
+            break;
+        }
+    } else {
+        panic!("no name starts with b");
+    }}
+
+    assert_eq!(found, Some(3));
+    assert_eq!(names.len(), 2);
+}
+
+#[test]
+fn test_in_mut() {
+    let mut counts = vec![1, 2, 3];
+    let mut flag = false;
+    for_! { count in mut counts, take 2 {
+        if *count == 0 {
+            break;
+        }
+        *count *= 10;
+    } else {
+        flag = true;
+    }}
+
+    assert!(flag);
+    assert_eq!(counts, [10, 20, 3]);
+}
+
+#[test]
+fn test_in_ref_of_range_expression() {
+    let ranges = [0..2, 5..7];
+    let mut total = 0;
+    for_! { range in ref ranges[..] {
+        if range.is_empty() {
+            break;
+        }
+        total += range.len();
+    } else {
+        total += 100;
+    }}
+
+    assert_eq!(total, 104);
+}
+
+#[test]
+fn test_in_mut_loop_else() {
+    let mut stacks = vec![vec![1], vec![2, 3]];
+    loop_else! { for stack in mut stacks {
+        if stack.is_empty() {
+            break;
+        }
+        stack.push(0);
+    } else {
+        stacks.push(vec![]);
+    }}
+
+    assert_eq!(stacks, [vec![1, 0], vec![2, 3, 0], vec![]]);
+}
+
+#[test]
+fn test_match_in_iterable() {
+    let (small, large) = ([1, 2], [10, 20, 30]);
+    let big = true;
+    let mut total = 0;
+    for_! { x in match big { true => large.iter(), false => small.iter() } {
+        if *x == 0 {
+            break;
+        }
+        total += x;
+    } else {
+        total += 1;
+    }}
+
+    assert_eq!(total, 61);
+}
+
+#[test]
+fn test_loop_and_unsafe_in_header() {
+    let values = [4, 7, 9];
+    let mut attempts = 0;
+    let mut found = None;
+    for_! { x in loop { attempts += 1; if attempts == 2 { break values } } {
+        if x % 2 == 1 {
+            found = Some(x);
+            break;
+        }
+    } else {
+        panic!("no odd value");
+    }}
+
+    let mut remaining = 3;
+    loop_else! { while unsafe { core::ptr::read(&remaining) } > 0 {
+        remaining -= 1;
+        if found.is_none() {
+            break;
+        }
+    } else {
+        found = found.map(|x| x + remaining);
+    }}
+
+    assert_eq!(found, Some(7));
+    assert_eq!(attempts, 2);
+}
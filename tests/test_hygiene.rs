@@ -0,0 +1,257 @@
+//! The macros in the contexts of library code: trait default methods, generic methods with
+//! `Self` and `self` in the header, and `impl Trait` arguments.
+
+use for_else::{for_, loop_else};
+
+trait Inventory {
+    const LIMIT: usize;
+
+    fn items(&self) -> &[u32];
+
+    fn first_over(&self, threshold: u32) -> Option<u32> {
+        let mut found = None;
+        for_! { &item in self.items() {
+            if item > threshold {
+                found = Some(item);
+                break;
+            }
+        } else {
+            found = None;
+        }}
+        found
+    }
+
+    fn within_limit(&self) -> bool {
+        for_! { index in 0..self.items().len(), take Self::LIMIT + 1 {
+            if index == Self::LIMIT {
+                return false;
+            }
+        } else {
+            return true;
+        }}
+        unreachable!()
+    }
+
+    fn evens(&self) -> Vec<u32> {
+        for_! { collect &item in self.items() {
+            if item % 2 == 0 {
+                emit item;
+            }
+        }}
+    }
+
+    fn count_down(&mut self) -> usize
+    where
+        Self: Sized,
+    {
+        let mut steps = 0;
+        loop_else! { while steps < Self::LIMIT && steps < self.items().len() {
+            if self.items()[steps] == 0 {
+                break;
+            }
+            steps += 1;
+        } else {
+            steps *= 10;
+        }}
+        steps
+    }
+}
+
+struct Shelf(Vec<u32>);
+
+impl Inventory for Shelf {
+    const LIMIT: usize = 2;
+
+    fn items(&self) -> &[u32] {
+        &self.0
+    }
+}
+
+#[test]
+fn test_trait_default_methods() {
+    let mut shelf = Shelf(vec![1, 4, 6]);
+    assert_eq!(shelf.first_over(3), Some(4));
+    assert_eq!(shelf.first_over(9), None);
+    assert!(!shelf.within_limit());
+    assert!(Shelf(vec![1]).within_limit());
+    assert_eq!(shelf.evens(), [4, 6]);
+    assert_eq!(shelf.count_down(), 20);
+}
+
+struct Grid<T> {
+    rows: Vec<Vec<T>>,
+}
+
+impl<T: PartialEq + Copy> Grid<T> {
+    fn new(rows: Vec<Vec<T>>) -> Self {
+        Self { rows }
+    }
+
+    fn position(&self, target: T) -> Option<(usize, usize)> {
+        let mut position = None;
+        for_! { 'rows: (y, row) in self.rows.iter().enumerate() with mut checked = 0usize {
+            for (x, &cell) in row.iter().enumerate() {
+                checked += 1;
+                if cell == target {
+                    position = Some((x, y));
+                    break 'rows;
+                }
+            }
+        } else {
+            assert_eq!(checked, self.rows.iter().map(Vec::len).sum::<usize>());
+        }}
+        position
+    }
+
+    fn transposed_into<U: TryFrom<T>>(self) -> Option<Grid<U>> {
+        let mut rows: Vec<Vec<U>> = Vec::new();
+        for_! { row in ref self.rows {
+            match row.iter().map(|&cell| U::try_from(cell)).collect() {
+                Ok(converted) => rows.push(converted),
+                Err(_) => break,
+            }
+        } else {
+            return Some(Grid::<U> { rows });
+        }}
+        None
+    }
+
+    fn is_empty(&self) -> bool {
+        let mut cells = 0;
+        for_! { row in Self::non_empty_rows(self) {
+            if cells + row.len() > 1 {
+                return false;
+            }
+            cells += row.len();
+        } else {
+            return cells == 0;
+        }}
+        unreachable!()
+    }
+
+    fn non_empty_rows(grid: &Self) -> impl Iterator<Item = &Vec<T>> {
+        grid.rows.iter().filter(|row| !row.is_empty())
+    }
+}
+
+#[test]
+fn test_generic_methods_with_self() {
+    let grid = Grid::new(vec![vec![1u8, 2], vec![3]]);
+    assert_eq!(grid.position(3), Some((0, 1)));
+    assert_eq!(grid.position(9), None);
+    assert!(!grid.is_empty());
+    assert!(Grid::<u8>::new(vec![vec![]]).is_empty());
+    let widened: Grid<u32> = grid.transposed_into().unwrap();
+    assert_eq!(widened.rows, [vec![1, 2], vec![3]]);
+    assert!(Grid::new(vec![vec![300u16]])
+        .transposed_into::<u8>()
+        .is_none());
+}
+
+fn first_word(words: impl IntoIterator<Item = impl AsRef<str>>, prefix: &str) -> Option<String> {
+    for_! { word in words {
+        if word.as_ref().starts_with(prefix) {
+            return Some(word.as_ref().to_string());
+        }
+    } else {
+        return None;
+    }}
+    unreachable!()
+}
+
+fn total_until(mut numbers: impl Iterator<Item = u32>, cap: u32) -> (u32, bool) {
+    let mut total = 0;
+    let mut capped = false;
+    loop_else! { while let Some(n) = numbers.next() until total >= cap {
+        total += n;
+    } else {
+    } stopped {
+        capped = true;
+    }}
+    (total, capped)
+}
+
+#[test]
+fn test_impl_trait_arguments() {
+    assert_eq!(
+        first_word(["apple", "banana"], "b").as_deref(),
+        Some("banana")
+    );
+    assert_eq!(first_word(vec![String::from("kiwi")], "b"), None);
+    assert_eq!(total_until([1, 2, 3, 4].into_iter(), 3), (3, true));
+    assert_eq!(total_until([1, 1].into_iter(), 3), (2, false));
+}
+
+/// A macro of the user's crate that names locals the same way the expansion does.
+macro_rules! search {
+    ($items:expr, $target:expr) => {{
+        let _for_else_break_occurred = "user value";
+        let mut found = false;
+        for_! { item in $items {
+            if item == $target {
+                found = true;
+                break;
+            }
+        } else {
+            found = false;
+        }}
+        assert_eq!(_for_else_break_occurred, "user value");
+        found
+    }};
+}
+
+#[test]
+fn test_inside_macro_rules() {
+    assert!(search!([1, 2, 3], 2));
+    assert!(!search!([1, 2, 3], 4));
+}
+
+#[test]
+fn test_user_variable_named_like_the_flag() {
+    let mut else_ran = false;
+    for_! { n in 0..3 {
+        let _for_else_break_occurred = n == 1;
+        if _for_else_break_occurred {
+            break;
+        }
+    } else {
+        else_ran = true;
+    }}
+
+    assert!(!else_ran);
+
+    let mut _for_else_break_occurred = true;
+    for_! { n in 0..3 {
+        if n == 9 {
+            break;
+        }
+    } else {
+        _for_else_break_occurred = false;
+    }}
+
+    // the `else` block assigns the user's variable rather than the flag of the loop
+    assert!(!_for_else_break_occurred);
+}
+
+#[test]
+fn test_nested_expansions_keep_their_own_flags() {
+    let mut inner_else = 0;
+    let mut outer_else = false;
+    for_! { row in [[1, 2], [3, 4]] {
+        for_! { cell in row {
+            if cell == 2 {
+                break;
+            }
+        } else {
+            inner_else += 1;
+        }}
+        if row[0] == 9 {
+            break;
+        }
+    } else {
+        outer_else = true;
+    }}
+
+    assert_eq!(inner_else, 1);
+    assert!(outer_else);
+}
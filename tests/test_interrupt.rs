@@ -0,0 +1,98 @@
+use for_else::{for_, loop_else, Interruptible};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+#[test]
+fn test_not_interrupted() {
+    let flag = AtomicBool::new(false);
+    let mut seen = Vec::new();
+    let mut outcome = "";
+    for_! { n in 0..3 interrupt(&flag) {
+        seen.push(n);
+    } else {
+        outcome = "else";
+    } stopped {
+        outcome = "stopped";
+    }}
+
+    assert_eq!(outcome, "else");
+    assert_eq!(seen, [0, 1, 2]);
+}
+
+#[test]
+fn test_interrupted_by_flag() {
+    let flag = Arc::new(AtomicBool::new(false));
+    let mut seen = Vec::new();
+    let mut outcome = "";
+    for_! { n in 0..10 interrupt(flag.clone()) {
+        seen.push(n);
+        if n == 2 {
+            flag.store(true, Ordering::Release);
+        }
+    } else {
+        outcome = "else";
+    } stopped {
+        outcome = "stopped";
+    }}
+
+    assert_eq!(outcome, "stopped");
+    assert_eq!(seen, [0, 1, 2]);
+}
+
+#[test]
+fn test_interrupt_with_until() {
+    let flag = Cell::new(false);
+    let mut seen = Vec::new();
+    let mut flagged = false;
+    for_! { n in 0..10 until seen.len() == 5 interrupt(&flag) {
+        seen.push(n);
+    } else {
+    } stopped {
+        flagged = true;
+    }}
+
+    assert!(flagged);
+    assert_eq!(seen, [0, 1, 2, 3, 4]);
+
+    let mut seen = Vec::new();
+    for_! { n in 0..10 until seen.len() == 5 interrupt(&flag) {
+        seen.push(n);
+        flag.set(n == 1);
+    } else {
+    }}
+    assert_eq!(seen, [0, 1]);
+}
+
+#[test]
+fn test_loop_else_while() {
+    let flag = Cell::new(false);
+    let mut count = 0;
+    loop_else! { while count < 100 interrupt(&flag) {
+        count += 1;
+        if count == 3 {
+            flag.set(true);
+        }
+    } else {
+    }}
+
+    assert_eq!(count, 3);
+    assert!(flag.is_interrupted());
+}
+
+#[cfg(feature = "tokio-util")]
+#[test]
+fn test_cancellation_token() {
+    let token = tokio_util::sync::CancellationToken::new();
+    let mut seen = Vec::new();
+    for_! { n in 0..10 interrupt(token.clone()) {
+        seen.push(n);
+        if n == 1 {
+            token.cancel();
+        }
+    } else {
+        panic!("not cancelled");
+    }}
+
+    assert_eq!(seen, [0, 1]);
+}
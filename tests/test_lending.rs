@@ -0,0 +1,97 @@
+#![cfg(feature = "lending")]
+
+use for_else::{for_, loop_else, LendingIterator};
+
+/// Yields the fields of comma-separated lines, reusing one buffer for every line.
+struct Fields<'d> {
+    lines: std::str::Lines<'d>,
+    buffer: Vec<String>,
+}
+
+impl<'d> Fields<'d> {
+    fn new(data: &'d str) -> Self {
+        Fields {
+            lines: data.lines(),
+            buffer: Vec::new(),
+        }
+    }
+}
+
+impl LendingIterator for Fields<'_> {
+    type Item<'a>
+        = &'a [String]
+    where
+        Self: 'a;
+
+    fn next(&mut self) -> Option<&[String]> {
+        let line = self.lines.next()?;
+        self.buffer.clear();
+        self.buffer
+            .extend(line.split(',').map(|field| field.trim().to_string()));
+        Some(&self.buffer)
+    }
+}
+
+#[test]
+fn test_lend_else() {
+    let mut widths = Vec::new();
+    let mut finished = false;
+    for_! { fields in lend Fields::new("a, b\nc\nd, e, f") {
+        widths.push(fields.len());
+        if fields.is_empty() {
+            break;
+        }
+    } else {
+        finished = true;
+    }}
+
+    assert_eq!(widths, [2, 1, 3]);
+    assert!(finished);
+}
+
+#[test]
+fn test_lend_break() {
+    let mut fields = Fields::new("id, name\n1, ann\n2, bob");
+    let mut found = None;
+    for_! { row in lend &mut fields {
+        if row[1] == "ann" {
+            found = Some(row[0].clone());
+            break;
+        }
+    } else {
+        found = None;
+    }}
+
+    assert_eq!(found.as_deref(), Some("1"));
+    // the lender was borrowed, so the rest of its items are still there
+    assert_eq!(
+        fields.next().map(<[String]>::to_vec),
+        Some(vec!["2".to_string(), "bob".to_string()])
+    );
+}
+
+#[test]
+fn test_lend_refutable_pattern() {
+    let mut names = Vec::new();
+    for_! { [_, name] in lend Fields::new("1, ann\n2\n3, bob") {
+        names.push(name.clone());
+    } else {
+        names.push("end".to_string());
+    }}
+
+    assert_eq!(names, ["ann", "bob", "end"]);
+}
+
+#[test]
+fn test_lend_until_loop_else() {
+    let mut seen = 0;
+    loop_else! { for _ in lend Fields::new("a\nb\nc") until seen == 2 {
+        seen += 1;
+    } else {
+        seen = 100;
+    } stopped {
+        seen += 10;
+    }}
+
+    assert_eq!(seen, 12);
+}
@@ -0,0 +1,91 @@
+use for_else::{loop_else, while_let_};
+
+#[test]
+fn test_else_runs_when_condition_fails() {
+    let mut lines = ["a", "b", "", "c"].into_iter();
+    let mut read = Vec::new();
+    let mut else_ran = false;
+    loop_else! { while let Some(line) = lines.next() && !line.is_empty() {
+        if line == "c" {
+            break;
+        }
+        read.push(line);
+    } else {
+        else_ran = true;
+    }}
+
+    assert_eq!(read, ["a", "b"]);
+    assert!(else_ran);
+    assert_eq!(lines.next(), Some("c"));
+}
+
+#[test]
+fn test_break_skips_else() {
+    let mut stack = vec![9, 4, 2, 1];
+    let mut seen = Vec::new();
+    let mut else_ran = false;
+    while_let_! { Some(n) = stack.pop() && n < 5 {
+        seen.push(n);
+        if n == 4 {
+            break;
+        }
+    } else {
+        else_ran = true;
+    }}
+
+    assert_eq!(seen, [1, 2, 4]);
+    assert!(!else_ran);
+    assert_eq!(stack, [9]);
+}
+
+#[test]
+fn test_chained_lets_see_earlier_bindings() {
+    let words = ["1", "2", "x", "4"];
+    let mut index = 0;
+    let mut total = 0;
+    let mut stopped_at = None;
+    loop_else! { while index < words.len() && let Some(word) = words.get(index) && let Ok(n) = word.parse::<u32>() {
+        total += n;
+        index += 1;
+        if total > 100 {
+            break;
+        }
+    } else {
+        stopped_at = Some(index);
+    }}
+
+    assert_eq!(total, 3);
+    assert_eq!(stopped_at, Some(2));
+}
+
+#[test]
+fn test_label_continue_and_clauses() {
+    let mut queue = vec![5, 0, 3, 2, 7, 1].into_iter();
+    let mut kept = Vec::new();
+    let mut outcome = "";
+    loop_else! { 'queue: while let Some(n) = queue.next() && n != 7 until kept.len() == 2 {
+        if n == 0 {
+            continue;
+        }
+        for _ in 0..n {
+            if n > 10 {
+                break 'queue;
+            }
+        }
+        kept.push(n);
+    } else {
+        outcome = "else";
+    } stopped {
+        outcome = "stopped";
+    }}
+
+    assert_eq!(kept, [5, 3]);
+    assert_eq!(outcome, "stopped");
+
+    let mut chars = "ab1c".chars();
+    let letters = while_let_! { collect Some(c) = chars.next() && c.is_alphabetic() {
+        emit c.to_ascii_uppercase();
+    }};
+
+    assert_eq!(letters, ['A', 'B']);
+}
@@ -0,0 +1,185 @@
+//! The expansions compiled under `-D warnings` and clippy's pedantic group, which must not
+//! report anything about code the user did not write.
+
+#![deny(warnings, clippy::pedantic)]
+
+use for_else::{cfor_, do_while_, for_, loop_else, search_, try_for_, while_let_};
+
+#[test]
+fn test_for_clauses() {
+    let mut outcome = "";
+    for_! { 'items: n in 0..10 {
+        if n == 3 {
+            break 'items;
+        }
+    } else {
+        outcome = "else";
+    }}
+    assert_eq!(outcome, "");
+
+    let mut deferred = 0;
+    for_! { n in 0..5 until deferred == 4 {
+        if n == 9 {
+            break;
+        }
+        if n == 1 {
+            continue;
+        }
+    } defer {
+        deferred += 1;
+    } else {
+        outcome = "else";
+    } stopped {
+        outcome = "stopped";
+    }}
+    assert_eq!((outcome, deferred), ("stopped", 4));
+
+    let found = for_! { n in 1..10 if n % 2 == 0 {
+        if n > 5 {
+            break n;
+        }
+    } else {
+        0
+    }};
+    assert_eq!(found, 6);
+
+    let squares = for_! { collect n in 1..4 {
+        emit n * n;
+    }};
+    assert_eq!(squares, [1, 4, 9]);
+}
+
+#[test]
+fn test_count_empty_and_then() {
+    let mut outcome = String::new();
+    for_! { n in Vec::<u32>::new() {
+        if n == 1 {
+            break;
+        }
+    } then {
+        outcome.push_str("then");
+    } else |count| {
+        outcome = format!("ran {count} times");
+    } empty {
+        outcome.push_str("empty");
+    }}
+    assert_eq!(outcome, "empty");
+}
+
+#[test]
+fn test_count_forms() {
+    let mut ran = 0;
+    for_! { n in 0..4 {
+        if n == 9 {
+            break;
+        }
+    } else |count| { ran = count; }}
+    assert_eq!(ran, 4);
+
+    let found = for_! { n in 0..4 {
+        if n == 9 {
+            break n;
+        }
+    } else |count| { count }};
+    assert_eq!(found, 4);
+
+    let mut seen = Vec::new();
+    for_! { n in 0..3 {
+        seen.push(n);
+    } else |count| { assert_eq!(count, seen.len()) }}
+}
+
+#[test]
+fn test_other_forms() {
+    let mut stack = vec![1, 2, 3];
+    let mut seen = Vec::new();
+    while_let_! { Some(n) = stack.pop() {
+        if n == 9 {
+            break;
+        }
+        seen.push(n);
+    } else {
+        seen.push(0);
+    }}
+    assert_eq!(seen, [3, 2, 1, 0]);
+
+    let mut n = 0;
+    loop_else! { while n < 5 {
+        n += 1;
+        if n == 3 {
+            break;
+        }
+    } else {
+        n = 0;
+    }}
+    assert_eq!(n, 3);
+
+    let mut last = 0;
+    cfor_! { let mut i = 0; i < 4; i += 1 {
+        if i == 9 {
+            break;
+        }
+        last = i;
+    } else {
+        last += 10;
+    }}
+    assert_eq!(last, 13);
+
+    let mut runs = 0;
+    do_while_! { runs < 3 {
+        runs += 1;
+        if runs == 9 {
+            break;
+        }
+    } else {
+        runs += 10;
+    }}
+    assert_eq!(runs, 13);
+
+    let first = search_! { n in [1, 4, 6] {
+        if n % 2 == 0 {
+            break n;
+        }
+    }};
+    assert_eq!(first, Some(4));
+
+    let mut parsed = Vec::new();
+    let mut failure = None;
+    try_for_! { n in ["1", "x"].map(str::parse::<u32>) {
+        parsed.push(n);
+    } catch err {
+        failure = Some(err.to_string());
+    } else {
+        failure = None;
+    }}
+    assert_eq!(parsed, [1]);
+    assert!(failure.is_some());
+}
+
+#[test]
+fn test_generated_labels_and_try() {
+    let mut deferred = 0;
+    let mut outcome = "";
+    for_! { n in 0..10 until deferred == 3 with mut total = 0 {
+        total += n;
+    } defer {
+        deferred += 1;
+    } else {
+        outcome = "else";
+    } stopped {
+        assert_eq!(total, 3);
+        outcome = "stopped";
+    }}
+    assert_eq!(outcome, "stopped");
+
+    let mut numbers = Vec::new();
+    for_! { word in ["1", "x", "3"] try continue {
+        numbers.push(word.parse::<u32>().ok()?);
+        if numbers.len() == 9 {
+            break;
+        }
+    } else {
+        numbers.push(0);
+    }}
+    assert_eq!(numbers, [1, 3, 0]);
+}
@@ -0,0 +1,188 @@
+//! Model checks of the `interrupt` clause against every interleaving of the threads.
+//!
+//! The threads of `par_for_!` belong to rayon, which loom cannot drive, so its expansion is
+//! checked against a model of `ParallelIterator::any`, the only concurrency it relies on.
+//!
+//! Run with `RUSTFLAGS="--cfg for_else_loom" cargo test --release --test test_loom`.
+#![cfg(for_else_loom)]
+// loom threads do not need `Send`, so its cells are shared through a plain `Arc`
+#![allow(clippy::arc_with_non_send_sync)]
+
+use for_else::for_;
+use loom::cell::UnsafeCell;
+use loom::sync::atomic::{AtomicBool, Ordering};
+use loom::thread;
+use std::sync::Arc;
+
+#[derive(Debug, PartialEq)]
+enum Outcome {
+    Broke,
+    Completed,
+    Stopped,
+}
+
+#[test]
+fn test_interruption_publishes_prior_writes() {
+    loom::model(|| {
+        let flag = Arc::new(AtomicBool::new(false));
+        let reason = Arc::new(UnsafeCell::new(""));
+        let interrupter = thread::spawn({
+            let (flag, reason) = (flag.clone(), reason.clone());
+            move || {
+                reason.with_mut(|reason| unsafe { *reason = "shutdown" });
+                flag.store(true, Ordering::Release);
+            }
+        });
+
+        for_! { _ in 0..2 interrupt(flag.clone()) {
+        } else {
+        } stopped {
+            // the loop only stops once it sees the flag, after the reason was written
+            assert_eq!(reason.with(|reason| unsafe { *reason }), "shutdown");
+        }}
+
+        interrupter.join().unwrap();
+    });
+}
+
+#[test]
+fn test_break_suppresses_else_and_stopped() {
+    loom::model(|| {
+        let flag = Arc::new(AtomicBool::new(false));
+        let interrupter = thread::spawn({
+            let flag = flag.clone();
+            move || flag.store(true, Ordering::Release)
+        });
+
+        let mut outcomes = Vec::new();
+        let mut iterations = 0;
+        for_! { n in 0..3 interrupt(flag.clone()) {
+            iterations += 1;
+            if n == 1 {
+                outcomes.push(Outcome::Broke);
+                break;
+            }
+        } else {
+            outcomes.push(Outcome::Completed);
+        } stopped {
+            outcomes.push(Outcome::Stopped);
+        }}
+
+        interrupter.join().unwrap();
+        // a loop that was interrupted before reaching `n == 1` never broke, and one that broke
+        // neither completed nor stopped
+        match outcomes[..] {
+            [Outcome::Broke] => assert_eq!(iterations, 2),
+            [Outcome::Stopped] => assert!(iterations < 2),
+            _ => panic!("unexpected outcomes {:?}", outcomes),
+        }
+    });
+}
+
+#[test]
+fn test_break_in_one_thread_stops_the_other() {
+    loom::model(|| {
+        let found = Arc::new(AtomicBool::new(false));
+        let position = Arc::new(UnsafeCell::new(None));
+        let finder = thread::spawn({
+            let (found, position) = (found.clone(), position.clone());
+            move || {
+                let mut outcome = Outcome::Completed;
+                for_! { (index, item) in [3, 7].into_iter().enumerate() interrupt(found.clone()) {
+                    if item == 7 {
+                        position.with_mut(|position| unsafe { *position = Some(index) });
+                        found.store(true, Ordering::Release);
+                        outcome = Outcome::Broke;
+                        break;
+                    }
+                } else {
+                } stopped {
+                    outcome = Outcome::Stopped;
+                }}
+                outcome
+            }
+        });
+
+        let mut outcome = Outcome::Completed;
+        for_! { item in [1, 2] interrupt(found.clone()) {
+            assert_ne!(item, 7);
+        } else {
+        } stopped {
+            // the other loop broke, and what it found is visible here
+            assert_eq!(position.with(|position| unsafe { *position }), Some(1));
+            outcome = Outcome::Stopped;
+        }}
+
+        // the item is only in the first half, so that search always breaks
+        assert_eq!(finder.join().unwrap(), Outcome::Broke);
+        assert!(found.load(Ordering::Acquire));
+        assert_ne!(outcome, Outcome::Broke);
+    });
+}
+
+/// A model of `ParallelIterator::any` over two threads, each with half of `items`: a thread
+/// stops taking items once any thread found one, and the result is read after both joined.
+fn any(items: [u32; 4], predicate: impl Fn(u32) -> bool + Clone + 'static) -> bool {
+    let found = Arc::new(AtomicBool::new(false));
+    let halves = [[items[0], items[1]], [items[2], items[3]]].map(|half| {
+        let (found, predicate) = (found.clone(), predicate.clone());
+        thread::spawn(move || {
+            for item in half {
+                if found.load(Ordering::Relaxed) {
+                    return;
+                }
+                if predicate(item) {
+                    found.store(true, Ordering::Relaxed);
+                    return;
+                }
+            }
+        })
+    });
+    for half in halves {
+        half.join().unwrap();
+    }
+    found.load(Ordering::Relaxed)
+}
+
+#[test]
+fn test_par_break_is_seen_after_the_threads_join() {
+    loom::model(|| {
+        let position = Arc::new(UnsafeCell::new(None));
+        // the closure `par_for_!` hands to `any`: a `break` returns true, the end of the body
+        // or a `continue` returns false
+        let broke = any([1, 2, 7, 4], {
+            let position = position.clone();
+            move |item| {
+                if item == 2 {
+                    return false;
+                }
+                if item == 7 {
+                    position.with_mut(|position| unsafe { *position = Some(item) });
+                    return true;
+                }
+                false
+            }
+        });
+
+        // the `else` block is skipped, and what the breaking thread wrote is visible to it
+        assert!(broke);
+        assert_eq!(position.with(|position| unsafe { *position }), Some(7));
+    });
+}
+
+#[test]
+fn test_par_else_runs_when_no_thread_breaks() {
+    loom::model(|| {
+        let visited = Arc::new(loom::sync::atomic::AtomicUsize::new(0));
+        let broke = any([1, 2, 3, 4], {
+            let visited = visited.clone();
+            move |_| {
+                visited.fetch_add(1, Ordering::Relaxed);
+                false
+            }
+        });
+
+        assert!(!broke);
+        assert_eq!(visited.load(Ordering::Relaxed), 4);
+    });
+}
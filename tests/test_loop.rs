@@ -0,0 +1,90 @@
+use for_else::{loop_, LoopBudget};
+
+#[test]
+fn test_else_runs_when_limit_is_exhausted() {
+    let mut runs = 0;
+    let mut else_ran = false;
+    loop_! { limit 3 {
+        runs += 1;
+        if runs == 5 {
+            break;
+        }
+    } else {
+        else_ran = true;
+    }}
+
+    assert_eq!(runs, 3);
+    assert!(else_ran);
+}
+
+#[test]
+fn test_break_skips_else() {
+    let mut runs = 0;
+    let mut else_ran = false;
+    loop_! { limit 10 {
+        runs += 1;
+        if runs == 2 {
+            break;
+        }
+    } else {
+        else_ran = true;
+    }}
+
+    assert_eq!(runs, 2);
+    assert!(!else_ran);
+}
+
+#[test]
+fn test_labeled_break_from_nested_loop() {
+    let mut found = None;
+    loop_! { 'search: limit 4 {
+        for candidate in 0..10 {
+            if candidate * candidate == 49 {
+                found = Some(candidate);
+                break 'search;
+            }
+        }
+    } else {
+        found = Some(0);
+    }}
+
+    assert_eq!(found, Some(7));
+}
+
+#[test]
+fn test_zero_limit_and_shared_budget() {
+    let mut runs = 0;
+    loop_! { limit 0 {
+        runs += 1;
+        if runs > 1 {
+            break;
+        }
+    } else {
+        runs += 10;
+    }}
+
+    assert_eq!(runs, 10);
+
+    let budget = LoopBudget::new().iterations(5);
+    let mut first = 0;
+    loop_! { limit &budget {
+        first += 1;
+        if first == 3 {
+            break;
+        }
+    } else {
+        first += 100;
+    }}
+    let mut second = 0;
+    loop_! { limit &budget {
+        second += 1;
+        // the shared budget runs out first
+        if second == 3 {
+            break;
+        }
+    } else {
+        second += 100;
+    }}
+
+    assert_eq!((first, second), (3, 102));
+}
@@ -0,0 +1,204 @@
+use for_else::loop_else;
+
+#[test]
+fn test_for_form() {
+    let mut else_ran = false;
+    loop_else! { for x in 1..5, step 2 {
+        if x == 2 {
+            break;
+        }
+    } else {
+        else_ran = true;
+    }}
+
+    assert!(else_ran);
+}
+
+#[test]
+fn test_while_form() {
+    let mut n = 0;
+    let mut broke = true;
+    loop_else! { while n < 5 {
+        n += 1;
+        if n == 3 {
+            break;
+        }
+    } else {
+        broke = false;
+    }}
+
+    assert_eq!(n, 3);
+    assert!(broke);
+}
+
+#[test]
+fn test_while_let_form() {
+    let mut stack = vec![1, 3, 5];
+    let mut else_ran = false;
+    loop_else! { while let Some(n) = stack.pop() {
+        if n % 2 == 0 {
+            break;
+        }
+    } else {
+        else_ran = true;
+    }}
+
+    assert!(else_ran);
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_loop_max_form() {
+    let mut attempts = 0;
+    let mut gave_up = false;
+    loop_else! { loop max 3 {
+        attempts += 1;
+        if attempts > 3 {
+            break;
+        }
+    } else {
+        gave_up = true;
+    }}
+
+    assert_eq!(attempts, 3);
+    assert!(gave_up);
+}
+
+#[test]
+fn test_shared_clauses() {
+    let mut n = 0;
+    let mut outcome = "";
+    loop_else! { 'outer: while n < 10 until n == 4 {
+        n += 1;
+        for _ in 0..1 {
+            if n == 20 {
+                break 'outer;
+            }
+        }
+    } else {
+        outcome = "else";
+    } stopped {
+        outcome = "stopped";
+    }}
+
+    assert_eq!(outcome, "stopped");
+
+    let mut stack = vec![1, 2, 3];
+    let odd = loop_else! { collect while let Some(n) = stack.pop() {
+        if n % 2 == 1 {
+            emit n;
+        }
+    }};
+
+    assert_eq!(odd, [3, 1]);
+}
+
+#[test]
+fn test_collect_in_polling_loops() {
+    let mut queue = vec![Some(4), Some(2), None, Some(7)];
+    let drained = loop_else! { collect while !queue.is_empty() {
+        let Some(value) = queue.remove(0) else {
+            break;
+        };
+        emit value;
+    } else {
+        Vec::new()
+    }};
+
+    assert_eq!(drained, [4, 2]);
+    assert_eq!(queue, [Some(7)]);
+
+    let mut reading = 0;
+    let readings = loop_else! { collect loop max 3 {
+        reading += 5;
+        if reading > 100 {
+            break;
+        }
+        emit reading;
+    } else {
+        vec![-1]
+    }};
+
+    assert_eq!(readings, [-1]);
+
+    let mut lines = vec!["3", "x", "1"].into_iter();
+    let numbers: Vec<u32> = loop_else! { collect while let Some(line) = lines.next() try break {
+        emit line.parse::<u32>().ok()?;
+    } else {
+        Vec::new()
+    }};
+
+    assert_eq!(numbers, [3]);
+}
+
+#[test]
+fn test_cause_clauses() {
+    let drain = |mut queue: Vec<u32>, budget: u32| {
+        let mut spent = 0;
+        let mut outcome = "";
+        loop_else! { while open: !queue.is_empty() && in_budget: spent < budget {
+            spent += queue.pop().unwrap();
+        } else open {
+            outcome = "drained";
+        } else in_budget {
+            outcome = "over budget";
+        }}
+        outcome
+    };
+
+    assert_eq!(drain(vec![1, 2], 10), "drained");
+    assert_eq!(drain(vec![1, 2, 8, 9], 10), "over budget");
+
+    let mut polls = 0;
+    let mut stopped_by = "";
+    loop_else! { while ready: polls < 5 && polls % 7 != 3 && (polls < 10 || stopped_by == "ready") {
+        polls += 1;
+    } else ready {
+        stopped_by = "ready";
+    } else {
+        stopped_by = "other";
+    }}
+
+    assert_eq!((polls, stopped_by), (3, "other"));
+
+    let mut items = vec![3, 4, 5].into_iter();
+    let mut next = items.next();
+    loop_else! { while some: next.is_some() && small: next < Some(5) {
+        if next == Some(4) {
+            break;
+        }
+        next = items.next();
+    } else small {
+        panic!("the loop breaks before reaching 5");
+    }}
+
+    assert_eq!(next, Some(4));
+}
+
+#[test]
+fn test_cause_clauses_with_clauses_and_modes() {
+    let mut ticks = 0;
+    let mut outcome = "";
+    loop_else! { while alive: ticks < 10 until ticks == 4 {
+        ticks += 1;
+    } else alive {
+        outcome = "dead";
+    } stopped {
+        outcome = "stopped";
+    }}
+
+    assert_eq!(outcome, "stopped");
+
+    let mut readings = vec![7, 3, 12, 5].into_iter();
+    let mut reading = readings.next();
+    let below = loop_else! { collect while present: reading.is_some() && low: reading < Some(10) {
+        emit reading.unwrap();
+        reading = readings.next();
+    } else present {
+        vec![0]
+    } else low {
+        vec![reading.unwrap()]
+    }};
+
+    assert_eq!(below, [12]);
+}
@@ -0,0 +1,75 @@
+use for_else::{for_, while_let_};
+
+#[test]
+fn test_for_keyword_is_accepted() {
+    let mut seen = Vec::new();
+    let mut else_ran = false;
+    for_! { for n in 0..3 {
+        if n == 9 {
+            break;
+        }
+        seen.push(n);
+    } else {
+        else_ran = true;
+    }}
+
+    assert_eq!(seen, [0, 1, 2]);
+    assert!(else_ran);
+}
+
+#[test]
+fn test_for_keyword_after_label_and_collect() {
+    let mut found = None;
+    for_! { 'rows: for row in [[1, 2], [3, 4]] {
+        for cell in row {
+            if cell == 3 {
+                found = Some(cell);
+                break 'rows;
+            }
+        }
+    } else {
+        found = Some(0);
+    }}
+
+    assert_eq!(found, Some(3));
+
+    let doubled = for_! { collect for n in 1..=3 {
+        emit n * 2;
+    }};
+
+    assert_eq!(doubled, [2, 4, 6]);
+}
+
+#[test]
+fn test_while_let_keywords_are_accepted() {
+    let mut stack = vec![1, 2, 3];
+    let mut seen = Vec::new();
+    let mut else_ran = false;
+    while_let_! { while let Some(n) = stack.pop() {
+        if n == 9 {
+            break;
+        }
+        seen.push(n);
+    } else {
+        else_ran = true;
+    }}
+
+    assert_eq!(seen, [3, 2, 1]);
+    assert!(else_ran);
+}
+
+#[test]
+fn test_break_skips_else() {
+    let mut queue = vec![4, 5, 6];
+    let mut else_ran = false;
+    while_let_! { let Some(n) = queue.pop() {
+        if n == 5 {
+            break;
+        }
+    } else {
+        else_ran = true;
+    }}
+
+    assert!(!else_ran);
+    assert_eq!(queue, [4]);
+}
@@ -0,0 +1,55 @@
+use for_else::Looped;
+use std::cell::Cell;
+
+#[test]
+fn test_looped_runs_else_without_break() {
+    let else_ran = Cell::new(false);
+    {
+        let _guard = Looped::new(|| else_ran.set(true));
+        for _ in 0..3 {}
+    }
+
+    assert!(else_ran.get());
+}
+
+#[test]
+fn test_looped_skips_else_after_break() {
+    let else_ran = Cell::new(false);
+    {
+        let mut guard = Looped::new(|| else_ran.set(true));
+        for n in 0..3 {
+            if n == 1 {
+                guard.broke();
+                break;
+            }
+        }
+        assert!(guard.has_broken());
+    }
+
+    assert!(!else_ran.get());
+}
+
+#[test]
+fn test_looped_runs_else_on_empty_loop() {
+    let else_ran = Cell::new(false);
+    let guard = Looped::new(|| else_ran.set(true));
+    for _ in std::iter::empty::<u32>() {}
+    assert!(!guard.has_broken());
+    drop(guard);
+
+    assert!(else_ran.get());
+}
+
+#[test]
+fn test_looped_skips_else_when_unwinding() {
+    let else_ran = Cell::new(false);
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let _guard = Looped::new(|| else_ran.set(true));
+        for n in 0..3 {
+            assert!(n < 1, "failed on {}", n);
+        }
+    }));
+
+    assert!(result.is_err());
+    assert!(!else_ran.get());
+}
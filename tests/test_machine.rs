@@ -0,0 +1,81 @@
+use for_else::machine_loop_;
+
+#[derive(Debug, PartialEq)]
+enum Outcome {
+    Done(u32),
+    Failed,
+}
+
+#[test]
+fn test_machine_reaches_outcome() {
+    let result = machine_loop_! { n = 1, max 100 {
+        if n > 50 {
+            break Outcome::Done(n);
+        }
+        n * 2
+    } on Outcome::Done(n) => {
+        Some(n)
+    } on Outcome::Failed => {
+        None
+    } else {
+        None
+    }};
+
+    assert_eq!(result, Some(64));
+}
+
+#[test]
+fn test_machine_dispatches_to_matching_handler() {
+    let mut handled = Vec::new();
+    machine_loop_! { n = 10, max 100 {
+        match n {
+            0 => break Outcome::Failed,
+            n => n - 5,
+        }
+    } on Outcome::Done(_) => {
+        handled.push("done");
+    } on Outcome::Failed => {
+        handled.push("failed");
+    } else {
+        handled.push("else");
+    }}
+
+    assert_eq!(handled, ["failed"]);
+}
+
+#[test]
+fn test_machine_hits_bound() {
+    let mut steps = 0;
+    let final_state = machine_loop_! { n = 0u32, max 5 {
+        steps += 1;
+        if n == 100 {
+            break;
+        }
+        n + 1
+    } on () => {
+        None
+    } else {
+        Some(n)
+    }};
+
+    assert_eq!(steps, 5);
+    assert_eq!(final_state, Some(5));
+}
+
+#[test]
+fn test_machine_labeled_break_leaves_outer_loop() {
+    let mut rounds = 0;
+    'outer: for _ in 0..3 {
+        rounds += 1;
+        machine_loop_! { n = 0, max 10 {
+            if n == 2 {
+                break 'outer;
+            }
+            n + 1
+        } on () => {} else {
+            panic!("the outer loop was left");
+        }}
+    }
+
+    assert_eq!(rounds, 1);
+}
@@ -34,6 +34,7 @@ fn test_else_block() {
 }
 
 #[test]
+#[allow(clippy::single_match)]
 fn test_match_arm_statemnt() {
     let mut flag = true;
     for_! { i in 0..10 {
@@ -107,3 +108,720 @@ fn test_negative() {
 
     assert!(flag);
 }
+
+#[test]
+fn test_must_break() {
+    let mut found = None;
+    for_! { i in 0..10 {
+        if i == 5 {
+            found = Some(i);
+            break;
+        }
+    } must_break }
+
+    assert_eq!(found, Some(5));
+}
+
+#[test]
+#[should_panic(expected = "finished without a `break`")]
+fn test_must_break_without_break() {
+    for_! { i in 0..10 {
+        if i == 11 {
+            break;
+        }
+    } must_break }
+}
+
+#[test]
+fn test_must_break_panic_names_the_loop() {
+    let result = std::panic::catch_unwind(|| {
+        for_! { i in 0..3 {
+            if i == 3 {
+                break;
+            }
+        } must_break }
+    });
+
+    // the message is a `&str` when the compiler formats it at compile time
+    let payload = result.unwrap_err();
+    let message = match payload.downcast_ref::<&str>() {
+        Some(message) => message.to_string(),
+        None => payload.downcast_ref::<String>().unwrap().clone(),
+    };
+    assert!(
+        message.contains("declared `must_break` at tests/test_macros.rs:"),
+        "{}",
+        message
+    );
+}
+
+#[test]
+#[cfg(feature = "unstable-unchecked")]
+fn test_unsafe_must_break() {
+    let mut found = None;
+    for_! { i in 0..10 {
+        if i == 5 {
+            found = Some(i);
+            break;
+        }
+    } unsafe must_break }
+
+    assert_eq!(found, Some(5));
+}
+
+#[test]
+fn test_skip_take() {
+    let xs = vec![1, 2, 3, 4, 5, 6];
+    let mut seen = Vec::new();
+    let mut flag = false;
+    for_! { x in xs, skip 1, take 3 {
+        // items past the third are not taken
+        if x > 4 {
+            break;
+        }
+        seen.push(x);
+    } else {
+        flag = true;
+    }}
+
+    assert!(flag);
+    assert_eq!(seen, [2, 3, 4]);
+}
+
+#[test]
+fn test_take_then_skip() {
+    let n = 2;
+    let mut flag = true;
+    for_! { i in 0..10, take n, skip n {
+        if i < 2 {
+            break;
+        }
+    } else {
+        flag = false;
+    }}
+
+    assert!(!flag);
+}
+
+#[test]
+fn test_step() {
+    let mut seen = Vec::new();
+    let mut flag = false;
+    for_! { i in 0..10, step 4 {
+        if i % 4 != 0 {
+            break;
+        }
+        seen.push(i);
+    } else {
+        flag = true;
+    }}
+
+    assert!(flag);
+    assert_eq!(seen, [0, 4, 8]);
+}
+
+#[test]
+fn test_skip_step() {
+    let xs = [1, 2, 3, 4, 5, 6, 7];
+    let mut flag = true;
+    for_! { &x in &xs, skip 1, step 3 {
+        if x == 5 {
+            break;
+        }
+    } else {
+        flag = false;
+    }}
+
+    assert!(flag);
+}
+
+#[test]
+fn test_skipped() {
+    let mut outcome = "";
+    for_! { i in 0..10 {
+        if i >= 0 {
+            continue;
+        }
+    } else {
+        outcome = "else";
+    } skipped {
+        outcome = "skipped";
+    }}
+
+    assert_eq!(outcome, "skipped");
+}
+
+#[test]
+fn test_skipped_some_completed() {
+    let mut outcome = "";
+    for_! { i in 0..10 {
+        if i % 2 == 0 {
+            continue;
+        }
+    } else {
+        outcome = "else";
+    } skipped {
+        outcome = "skipped";
+    }}
+
+    assert_eq!(outcome, "else");
+}
+
+#[test]
+fn test_skipped_empty() {
+    let mut outcome = "";
+    for_! { _i in Vec::<u32>::new() {
+        continue;
+    } else {
+        outcome = "else";
+    } skipped {
+        outcome = "skipped";
+    }}
+
+    assert_eq!(outcome, "else");
+}
+
+#[test]
+fn test_collect() {
+    let squares = for_! { collect i in 0..6 {
+        if i % 2 == 0 {
+            emit i * i;
+        }
+    }};
+
+    assert_eq!(squares, [0, 4, 16]);
+}
+
+#[test]
+fn test_collect_break() {
+    let values = for_! { collect x in [1, 2, -1, 3] {
+        if x < 0 {
+            break;
+        }
+        emit x;
+    } else {
+        vec![0]
+    }};
+
+    assert_eq!(values, [1, 2]);
+}
+
+#[test]
+fn test_collect_else() {
+    let values = for_! { collect x in [1, 2, 3] {
+        if x < 0 {
+            break;
+        }
+        emit x;
+    } else {
+        vec![0]
+    }};
+
+    assert_eq!(values, [0]);
+}
+
+#[test]
+fn test_collect_nested_and_tuples() {
+    let emit = 10;
+    let values: Vec<(i32, i32)> = for_! { collect x in [1, 2] {
+        match x {
+            1 => {
+                emit (x, emit + 1);
+            }
+            _ => {
+                if x > 0 {
+                    emit (x, 0);
+                }
+            }
+        }
+    }};
+
+    assert_eq!(values, [(1, 11), (2, 0)]);
+}
+
+#[test]
+fn test_until_stopped() {
+    let mut seen = Vec::new();
+    let mut outcome = "";
+    for_! { i in 0..10 until seen.len() == 3 {
+        seen.push(i);
+    } else {
+        outcome = "else";
+    } stopped {
+        outcome = "stopped";
+    }}
+
+    assert_eq!(outcome, "stopped");
+    assert_eq!(seen, [0, 1, 2]);
+}
+
+#[test]
+fn test_until_exhausted() {
+    let mut outcome = "";
+    let mut count = 0;
+    for_! { _i in 0..10, take 3 until count == 5 {
+        count += 1;
+    } else {
+        outcome = "else";
+    } stopped {
+        outcome = "stopped";
+    }}
+
+    assert_eq!(outcome, "else");
+}
+
+#[test]
+fn test_until_does_not_consume() {
+    let mut items = vec![1, 2, 3].into_iter();
+    let mut count = 0;
+    for_! { _x in items.by_ref() until count == 1 {
+        count += 1;
+    } else {
+        panic!("the loop was stopped");
+    }}
+
+    assert_eq!(items.next(), Some(2));
+}
+
+#[test]
+fn test_labeled_break_from_nested_loop() {
+    let mut found = None;
+    for_! { 'rows: row in [[1, 2], [3, 4]] {
+        for cell in row {
+            if cell == 3 {
+                found = Some(row);
+                break 'rows;
+            }
+        }
+    } else {
+        panic!("the loop was broken");
+    }}
+
+    assert_eq!(found, Some([3, 4]));
+}
+
+#[test]
+fn test_unlabeled_break_in_nested_loop_runs_else() {
+    let mut else_ran = false;
+    #[allow(deprecated)]
+    {
+        for_! { x in 0..3 {
+            for y in 0..3 {
+                if x == y {
+                    break;
+                }
+            }
+        } else {
+            else_ran = true;
+        }}
+    }
+
+    assert!(else_ran);
+}
+
+#[test]
+fn test_labeled_nested_loop_keeps_its_breaks() {
+    let mut inner_breaks = 0;
+    for_! { 'outer: x in 0..3 {
+        'inner: for y in 0..3 {
+            if y > x {
+                inner_breaks += 1;
+                break 'inner;
+            }
+        }
+        if x == 5 {
+            break 'outer;
+        }
+    } else {
+        inner_breaks += 10;
+    }}
+
+    assert_eq!(inner_breaks, 12);
+}
+
+#[test]
+fn test_break_after_nested_for_else() {
+    let mut found = None;
+    for_! { row in [[1, 2], [3, 4]] {
+        for_! { cell in row {
+            if cell == 3 {
+                break;
+            }
+        } else {
+            continue;
+        }}
+        found = Some(row);
+        break;
+    } else {
+        panic!("the outer loop was broken");
+    }}
+
+    assert_eq!(found, Some([3, 4]));
+}
+
+#[test]
+fn test_binding_pattern() {
+    let mut found = None;
+    for_! { pair @ (n, _) in [(3, 'a'), (14, 'b'), (25, 'c')] {
+        if n > 10 {
+            found = Some(pair);
+            break;
+        }
+    } else {
+        panic!("the loop was broken");
+    }}
+
+    assert_eq!(found, Some((14, 'b')));
+}
+
+#[test]
+fn test_rest_pattern() {
+    let mut ends = Vec::new();
+    for_! { [first, .., last] in [[1, 2, 3], [4, 5, 6]] {
+        ends.push((first, last));
+    } else {
+        ends.push((0, 0));
+    }}
+
+    assert_eq!(ends, [(1, 3), (4, 6), (0, 0)]);
+}
+
+#[test]
+fn test_nested_pattern() {
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    let points = [Point { x: 1, y: 2 }, Point { x: 3, y: 4 }];
+    let mut sum = 0;
+    for_! { (i, &Point { x, y: ref y_ref }) in points.iter().enumerate() {
+        sum += i as i32 + x + *y_ref;
+    } else {
+        sum *= 10;
+    }}
+
+    assert_eq!(sum, 110);
+}
+
+#[test]
+fn test_or_pattern() {
+    let results: [Result<i32, i32>; 2] = [Ok(1), Err(2)];
+    let mut sum = 0;
+    for_! { Ok(n) | Err(n) in results {
+        sum += n;
+    } else {
+        sum *= 10;
+    }}
+
+    assert_eq!(sum, 30);
+}
+
+#[test]
+fn test_fallback_source() {
+    let mut seen = Vec::new();
+    for_! { x in [1, 2] {
+        seen.push(x);
+        if x == 4 {
+            break;
+        }
+    } else continue with [3, 4, 5]; else {
+        panic!("the loop was broken");
+    }}
+
+    assert_eq!(seen, [1, 2, 3, 4]);
+}
+
+#[test]
+fn test_fallback_not_evaluated_after_break() {
+    let mut fallback_evaluated = false;
+    for_! { x in [1, 2] {
+        if x == 1 {
+            break;
+        }
+    } else continue with {
+        fallback_evaluated = true;
+        vec![3]
+    }; else {
+        panic!("the loop was broken");
+    }}
+
+    assert!(!fallback_evaluated);
+}
+
+#[test]
+fn test_fallback_exhausted() {
+    let mut count = 0;
+    let mut else_ran = false;
+    for_! { _x in 0..10, take 2 {
+        count += 1;
+    } else continue with 0..10; else {
+        else_ran = true;
+    }}
+
+    assert_eq!(count, 4);
+    assert!(else_ran);
+}
+
+#[test]
+fn test_fallback_with_until() {
+    let mut seen = Vec::new();
+    let mut outcome = "";
+    for_! { x in [1, 2] until seen.len() == 3 {
+        seen.push(x);
+    } else continue with [3, 4, 5]; else {
+        outcome = "else";
+    } stopped {
+        outcome = "stopped";
+    }}
+
+    assert_eq!(seen, [1, 2, 3]);
+    assert_eq!(outcome, "stopped");
+}
+
+#[test]
+fn test_refutable_pattern_skips_items() {
+    let mut values = Vec::new();
+    for_! { Some(x) in [Some(1), None, Some(3)] {
+        values.push(x);
+    } else {
+        values.push(0);
+    }}
+
+    assert_eq!(values, [1, 3, 0]);
+}
+
+#[test]
+fn test_refutable_pattern_with_break() {
+    let results: [Result<i32, &str>; 3] = [Err("a"), Ok(2), Ok(5)];
+    let mut found = None;
+    for_! { Ok(n) in results {
+        if n > 1 {
+            found = Some(n);
+            break;
+        }
+    } else {
+        panic!("the loop was broken");
+    }}
+
+    assert_eq!(found, Some(2));
+}
+
+#[test]
+fn test_refutable_pattern_is_not_skipped_iteration() {
+    let mut outcome = "";
+    for_! { Some(_x) in [None, Some(1), None] {
+    } else {
+        outcome = "else";
+    } skipped {
+        outcome = "skipped";
+    }}
+
+    assert_eq!(outcome, "else");
+}
+
+#[test]
+fn test_refutable_pattern_with_until() {
+    let mut sum = 0;
+    for_! { Some(x) in [Some(1), None, Some(2), Some(3)] until sum >= 3 {
+        sum += x;
+    } else {
+        panic!("the loop was stopped");
+    }}
+
+    assert_eq!(sum, 3);
+}
+
+#[test]
+fn test_cooldown_between_iterations() {
+    use std::time::{Duration, Instant};
+
+    let start = Instant::now();
+    let mut iterations = 0;
+    for_! { _x in 0..3 cooldown(Duration::from_millis(20)) {
+        iterations += 1;
+    } else {
+        iterations += 10;
+    }}
+
+    let elapsed = start.elapsed();
+    assert_eq!(iterations, 13);
+    // two sleeps between three iterations
+    assert!(elapsed >= Duration::from_millis(40));
+}
+
+#[test]
+fn test_cooldown_no_sleep_for_single_iteration() {
+    use std::time::{Duration, Instant};
+
+    let start = Instant::now();
+    for_! { x in [1] cooldown(Duration::from_secs(5)) {
+        assert_eq!(x, 1);
+    } else {}}
+
+    assert!(start.elapsed() < Duration::from_secs(1));
+}
+
+#[test]
+fn test_cooldown_with_until() {
+    use std::time::Duration;
+
+    let mut seen = Vec::new();
+    for_! { x in 0..10 until seen.len() == 2 cooldown(Duration::from_millis(1)) {
+        seen.push(x);
+    } else {
+        panic!("the loop was stopped");
+    }}
+
+    assert_eq!(seen, [0, 1]);
+}
+
+#[test]
+fn test_with_state() {
+    let mut result = None;
+    for_! { x in [4, 9, 2] with mut best = 0 {
+        if x > best {
+            best = x;
+        }
+    } else {
+        result = Some(best);
+    }}
+
+    assert_eq!(result, Some(9));
+}
+
+#[test]
+fn test_with_state_is_scoped() {
+    let count = "outer";
+    let mut seen = 0;
+    for_! { _x in 0..3 with mut count: u32 = 0, step = 2 {
+        count += step;
+    } else {
+        seen = count;
+    }}
+
+    assert_eq!(seen, 6);
+    assert_eq!(count, "outer");
+}
+
+#[test]
+fn test_with_state_in_collect_mode() {
+    let values = for_! { collect x in 1..=4 with mut total = 0 {
+        total += x;
+        emit total;
+    }};
+
+    assert_eq!(values, [1, 3, 6, 10]);
+}
+
+#[test]
+fn test_with_state_and_until() {
+    let mut outcome = 0;
+    for_! { x in 0..100 with mut taken = 0 until taken == 3 {
+        taken += 1;
+        let _ = x;
+    } else {
+    } stopped {
+        outcome = taken;
+    }}
+
+    assert_eq!(outcome, 3);
+}
+
+#[test]
+fn test_defer_runs_after_continue() {
+    let mut deferred = Vec::new();
+    let mut flag = false;
+    for_! { x in 0..4 {
+        if x % 2 == 0 {
+            continue;
+        }
+    } defer {
+        deferred.push(x);
+    } else {
+        flag = true;
+    }}
+
+    assert!(flag);
+    assert_eq!(deferred, [0, 1, 2, 3]);
+}
+
+#[test]
+fn test_defer_skipped_on_break() {
+    let mut deferred = Vec::new();
+    let mut flag = true;
+    for_! { x in 0..10 {
+        match x {
+            1 => continue,
+            3 => break,
+            _ => {}
+        }
+    } defer {
+        deferred.push(x);
+    } else {
+        flag = false;
+    }}
+
+    assert!(flag);
+    assert_eq!(deferred, [0, 1, 2]);
+}
+
+#[test]
+fn test_defer_with_nested_loops_and_labels() {
+    let mut deferred = 0;
+    let mut inner_runs = 0;
+    for_! { 'outer: x in 0..3 {
+        for y in 0..3 {
+            if y == 1 {
+                continue;
+            }
+            if x == 1 {
+                continue 'outer;
+            }
+            inner_runs += 1;
+        }
+        let Some(_) = (x != 0).then_some(x) else {
+            continue;
+        };
+    } defer {
+        deferred += 1;
+    } else {
+    }}
+
+    assert_eq!(deferred, 3);
+    assert_eq!(inner_runs, 4);
+}
+
+#[test]
+fn test_defer_with_skipped() {
+    let mut deferred = 0;
+    let mut outcome = "";
+    for_! { _x in 0..2 {
+        continue;
+    } defer {
+        deferred += 1;
+    } else {
+        outcome = "else";
+    } skipped {
+        outcome = "skipped";
+    }}
+
+    assert_eq!(outcome, "skipped");
+    assert_eq!(deferred, 2);
+}
+
+#[test]
+fn test_defer_in_collect_mode() {
+    let mut deferred = 0;
+    let values = for_! { collect x in 0..5 {
+        if x == 3 {
+            break;
+        }
+        emit x;
+    } defer {
+        deferred += 1;
+    }};
+
+    assert_eq!(values, [0, 1, 2]);
+    assert_eq!(deferred, 3);
+}
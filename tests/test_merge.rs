@@ -0,0 +1,67 @@
+use for_else::{merge_for_, Merge};
+
+#[test]
+fn test_merge_finds_common_item() {
+    let mut steps = Vec::new();
+    let mut found = None;
+    merge_for_! { step in [1, 4, 7, 9], [2, 7, 8] {
+        steps.push(step);
+        if let Merge::Both(a, _) = step {
+            found = Some(a);
+            break;
+        }
+    } else {
+        panic!("7 is in both");
+    }}
+
+    assert_eq!(found, Some(7));
+    assert_eq!(
+        steps,
+        [
+            Merge::Left(1),
+            Merge::Right(2),
+            Merge::Left(4),
+            Merge::Both(7, 7)
+        ]
+    );
+}
+
+#[test]
+fn test_merge_disjoint() {
+    let mut else_ran = false;
+    merge_for_! { step in vec![1, 3, 5], vec![2, 4, 6] {
+        if let Merge::Both(..) = step {
+            break;
+        }
+    } else {
+        else_ran = true;
+    }}
+
+    assert!(else_ran);
+}
+
+#[test]
+fn test_merge_stops_when_either_side_runs_out() {
+    let mut steps = 0;
+    let mut else_ran = false;
+    merge_for_! { _step in 1..=2, 10..20 {
+        steps += 1;
+    } else {
+        else_ran = true;
+    }}
+
+    assert_eq!(steps, 2);
+    assert!(else_ran);
+}
+
+#[test]
+fn test_merge_empty_side() {
+    let mut else_ran = false;
+    merge_for_! { _step in Vec::<i32>::new(), [1, 2] {
+        panic!("the left side is empty");
+    } else {
+        else_ran = true;
+    }}
+
+    assert!(else_ran);
+}
@@ -0,0 +1,106 @@
+#![cfg(feature = "metrics")]
+
+use for_else::{for_, LoopMetrics};
+use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+use metrics_util::CompositeKey;
+
+fn search(items: &[u32], target: u32) {
+    for_! { item in items observe(LoopMetrics::new("search")) {
+        if *item == target {
+            break;
+        }
+    } else {
+    }}
+}
+
+/// The outcome counters recorded since the last call, as `(loop, outcome, count)`.
+fn outcomes(recorder: &DebuggingRecorder) -> Vec<(String, String, u64)> {
+    let label = |key: &CompositeKey, name: &str| {
+        key.key()
+            .labels()
+            .find(|label| label.key() == name)
+            .unwrap()
+            .value()
+            .to_string()
+    };
+    let mut outcomes = recorder
+        .snapshotter()
+        .snapshot()
+        .into_vec()
+        .into_iter()
+        .filter(|(key, ..)| key.key().name() == LoopMetrics::COUNTER)
+        .map(|(key, _, _, value)| {
+            let DebugValue::Counter(count) = value else {
+                panic!("not a counter");
+            };
+            (label(&key, "loop"), label(&key, "outcome"), count)
+        })
+        .collect::<Vec<_>>();
+    outcomes.sort();
+    outcomes
+}
+
+fn outcome(name: &str, outcome: &str, count: u64) -> (String, String, u64) {
+    (name.to_string(), outcome.to_string(), count)
+}
+
+#[test]
+fn test_counts_outcomes() {
+    let recorder = DebuggingRecorder::new();
+    metrics::with_local_recorder(&recorder, || {
+        search(&[1, 2, 3], 2);
+        search(&[1, 2, 3], 4);
+        search(&[1, 2, 3], 3);
+        search(&[], 1);
+    });
+
+    assert_eq!(
+        outcomes(&recorder),
+        [
+            outcome("search", "broke", 2),
+            outcome("search", "completed", 1),
+            outcome("search", "empty", 1),
+        ]
+    );
+}
+
+#[test]
+fn test_names_label_the_counters() {
+    let recorder = DebuggingRecorder::new();
+    metrics::with_local_recorder(&recorder, || {
+        let name = format!("retry-{}", 1);
+        for_! { _attempt in 0..3 observe(LoopMetrics::new(name)) {
+        } else {
+        }}
+        search(&[5], 5);
+    });
+
+    assert_eq!(
+        outcomes(&recorder),
+        [
+            outcome("retry-1", "completed", 1),
+            outcome("search", "broke", 1)
+        ]
+    );
+}
+
+#[test]
+fn test_location_label() {
+    let recorder = DebuggingRecorder::new();
+    metrics::with_local_recorder(&recorder, || search(&[], 1));
+
+    let snapshot = recorder.snapshotter().snapshot().into_vec();
+    let (key, ..) = &snapshot[0];
+    let location = key
+        .key()
+        .labels()
+        .find(|label| label.key() == "location")
+        .unwrap();
+    // the observer is created in the `observe` clause of the loop in `search`
+    assert_eq!(location.value(), "tests/test_metrics.rs:8:35");
+}
+
+#[test]
+fn test_without_recorder() {
+    search(&[1], 1);
+}
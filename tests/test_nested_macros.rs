@@ -0,0 +1,119 @@
+use for_else::{for_, loop_else};
+
+#[test]
+fn test_labeled_break_from_nested_for_skips_outer_else() {
+    let mut found = None;
+    let mut outer_else = false;
+    let mut inner_else = 0;
+    for_! { 'rows: row in [[1, 2], [3, 4]] {
+        for_! { cell in row {
+            if cell == 3 {
+                found = Some(cell);
+                break 'rows;
+            }
+        } else {
+            inner_else += 1;
+        }}
+    } else {
+        outer_else = true;
+    }}
+
+    assert_eq!(found, Some(3));
+    assert_eq!(inner_else, 1);
+    assert!(!outer_else);
+}
+
+#[test]
+fn test_inner_breaks_do_not_skip_outer_else() {
+    let mut outer_else = false;
+    let mut cells = Vec::new();
+    for_! { row in [[1, 2], [3, 4]] {
+        for_! { 'cells: cell in row {
+            if cell % 2 == 0 {
+                break 'cells;
+            }
+            cells.push(cell);
+            if cell > 5 {
+                break;
+            }
+        } else {
+            panic!("every row has an even cell");
+        }}
+        if row[0] > 5 {
+            break;
+        }
+    } else {
+        outer_else = true;
+    }}
+
+    assert_eq!(cells, [1, 3]);
+    assert!(outer_else);
+}
+
+#[test]
+fn test_break_through_several_macros_and_with_value() {
+    let mut outer_else = false;
+    let mut middle_else = false;
+    let mut misses = 0;
+    for_! { 'outer: a in 0..3 {
+        loop_else! { while let Some(b) = (a..3).next() {
+            for_! { c in 0..3 {
+                if a + b + c == 3 {
+                    break 'outer;
+                }
+            } else {
+                misses += 1;
+            }}
+            break;
+        } else {
+            middle_else = true;
+        }}
+    } else {
+        outer_else = true;
+    }}
+
+    assert!(!outer_else);
+    assert!(!middle_else);
+    assert_eq!(misses, 1);
+
+    let pair = for_! { 'outer: a in 1..5 {
+        for_! { b in 1..5 {
+            if a * b == 6 {
+                break 'outer (a, b);
+            }
+        } else {
+            misses += 1;
+        }}
+    } else {
+        (0, 0)
+    }};
+
+    assert_eq!(pair, (2, 3));
+    assert_eq!(misses, 2);
+}
+
+/// A macro of the user's crate that wraps a loop around its body.
+macro_rules! twice {
+    ($body:block) => {
+        for _ in 0..2 $body
+    };
+}
+
+#[test]
+fn test_labeled_break_in_user_macro() {
+    let mut outer_else = false;
+    let mut runs = 0;
+    for_! { 'outer: n in 0..3 {
+        twice!({
+            runs += 1;
+            if n == 1 {
+                break 'outer;
+            }
+        });
+    } else {
+        outer_else = true;
+    }}
+
+    assert_eq!(runs, 3);
+    assert!(!outer_else);
+}
@@ -0,0 +1,268 @@
+//! The macros in modules without the prelude, and in modules that shadow the names of the
+//! prelude, where every path of the expansion has to be fully qualified.
+
+#[no_implicit_prelude]
+mod no_prelude {
+    use ::core::assert_eq;
+    use ::core::cmp::Ord;
+    use ::core::option::Option::{None, Some};
+    use ::for_else::{bisect_loop_, chain_for_, for_, loop_else, machine_loop_, worklist_for_};
+    use ::std::vec;
+
+    #[::core::prelude::v1::test]
+    fn test_for_clauses_and_modes() {
+        let mut found = None;
+        for_! { 'rows: row in [[1, 2], [3, 4]] {
+            for x in row {
+                if x == 3 {
+                    found = Some(x);
+                    break 'rows;
+                }
+            }
+        } else {
+            found = Some(0);
+        }}
+        assert_eq!(found, Some(3));
+
+        let squares = for_! { collect i in 0..6, step 2 {
+            emit i * i;
+        }};
+        assert_eq!(squares, [0, 4, 16]);
+
+        let mut total = 0;
+        for_! { fold n in 1..10 with mut seen = 0 {
+            seen += 1;
+            total += n;
+            if seen == 3 {
+                break;
+            }
+        } else {
+            total = 0;
+        }}
+        assert_eq!(total, 6);
+
+        let mut seen = 0;
+        let mut stopped = false;
+        for_! { _ in 1..10 until seen == 3 {
+            seen += 1;
+        } else {
+            seen = 0;
+        } stopped {
+            stopped = true;
+        }}
+        assert_eq!((seen, stopped), (3, true));
+
+        let mut skipped = 0;
+        for_! { reading in [None, None::<u32>] {
+            let Some(n) = reading else {
+                continue;
+            };
+            if n > 5 {
+                break;
+            }
+        } else {
+        } skipped {
+            skipped += 1;
+        }}
+        assert_eq!(skipped, 1);
+
+        let mut parsed = 0;
+        for_! { word in ["1", "x", "3"] try continue {
+            parsed += word.parse::<u32>().ok()?;
+        } else {
+            parsed += 100;
+        }}
+        assert_eq!(parsed, 104);
+
+        let mut deferred = 0;
+        for_! { n in 1..=3 {
+            if n == 2 {
+                break;
+            }
+        } defer {
+            deferred += 1;
+        } else {
+            deferred = 100;
+        }}
+        assert_eq!(deferred, 1);
+
+        let mut fallback = None;
+        for_! { n in [1, 3] {
+            if n % 2 == 0 {
+                fallback = Some(n);
+                break;
+            }
+        } else continue with [5, 6]; else {
+            fallback = Some(0);
+        }}
+        assert_eq!(fallback, Some(6));
+    }
+
+    #[::core::prelude::v1::test]
+    fn test_loop_else_forms() {
+        let mut stack = vec![1, 2, 3];
+        let odd = loop_else! { collect while let Some(n) = stack.pop() {
+            if n % 2 == 1 {
+                emit n;
+            }
+        }};
+        assert_eq!(odd, [3, 1]);
+
+        let mut attempts = 0;
+        let mut gave_up = false;
+        loop_else! { loop max 3 {
+            attempts += 1;
+            if attempts == 5 {
+                break;
+            }
+        } else {
+            gave_up = true;
+        }}
+        assert_eq!((attempts, gave_up), (3, true));
+
+        let mut polls = 0;
+        let mut outcome = "";
+        loop_else! { while ready: polls < 5 && in_time: polls < 2 {
+            polls += 1;
+            if polls == 10 {
+                break;
+            }
+        } else ready {
+            outcome = "ready";
+        } else in_time {
+            outcome = "timed out";
+        }}
+        assert_eq!(outcome, "timed out");
+    }
+
+    #[::core::prelude::v1::test]
+    fn test_other_macros() {
+        let versions = [1, 4, 9, 16, 25];
+        let mut index = None;
+        bisect_loop_! { i in 0..versions.len() {
+            if versions[i] == 9 {
+                index = Some(i);
+                break;
+            }
+            versions[i].cmp(&9)
+        } else {
+        }}
+        assert_eq!(index, Some(2));
+
+        let mut sources = vec![];
+        chain_for_! { value in Local([1]), Remote([2]) {
+            match value {
+                Local(n) | Remote(n) if n > 5 => break,
+                Local(_) => sources.push("local"),
+                Remote(_) => sources.push("remote"),
+            }
+        } else {
+            sources.push("none");
+        }}
+        assert_eq!(sources, ["local", "remote", "none"]);
+
+        let mut visited = vec![];
+        worklist_for_! { n in queue = [1] {
+            visited.push(n);
+            if n < 4 {
+                queue.push_back(n * 2);
+            }
+        } else {
+            visited.push(0);
+        }}
+        assert_eq!(visited, [1, 2, 4, 0]);
+
+        let steps = machine_loop_! { count = 0, max 10 {
+            if count == 3 {
+                break count;
+            }
+            count + 1
+        } on steps => {
+            steps
+        } else {
+            0
+        }};
+        assert_eq!(steps, 3);
+    }
+}
+
+/// Items named like those of the prelude, which the expansion must not pick up.
+mod shadowed {
+    #![allow(dead_code, deprecated, non_camel_case_types)]
+
+    use for_else::{for_, loop_else};
+
+    struct Option;
+    struct Some;
+    struct None;
+    struct Result;
+    struct Ok;
+    struct Err;
+    struct Vec;
+    struct Iterator;
+    struct IntoIterator;
+    struct Into;
+    struct From;
+    struct Default;
+    struct Clone;
+    struct Drop;
+    struct Fn;
+    struct FnMut;
+    struct FnOnce;
+    type bool = u8;
+    type usize = u8;
+
+    mod core {}
+    mod std {}
+    mod alloc {}
+
+    struct Counter(::core::primitive::usize);
+
+    impl for_else::LoopObserver for Counter {
+        fn on_complete(&mut self, iterations: ::core::primitive::usize) {
+            self.0 = iterations;
+        }
+    }
+
+    #[test]
+    fn test_shadowed_prelude_names() {
+        let mut seen = 0;
+        let values = for_! { collect n in 0..9 until seen == 4 {
+            seen += 1;
+            if n % 2 == 0 {
+                emit n;
+            }
+        }};
+        assert_eq!(values, [0, 2]);
+
+        let mut items = [1, 2, 3].into_iter();
+        let mut rest = 0;
+        loop_else! { while let ::core::option::Option::Some(n) = items.next() with mut seen = 0 {
+            seen += 1;
+            rest += n * seen;
+        } else {
+            rest += 1;
+        }}
+        assert_eq!(rest, 15);
+
+        let mut polls = 0;
+        let mut outcome = "";
+        loop_else! { while alive: polls < 3 && fresh: polls != 7 {
+            polls += 1;
+        } else alive {
+            outcome = "done";
+        } else fresh {
+            outcome = "stale";
+        }}
+        assert_eq!(outcome, "done");
+
+        let mut counter = Counter(0);
+        for_! { n in 0..300 observe(&mut counter) {
+            if n == 1000 {
+                break;
+            }
+        } else {
+        }}
+        assert_eq!(counter.0, 300);
+    }
+}
@@ -0,0 +1,95 @@
+use for_else::{for_, loop_else, LoopObserver};
+
+#[derive(Default, Debug, PartialEq)]
+struct Events(Vec<String>);
+
+impl LoopObserver for Events {
+    fn on_iteration(&mut self, index: usize) {
+        self.0.push(format!("iteration {}", index));
+    }
+
+    fn on_break(&mut self, iterations: usize) {
+        self.0.push(format!("break after {}", iterations));
+    }
+
+    fn on_complete(&mut self, iterations: usize) {
+        self.0.push(format!("complete after {}", iterations));
+    }
+
+    fn on_empty(&mut self) {
+        self.0.push("empty".to_string());
+    }
+}
+
+#[test]
+fn test_break() {
+    let mut events = Events::default();
+    for_! { n in 1..10 observe(&mut events) {
+        if n == 2 {
+            break;
+        }
+    } else {
+    }}
+
+    assert_eq!(events.0, ["iteration 0", "iteration 1", "break after 2"]);
+}
+
+#[test]
+fn test_complete() {
+    let mut events = Events::default();
+    let mut flag = false;
+    for_! { _n in 0..2 observe(&mut events) {
+    } else {
+        flag = true;
+    }}
+
+    assert!(flag);
+    assert_eq!(events.0, ["iteration 0", "iteration 1", "complete after 2"]);
+}
+
+#[test]
+fn test_empty() {
+    let mut events = Events::default();
+    for_! { _n in Vec::<u32>::new() observe(&mut events) {
+    } else {
+    }}
+
+    assert_eq!(events.0, ["empty"]);
+}
+
+#[test]
+fn test_refutable_items_not_observed() {
+    let mut events = Events::default();
+    for_! { Some(_n) in [None, Some(1), None] observe(&mut events) {
+    } else {
+    }}
+
+    assert_eq!(events.0, ["iteration 0", "complete after 1"]);
+}
+
+#[test]
+fn test_collect_and_until() {
+    let mut events = Events::default();
+    let mut taken = 0;
+    let values = for_! { collect n in 0..10 until taken == 2 observe(&mut events) {
+        taken += 1;
+        emit n;
+    }};
+
+    assert_eq!(values, [0, 1]);
+    assert_eq!(events.0.last().unwrap(), "complete after 2");
+}
+
+#[test]
+fn test_loop_else_default_callbacks() {
+    struct Nothing;
+    impl LoopObserver for Nothing {}
+
+    let mut count = 0;
+    loop_else! { loop max 3 observe(Nothing) {
+        count += 1;
+    } else {
+    }}
+
+    assert_eq!(count, 3);
+}
@@ -0,0 +1,75 @@
+#![cfg(feature = "paginate")]
+
+use for_else::paginate_for_;
+
+fn fetch(cursor: Option<u32>) -> (Vec<u32>, Option<u32>) {
+    let page = cursor.unwrap_or(0);
+    let items = (page * 3..page * 3 + 3).collect();
+    let next = if page < 3 { Some(page + 1) } else { None };
+    (items, next)
+}
+
+#[test]
+fn test_break_on_later_page() {
+    let mut fetched = 0;
+    let mut flag = true;
+    paginate_for_! { item in cursor => { fetched += 1; fetch(cursor) } {
+        if item == 7 {
+            break;
+        }
+    } else {
+        flag = false;
+    }}
+
+    assert!(flag);
+    assert_eq!(fetched, 3);
+}
+
+#[test]
+fn test_exhausted() {
+    let mut seen = Vec::new();
+    let mut flag = false;
+    paginate_for_! { item in cursor => fetch(cursor) {
+        seen.push(item);
+    } else {
+        flag = true;
+    }}
+
+    assert!(flag);
+    assert_eq!(seen, (0..12).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_empty_pages() {
+    let mut flag = false;
+    paginate_for_! { _item in cursor => (Vec::<u32>::new(), cursor.map_or(Some(1), |c| (c < 5).then_some(c + 1))) {
+        break;
+    } else {
+        flag = true;
+    }}
+
+    assert!(flag);
+}
+
+fn try_fetch(cursor: Option<u32>) -> Result<(Vec<u32>, Option<u32>), String> {
+    match cursor {
+        None => Ok((vec![1, 2], Some(1))),
+        Some(_) => Err("server error".to_string()),
+    }
+}
+
+fn find_three() -> Result<bool, String> {
+    paginate_for_! { item in cursor => try_fetch(cursor)? {
+        if item == 3 {
+            return Ok(true);
+        }
+    } else {
+        return Ok(false);
+    }}
+    unreachable!()
+}
+
+#[test]
+fn test_question_mark_in_fetch() {
+    assert_eq!(find_three(), Err("server error".to_string()));
+}
@@ -0,0 +1,104 @@
+#![cfg(feature = "rayon")]
+
+use for_else::par_for_;
+use rayon::prelude::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+#[test]
+fn test_else_runs_when_no_thread_breaks() {
+    let visited = AtomicUsize::new(0);
+    let mut else_ran = false;
+    par_for_! { n in 0..1000 {
+        visited.fetch_add(1, Ordering::Relaxed);
+        if n > 5000 {
+            break;
+        }
+    } else {
+        else_ran = true;
+    }}
+
+    assert!(else_ran);
+    assert_eq!(visited.load(Ordering::Relaxed), 1000);
+}
+
+#[test]
+fn test_break_skips_else() {
+    let found = Mutex::new(None);
+    let mut else_ran = false;
+    par_for_! { n in 1..100_000u64 {
+        if n * n == 99_980_001 {
+            *found.lock().unwrap() = Some(n);
+            break;
+        }
+    } else {
+        else_ran = true;
+    }}
+
+    assert!(!else_ran);
+    assert_eq!(*found.lock().unwrap(), Some(9_999));
+}
+
+#[test]
+fn test_continue_and_inner_loops() {
+    let words = vec!["apple", "banana", "cherry", "date"];
+    let checked = AtomicUsize::new(0);
+    let mut else_ran = false;
+    par_for_! { word in words.par_iter() {
+        if word.len() < 5 {
+            continue;
+        }
+        checked.fetch_add(1, Ordering::Relaxed);
+        // an unlabeled `break` in an inner loop leaves only that loop
+        for c in word.chars() {
+            if c == 'z' {
+                break;
+            }
+        }
+    } else {
+        else_ran = true;
+    }}
+
+    assert!(else_ran);
+    assert_eq!(checked.load(Ordering::Relaxed), 3);
+}
+
+#[test]
+fn test_item_patterns() {
+    let pairs = vec![(1, 'a'), (2, 'b'), (3, 'c')];
+    let mut outcome = "";
+    par_for_! { (n, c) in pairs {
+        if n == 2 && c == 'b' {
+            break;
+        }
+    } else {
+        outcome = "not found";
+    }}
+
+    assert_eq!(outcome, "");
+}
+
+#[test]
+fn test_continue_in_else_if_and_let_initializer() {
+    let kept = AtomicUsize::new(0);
+    let mut else_ran = false;
+    par_for_! { n in 0..100 {
+        if n > 1000 {
+            break;
+        } else if n % 3 == 0 {
+            continue;
+        }
+        let half = if n % 2 == 0 { n / 2 } else { continue };
+        kept.fetch_add(half, Ordering::Relaxed);
+    } else {
+        else_ran = true;
+    }}
+
+    assert!(else_ran);
+    // the halves of the even numbers below 100 that are not multiples of 3
+    let expected = (0..100)
+        .filter(|n| n % 2 == 0 && n % 3 != 0)
+        .map(|n| n / 2)
+        .sum();
+    assert_eq!(kept.load(Ordering::Relaxed), expected);
+}
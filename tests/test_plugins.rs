@@ -0,0 +1,93 @@
+use for_else::{for_, loop_else};
+
+/// Records every hook it is invoked for into the `Vec<String>` named in the clause.
+macro_rules! trace {
+    (@for_else setup $state:ident { $log:ident }) => {
+        let mut $state = 0;
+        $log.push("setup".to_string());
+    };
+    (@for_else iteration $state:ident { $log:ident }) => {
+        $log.push(format!("iteration {}", $state));
+        $state += 1;
+    };
+    (@for_else $outcome:tt $state:ident { $log:ident }) => {
+        $log.push(format!("{} after {}", stringify!($outcome), $state));
+    };
+}
+
+mod plugins {
+    /// Adds the number of iterations to the counter named in the clause.
+    #[macro_export]
+    macro_rules! count_iterations {
+        (@for_else setup $state:ident { $counter:expr }) => {
+            let mut $state = &mut $counter;
+        };
+        (@for_else iteration $state:ident { $counter:expr }) => {
+            *$state += 1;
+        };
+        (@for_else $outcome:tt $state:ident { $counter:expr }) => {};
+    }
+}
+
+#[test]
+fn test_break() {
+    let mut log = Vec::new();
+    for_! { n in 1..10 {
+        if n == 2 {
+            break;
+        }
+    } else {
+        panic!("the loop was broken");
+    } trace! { log }}
+
+    assert_eq!(
+        log,
+        ["setup", "iteration 0", "iteration 1", "break after 2"]
+    );
+}
+
+#[test]
+fn test_complete_and_skipped_items() {
+    let mut log = Vec::new();
+    let mut else_ran = false;
+    for_! { Some(_n) in [Some(1), None, Some(3)] {
+    } else {
+        else_ran = true;
+    } trace! { log }}
+
+    assert!(else_ran);
+    assert_eq!(
+        log,
+        ["setup", "iteration 0", "iteration 1", "complete after 2"]
+    );
+}
+
+#[test]
+fn test_several_plugins_with_paths() {
+    let mut log = Vec::new();
+    let mut first = 0;
+    let mut second = 0;
+    loop_else! { while log.len() < 4 {
+    } else {
+    } crate::count_iterations! { first } trace! { log } count_iterations! { second }}
+
+    assert_eq!((first, second), (3, 3));
+    assert_eq!(log.last().unwrap(), "complete after 3");
+}
+
+#[test]
+fn test_attributes_apply_to_every_hook() {
+    let mut log = Vec::new();
+    let mut count = 0;
+    for_! { _n in 0..3 {
+    } else {
+    }
+    #[cfg(any())]
+    trace! { log }
+    #[cfg(all())]
+    count_iterations! { count }}
+
+    log.push("after".to_string());
+    assert_eq!(log, ["after"]);
+    assert_eq!(count, 3);
+}
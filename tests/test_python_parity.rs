@@ -0,0 +1,265 @@
+//! Runs the same loops through the macros and through CPython's `for`/`else` and
+//! `while`/`else`, and checks that both take the same steps. Fails when `python3` is not
+//! installed; set `FOR_ELSE_PYTHON` to use another interpreter.
+
+use std::fmt::Write as _;
+use std::process::Command;
+
+use for_else::{for_, loop_else};
+
+const LISTS: &[&[i32]] = &[&[], &[1], &[1, 2], &[1, 2, 3], &[3, 3, 1], &[2, 4, 6, 8]];
+
+/// The values a loop may break or continue on: none, values in some lists, and a value in none.
+const TARGETS: &[Option<i32>] = &[None, Some(1), Some(2), Some(3), Some(8), Some(9)];
+
+/// A loop over `items` that breaks on `break_on` and skips the rest of the body on
+/// `continue_on`.
+struct Scenario {
+    items: &'static [i32],
+    break_on: Option<i32>,
+    continue_on: Option<i32>,
+}
+
+fn scenarios() -> Vec<Scenario> {
+    let mut scenarios = Vec::new();
+    for &items in LISTS {
+        for &break_on in TARGETS {
+            for &continue_on in TARGETS {
+                scenarios.push(Scenario {
+                    items,
+                    break_on,
+                    continue_on,
+                });
+            }
+        }
+    }
+    scenarios
+}
+
+fn python_value(value: Option<i32>) -> String {
+    value.map_or("None".to_string(), |value| value.to_string())
+}
+
+fn python_list(items: &[i32]) -> String {
+    let items: Vec<String> = items.iter().map(i32::to_string).collect();
+    format!("[{}]", items.join(", "))
+}
+
+/// Runs `script` with Python and returns its output lines.
+fn run_python(script: &str) -> Vec<String> {
+    let python = std::env::var("FOR_ELSE_PYTHON").unwrap_or_else(|_| "python3".to_string());
+    let output = match Command::new(&python).arg("-c").arg(script).output() {
+        Ok(output) => output,
+        Err(err) => panic!(
+            "running {} failed: {}; install Python or point FOR_ELSE_PYTHON at it",
+            python, err
+        ),
+    };
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    stdout.lines().map(str::to_string).collect()
+}
+
+fn for_trace(scenario: &Scenario) -> String {
+    let mut trace = String::new();
+    for_! { x in scenario.items {
+        if Some(*x) == scenario.continue_on {
+            write!(trace, "c{} ", x).unwrap();
+            continue;
+        }
+        write!(trace, "{} ", x).unwrap();
+        if Some(*x) == scenario.break_on {
+            trace.push_str("break");
+            break;
+        }
+    } else {
+        trace.push_str("else");
+    }}
+    trace
+}
+
+#[test]
+fn test_for_else() {
+    let scenarios = scenarios();
+    let mut script = String::from(
+        "def run(items, break_on, continue_on):\n\
+         \x20   trace = ''\n\
+         \x20   for x in items:\n\
+         \x20       if x == continue_on:\n\
+         \x20           trace += 'c%d ' % x\n\
+         \x20           continue\n\
+         \x20       trace += '%d ' % x\n\
+         \x20       if x == break_on:\n\
+         \x20           trace += 'break'\n\
+         \x20           break\n\
+         \x20   else:\n\
+         \x20       trace += 'else'\n\
+         \x20   print(trace)\n",
+    );
+    for scenario in &scenarios {
+        writeln!(
+            script,
+            "run({}, {}, {})",
+            python_list(scenario.items),
+            python_value(scenario.break_on),
+            python_value(scenario.continue_on)
+        )
+        .unwrap();
+    }
+    let expected = run_python(&script);
+
+    assert_eq!(expected.len(), scenarios.len());
+    for (scenario, expected) in scenarios.iter().zip(expected) {
+        assert_eq!(
+            for_trace(scenario),
+            expected,
+            "items {:?}, break on {:?}, continue on {:?}",
+            scenario.items,
+            scenario.break_on,
+            scenario.continue_on
+        );
+    }
+}
+
+fn while_trace(scenario: &Scenario) -> String {
+    let mut trace = String::new();
+    let mut i = 0;
+    loop_else! { while i < scenario.items.len() {
+        let x = scenario.items[i];
+        i += 1;
+        if Some(x) == scenario.continue_on {
+            write!(trace, "c{} ", x).unwrap();
+            continue;
+        }
+        write!(trace, "{} ", x).unwrap();
+        if Some(x) == scenario.break_on {
+            trace.push_str("break");
+            break;
+        }
+    } else {
+        trace.push_str("else");
+    }}
+    trace
+}
+
+#[test]
+fn test_while_else() {
+    let scenarios = scenarios();
+    let mut script = String::from(
+        "def run(items, break_on, continue_on):\n\
+         \x20   trace = ''\n\
+         \x20   i = 0\n\
+         \x20   while i < len(items):\n\
+         \x20       x = items[i]\n\
+         \x20       i += 1\n\
+         \x20       if x == continue_on:\n\
+         \x20           trace += 'c%d ' % x\n\
+         \x20           continue\n\
+         \x20       trace += '%d ' % x\n\
+         \x20       if x == break_on:\n\
+         \x20           trace += 'break'\n\
+         \x20           break\n\
+         \x20   else:\n\
+         \x20       trace += 'else'\n\
+         \x20   print(trace)\n",
+    );
+    for scenario in &scenarios {
+        writeln!(
+            script,
+            "run({}, {}, {})",
+            python_list(scenario.items),
+            python_value(scenario.break_on),
+            python_value(scenario.continue_on)
+        )
+        .unwrap();
+    }
+    let expected = run_python(&script);
+
+    assert_eq!(expected.len(), scenarios.len());
+    for (scenario, expected) in scenarios.iter().zip(expected) {
+        assert_eq!(
+            while_trace(scenario),
+            expected,
+            "items {:?}, break on {:?}, continue on {:?}",
+            scenario.items,
+            scenario.break_on,
+            scenario.continue_on
+        );
+    }
+}
+
+/// Searches the rows for `target` with the nested for-else idiom: the outer loop only breaks
+/// when the inner one did.
+fn nested_trace(rows: &[&[i32]], target: i32) -> String {
+    let mut trace = String::new();
+    for_! { row in rows {
+        for_! { x in *row {
+            write!(trace, "{} ", x).unwrap();
+            if *x == target {
+                break;
+            }
+        } else {
+            trace.push_str("| ");
+            continue;
+        }}
+        trace.push_str("found");
+        break;
+    } else {
+        trace.push_str("else");
+    }}
+    trace
+}
+
+#[test]
+fn test_nested_for_else() {
+    let grids: Vec<Vec<&[i32]>> = vec![
+        vec![],
+        vec![&[]],
+        vec![&[1, 2], &[3]],
+        vec![&[], &[2, 4], &[]],
+        vec![LISTS[3], LISTS[5], LISTS[4]],
+    ];
+    let targets = [1, 2, 3, 8, 9];
+    let mut script = String::from(
+        "def run(rows, target):\n\
+         \x20   trace = ''\n\
+         \x20   for row in rows:\n\
+         \x20       for x in row:\n\
+         \x20           trace += '%d ' % x\n\
+         \x20           if x == target:\n\
+         \x20               break\n\
+         \x20       else:\n\
+         \x20           trace += '| '\n\
+         \x20           continue\n\
+         \x20       trace += 'found'\n\
+         \x20       break\n\
+         \x20   else:\n\
+         \x20       trace += 'else'\n\
+         \x20   print(trace)\n",
+    );
+    for grid in &grids {
+        for target in targets {
+            let rows: Vec<String> = grid.iter().map(|row| python_list(row)).collect();
+            writeln!(script, "run([{}], {})", rows.join(", "), target).unwrap();
+        }
+    }
+    let expected = run_python(&script);
+
+    let mut expected = expected.into_iter();
+    for grid in &grids {
+        for target in targets {
+            assert_eq!(
+                nested_trace(grid, target),
+                expected.next().unwrap(),
+                "rows {:?}, target {}",
+                grid,
+                target
+            );
+        }
+    }
+    assert_eq!(expected.next(), None);
+}
@@ -0,0 +1,89 @@
+use for_else::read_chunks_;
+use std::io::{self, Read};
+
+#[test]
+fn test_found() {
+    let data: &[u8] = b"hello, world";
+    let mut seen = Vec::new();
+    let mut flag = true;
+    read_chunks_! { chunk in data, size 4 {
+        seen.push(chunk.to_vec());
+        if chunk.contains(&b',') {
+            break;
+        }
+    } err e {
+        panic!("{}", e);
+    } else {
+        flag = false;
+    }}
+
+    assert!(flag);
+    assert_eq!(seen, [b"hell".to_vec(), b"o, w".to_vec()]);
+}
+
+#[test]
+fn test_eof() {
+    let mut total = 0;
+    let mut flag = false;
+    read_chunks_! { chunk in io::repeat(7).take(20_000) {
+        total += chunk.len();
+    } err e {
+        panic!("{}", e);
+    } else {
+        flag = true;
+    }}
+
+    assert!(flag);
+    assert_eq!(total, 20_000);
+}
+
+struct Flaky {
+    reads: u32,
+}
+
+impl Read for Flaky {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.reads += 1;
+        match self.reads {
+            1 => Err(io::Error::from(io::ErrorKind::Interrupted)),
+            2 => {
+                buf[0] = 1;
+                Ok(1)
+            }
+            _ => Err(io::Error::other("connection reset")),
+        }
+    }
+}
+
+#[test]
+fn test_error() {
+    let mut chunks = 0;
+    let mut outcome = String::new();
+    let reader = Flaky { reads: 0 };
+    read_chunks_! { _chunk in reader {
+        chunks += 1;
+    } err e {
+        outcome = e.to_string();
+    } else {
+        outcome = "else".to_string();
+    }}
+
+    assert_eq!(chunks, 1);
+    assert_eq!(outcome, "connection reset");
+}
+
+#[test]
+fn test_borrowed_reader() {
+    let mut reader = io::Cursor::new(vec![1u8, 2, 3]);
+    read_chunks_! { chunk in &mut reader, size 2 {
+        if chunk == [1, 2] {
+            break;
+        }
+    } err e {
+        panic!("{}", e);
+    } else {
+        panic!("not found");
+    }}
+
+    assert_eq!(reader.position(), 2);
+}
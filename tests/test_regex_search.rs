@@ -0,0 +1,51 @@
+#![cfg(feature = "regex")]
+
+use for_else::regex_search_;
+use regex::Regex;
+
+#[test]
+fn test_matches() {
+    let re = Regex::new(r"\d+").unwrap();
+    let mut numbers = Vec::new();
+    let mut flag = false;
+    regex_search_! { m in matches re, "a1 b22 c333" {
+        numbers.push(m.as_str().to_string());
+    } else {
+        flag = true;
+    }}
+
+    assert!(flag);
+    assert_eq!(numbers, ["1", "22", "333"]);
+}
+
+#[test]
+fn test_captures_literal() {
+    let mut found = None;
+    for line in ["level=info msg=ok", "level=error msg=disk"] {
+        regex_search_! { caps in captures r"level=(\w+) msg=(\w+)", line {
+            if &caps[1] == "error" {
+                found = Some(caps[2].to_string());
+                break;
+            }
+        } else {
+        }}
+    }
+
+    assert_eq!(found.as_deref(), Some("disk"));
+}
+
+#[test]
+fn test_no_match() {
+    let re = Regex::new("needle").unwrap();
+    let haystack = String::from("hay hay hay");
+    let mut flag = false;
+    regex_search_! { m in matches &re, &haystack {
+        if m.start() > 0 {
+            break;
+        }
+    } else {
+        flag = true;
+    }}
+
+    assert!(flag);
+}
@@ -0,0 +1,86 @@
+use for_else::{retry_, Linear};
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_break_means_success() {
+    let mut attempts = Vec::new();
+    let mut gave_up = false;
+    retry_! { 5 times {
+        attempts.push(attempt);
+        if attempt == 3 {
+            break;
+        }
+    } else {
+        gave_up = true;
+    }}
+
+    assert_eq!(attempts, [1, 2, 3]);
+    assert!(!gave_up);
+}
+
+#[test]
+fn test_else_runs_when_attempts_are_exhausted() {
+    let limit = 4;
+    let mut last = 0;
+    let mut gave_up = false;
+    retry_! { try_number in limit times {
+        last = try_number;
+        if try_number > 10 {
+            break;
+        }
+    } else {
+        gave_up = true;
+    }}
+
+    assert_eq!(last, 4);
+    assert!(gave_up);
+}
+
+#[test]
+fn test_delay_between_attempts() {
+    let start = Instant::now();
+    let mut gave_up = false;
+    retry_! { 3 times delay Duration::from_millis(20) {
+        if attempt == 0 {
+            break;
+        }
+    } else {
+        gave_up = true;
+    }}
+
+    assert!(gave_up);
+    // two waits, since there is none before the first attempt or after the last
+    let elapsed = start.elapsed();
+    assert!(elapsed >= Duration::from_millis(40), "{:?}", elapsed);
+    assert!(elapsed < Duration::from_millis(60 + 500), "{:?}", elapsed);
+
+    let mut outcome = "";
+    retry_! { 3 times delay Linear::new(Duration::from_millis(1), Duration::from_millis(1)) {
+        if attempt == 2 {
+            outcome = "ok";
+            break;
+        }
+    } else {
+        outcome = "gave up";
+    }}
+
+    assert_eq!(outcome, "ok");
+}
+
+#[test]
+fn test_labeled_break_from_inner_loop() {
+    let replies = [["busy", "busy"], ["busy", "ok"]];
+    let mut succeeded_on = None;
+    retry_! { 'attempts: 2 times {
+        for reply in replies[attempt - 1] {
+            if reply == "ok" {
+                succeeded_on = Some(attempt);
+                break 'attempts;
+            }
+        }
+    } else {
+        succeeded_on = None;
+    }}
+
+    assert_eq!(succeeded_on, Some(2));
+}
@@ -0,0 +1,128 @@
+#![cfg(feature = "fallible-rows")]
+
+use for_else::rows_for_;
+use rusqlite::Connection;
+
+fn connection() -> Connection {
+    let conn = Connection::open_in_memory().unwrap();
+    conn.execute_batch(
+        "CREATE TABLE items (id INTEGER, name TEXT);
+         INSERT INTO items VALUES (1, 'one'), (2, 'two'), (3, NULL);",
+    )
+    .unwrap();
+    conn
+}
+
+#[test]
+fn test_found() {
+    let conn = connection();
+    let mut statement = conn.prepare("SELECT id FROM items").unwrap();
+    let mut flag = true;
+    rows_for_! { row in statement.query([]).unwrap() {
+        if row.get::<_, i64>(0).unwrap() == 2 {
+            break;
+        }
+    } err e {
+        panic!("{}", e);
+    } else {
+        flag = false;
+    }}
+
+    assert!(flag);
+}
+
+#[test]
+fn test_exhausted() {
+    let conn = connection();
+    let mut statement = conn.prepare("SELECT id FROM items").unwrap();
+    let mut ids = Vec::new();
+    let mut flag = false;
+    rows_for_! { id in statement.query([]).unwrap(), map |row| row.get::<_, i64>(0) {
+        ids.push(id);
+    } err e {
+        panic!("{}", e);
+    } else {
+        flag = true;
+    }}
+
+    assert!(flag);
+    assert_eq!(ids, [1, 2, 3]);
+}
+
+#[test]
+fn test_mapping_error() {
+    let conn = connection();
+    let mut statement = conn.prepare("SELECT name FROM items").unwrap();
+    let mut names = Vec::new();
+    let mut outcome = "";
+    rows_for_! { name in statement.query([]).unwrap(), map |row| row.get::<_, String>(0) {
+        names.push(name);
+    } err _e {
+        outcome = "err";
+    } else {
+        outcome = "else";
+    }}
+
+    assert_eq!(outcome, "err");
+    assert_eq!(names, ["one", "two"]);
+}
+
+struct Failing(u32);
+
+impl Failing {
+    fn next(&mut self) -> Result<Option<u32>, String> {
+        self.0 += 1;
+        if self.0 < 3 {
+            Ok(Some(self.0))
+        } else {
+            Err("connection lost".to_string())
+        }
+    }
+}
+
+#[test]
+fn test_generic_rows() {
+    let mut error = None;
+    rows_for_! { _n in Failing(0) {
+    } err e {
+        error = Some(e);
+    } else {
+        panic!("rows ended");
+    }}
+
+    assert_eq!(error.as_deref(), Some("connection lost"));
+}
+
+#[test]
+fn test_max_errors() {
+    let conn = connection();
+    let mut statement = conn.prepare("SELECT name FROM items").unwrap();
+    let mut names = Vec::new();
+    let mut outcome = "";
+    rows_for_! { name in statement.query([]).unwrap(), map |row| row.get::<_, String>(0), max_errors(1, skipped) {
+        names.push(name);
+    } err _e {
+        outcome = "err";
+    } else {
+        assert_eq!(skipped, 1);
+        outcome = "else";
+    }}
+
+    assert_eq!(outcome, "else");
+    assert_eq!(names, ["one", "two"]);
+}
+
+#[test]
+fn test_max_errors_exceeded() {
+    let conn = connection();
+    let mut statement = conn.prepare("SELECT name FROM items").unwrap();
+    let mut outcome = "";
+    rows_for_! { _name in statement.query([]).unwrap(), map |row| row.get::<_, String>(0), max_errors(0) {
+    } err _e {
+        outcome = "err";
+    } else {
+        outcome = "else";
+    }}
+
+    assert_eq!(outcome, "err");
+}
@@ -0,0 +1,110 @@
+use for_else::search_;
+
+#[test]
+fn test_finds_the_break_value() {
+    let numbers = [3, 8, 12, 5];
+    let even = search_! { n in numbers {
+        if n % 2 == 0 {
+            break n;
+        }
+    }};
+
+    assert_eq!(even, Some(8));
+
+    let negative = search_! { n in numbers {
+        if n < 0 {
+            break n;
+        }
+    }};
+
+    assert_eq!(negative, None);
+}
+
+#[test]
+fn test_else_supplies_the_fallback() {
+    let names = ["ada", "grace", "linus"];
+    let position = search_! { (i, name) in names.iter().enumerate() {
+        if *name == "grace" {
+            break i;
+        }
+    } else {
+        usize::MAX
+    }};
+
+    assert_eq!(position, 1);
+
+    let position = search_! { (i, name) in names.iter().enumerate() {
+        if *name == "ken" {
+            break i;
+        }
+    } else {
+        usize::MAX
+    }};
+
+    assert_eq!(position, usize::MAX);
+}
+
+#[test]
+fn test_bare_break_and_continue() {
+    let mut checked = 0;
+    let found = search_! { n in 1..10 {
+        if n % 3 != 0 {
+            continue;
+        }
+        checked += 1;
+        if n > 4 {
+            break;
+        }
+    }};
+
+    assert_eq!(found, Some(()));
+    assert_eq!(checked, 2);
+}
+
+#[test]
+fn test_labels() {
+    let grid = [[1, 2, 3], [4, 5, 6]];
+    let cell = search_! { 'rows: (r, row) in grid.iter().enumerate() {
+        for (c, &value) in row.iter().enumerate() {
+            if value == 5 {
+                break 'rows (r, c);
+            }
+            if value == 2 {
+                // an unlabeled `break` leaves only the inner loop
+                break;
+            }
+        }
+    }};
+
+    assert_eq!(cell, Some((1, 1)));
+
+    let mut searches = 0;
+    'outer: for limit in [2, 10] {
+        searches += 1;
+        let _: Option<()> = search_! { n in 0..limit {
+            if n == 5 {
+                break 'outer;
+            }
+        }};
+    }
+
+    assert_eq!(searches, 2);
+}
+
+#[test]
+fn test_break_to_outer_search_from_inner_search() {
+    let grid = [[1, 2], [3, 4]];
+    let found = search_! { 'outer: row in grid {
+        let inner = search_! { cell in row {
+            if cell == 3 {
+                break 'outer (row[0], cell);
+            }
+            if cell == 9 {
+                break cell;
+            }
+        }};
+        assert_eq!(inner, None);
+    }};
+
+    assert_eq!(found, Some((3, 3)));
+}
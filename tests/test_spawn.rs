@@ -0,0 +1,105 @@
+#![cfg(feature = "tokio")]
+
+use for_else::spawn_for_;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_first_success() {
+    let mut flag = true;
+    spawn_for_! { i in 0..10u64 {
+        tokio::time::sleep(Duration::from_millis(i)).await;
+        if i == 3 {
+            break;
+        }
+    } else {
+        flag = false;
+    }}
+
+    assert!(flag);
+}
+
+#[tokio::test]
+async fn test_no_success() {
+    let finished = Arc::new(AtomicUsize::new(0));
+    let mut flag = false;
+    spawn_for_! { i in 0..10, clone(finished) {
+        finished.fetch_add(1, Ordering::SeqCst);
+        if i == 10 {
+            break;
+        }
+    } else {
+        flag = true;
+    }}
+
+    assert!(flag);
+    assert_eq!(finished.load(Ordering::SeqCst), 10);
+}
+
+#[tokio::test]
+async fn test_limit() {
+    let running = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+    let mut flag = false;
+    spawn_for_! { _i in 0..8, limit 2, clone(running, peak) {
+        let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+        peak.fetch_max(now, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(2)).await;
+        running.fetch_sub(1, Ordering::SeqCst);
+    } else {
+        flag = true;
+    }}
+
+    assert!(flag);
+    assert!(peak.load(Ordering::SeqCst) <= 2);
+}
+
+#[tokio::test]
+async fn test_cancels_remaining_tasks() {
+    let finished = Arc::new(AtomicUsize::new(0));
+    spawn_for_! { i in 0..5u64, clone(finished) {
+        if i == 0 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        finished.fetch_add(1, Ordering::SeqCst);
+    } else {
+        panic!("a task broke");
+    }}
+
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert_eq!(finished.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn test_item_does_not_shadow_clones_and_zero_limit() {
+    let item = Arc::new(AtomicUsize::new(0));
+    let mut flag = false;
+    spawn_for_! { i in 1..=4, limit 0, clone(item) {
+        item.fetch_add(i, Ordering::SeqCst);
+    } else {
+        flag = true;
+    }}
+
+    // a limit of 0 sets no bound, so every task ran
+    assert!(flag);
+    assert_eq!(item.load(Ordering::SeqCst), 10);
+}
+
+#[tokio::test]
+async fn test_continue_ends_the_task() {
+    let sum = Arc::new(AtomicUsize::new(0));
+    let mut flag = false;
+    spawn_for_! { i in 1..=6usize, clone(sum) {
+        if i % 2 == 0 {
+            continue;
+        }
+        sum.fetch_add(i, Ordering::SeqCst);
+    } else {
+        flag = true;
+    }}
+
+    assert!(flag);
+    assert_eq!(sum.load(Ordering::SeqCst), 9);
+}
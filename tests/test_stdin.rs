@@ -0,0 +1,122 @@
+use for_else::for_stdin_;
+
+#[test]
+fn test_tokens() {
+    let input = "1 2\n3   4\n\n5\n";
+    let mut sum = 0;
+    let mut flag = false;
+    for_stdin_! { n: u32 in tokens from input.as_bytes() {
+        sum += n;
+    } else {
+        flag = true;
+    }}
+
+    assert_eq!(sum, 15);
+    assert!(flag);
+}
+
+#[test]
+fn test_lines_break() {
+    let input = "alpha\r\nbeta\ngamma";
+    let mut flag = true;
+    for_stdin_! { line: String in lines from input.as_bytes() {
+        if line == "beta" {
+            break;
+        }
+    } else {
+        flag = false;
+    }}
+
+    assert!(flag);
+}
+
+#[test]
+fn test_err_clause() {
+    let input = "1 two 3";
+    let mut errors = 0;
+    let mut sum = 0;
+    for_stdin_! { n: i32 in tokens from input.as_bytes() {
+        sum += n;
+    } err _e {
+        errors += 1;
+    } else {
+        sum += 100;
+    }}
+
+    assert_eq!(errors, 1);
+    assert_eq!(sum, 104);
+}
+
+#[test]
+fn test_inferred_type() {
+    let input = "1.5 x";
+    let mut values: Vec<f64> = Vec::new();
+    let mut flag = true;
+    for_stdin_! { value in tokens from input.as_bytes() {
+        values.push(value);
+    } err _e {
+        break;
+    } else {
+        flag = false;
+    }}
+
+    assert_eq!(values, [1.5]);
+    assert!(flag);
+}
+
+#[test]
+#[should_panic(expected = "for_stdin_!: failed to parse \"x\"")]
+fn test_parse_failure_without_err_clause() {
+    for_stdin_! { _n: u8 in tokens from "x".as_bytes() {
+    } else {
+    }}
+}
+
+#[test]
+fn test_max_errors() {
+    let input = "1 a b c 2 d";
+    let mut errors = Vec::new();
+    let mut sum = 0;
+    for_stdin_! { n: u32 in tokens from input.as_bytes(), max_errors(2, skipped) {
+        sum += n;
+    } err _e {
+        errors.push(skipped);
+    } else {
+        assert_eq!(skipped, 2);
+    }}
+
+    assert_eq!(sum, 3);
+    assert_eq!(errors, [2, 2]);
+}
+
+#[test]
+fn test_max_errors_without_err_clause() {
+    let mut sum = 0;
+    for_stdin_! { n: u32 in tokens from "1 x 2".as_bytes(), max_errors(1) {
+        sum += n;
+    } else {
+    }}
+
+    assert_eq!(sum, 3);
+}
+
+#[test]
+fn test_reader_keeps_the_lines_after_a_break() {
+    use std::io::{BufRead, BufReader};
+
+    let mut reader = BufReader::new("1 2\n3 stop 4\n5\n6\n".as_bytes());
+    let mut sum = 0;
+    for_stdin_! { token: String in tokens from reader {
+        if token == "stop" {
+            break;
+        }
+        sum += token.parse::<u32>().unwrap();
+    } else {
+        sum = 0;
+    }}
+
+    assert_eq!(sum, 6);
+    // the line the loop stopped in was read, the next ones are left to the caller
+    let rest: Vec<String> = reader.lines().map(Result::unwrap).collect();
+    assert_eq!(rest, ["5", "6"]);
+}
@@ -0,0 +1,35 @@
+#![cfg(feature = "crossterm")]
+
+use for_else::terminal_for_;
+use std::time::Duration;
+
+#[test]
+fn test_idle_without_waiting() {
+    let mut outcome = "";
+    terminal_for_! { _event in idle Duration::ZERO {
+        outcome = "event";
+    } err _e {
+        outcome = "err";
+    } else {
+        outcome = "else";
+    }}
+
+    assert_eq!(outcome, "else");
+}
+
+#[test]
+fn test_ends_by_idle_or_error() {
+    // test runs may or may not have a terminal, but without key presses the loop never breaks
+    let mut outcome = "";
+    let mut ticks = 0;
+    terminal_for_! { _event in idle Duration::from_millis(30), tick Duration::from_millis(5) {
+    } tick {
+        ticks += 1;
+    } err _e {
+        outcome = "err";
+    } else {
+        outcome = "else";
+    }}
+
+    assert!(outcome == "err" || (outcome == "else" && ticks > 0));
+}
@@ -0,0 +1,106 @@
+use for_else::{for_, loop_else};
+
+#[test]
+fn test_then_runs_only_after_a_break() {
+    let mut outcomes = Vec::new();
+    for_! { n in [1, 2, 3] {
+        if n == 2 {
+            break;
+        }
+    } then {
+        outcomes.push("then");
+    } else {
+        outcomes.push("else");
+    }}
+    for_! { n in [1, 2, 3] {
+        if n == 5 {
+            break;
+        }
+    } then {
+        outcomes.push("then");
+    } else {
+        outcomes.push("else");
+    }}
+
+    assert_eq!(outcomes, ["then", "else"]);
+}
+
+#[test]
+fn test_then_without_else() {
+    let mut log = Vec::new();
+    for attempts in [2, 5] {
+        let mut n = 0;
+        loop_else! { while n < attempts {
+            n += 1;
+            if n == 3 {
+                break;
+            }
+        } then {
+            log.push(n);
+        }}
+    }
+
+    assert_eq!(log, [3]);
+}
+
+#[test]
+fn test_then_with_clauses() {
+    let mut outcome;
+    let mut seen = 0;
+    for_! { n in 0..10 until seen == 4 {
+        seen += 1;
+        if n == 8 {
+            break;
+        }
+    } then {
+        outcome = "then";
+    } else {
+        outcome = "else";
+    } stopped {
+        outcome = "stopped";
+    }}
+
+    assert_eq!(outcome, "stopped");
+
+    let mut visited = 0;
+    for_! { fold n in 0..10 {
+        visited += 1;
+        if n == 1 {
+            break;
+        }
+    } then {
+        outcome = "then";
+    }}
+
+    assert_eq!((outcome, visited), ("then", 2));
+}
+
+#[test]
+fn test_then_in_expressions() {
+    let mut broke = false;
+    let collected = for_! { collect n in 0..10 {
+        if n == 3 {
+            break;
+        }
+        emit n;
+    } then {
+        broke = true;
+    }};
+
+    assert!(broke);
+    assert_eq!(collected, [0, 1, 2]);
+
+    let mut found_at = None;
+    let value = for_! { (i, word) in ["a", "bb", "ccc"].into_iter().enumerate() {
+        if word.len() == 2 {
+            break word;
+        }
+        found_at = Some(i);
+    } then {
+        found_at = found_at.map(|i| i + 1);
+    } else {
+        ""
+    }};
+
+    assert_eq!((value, found_at), ("bb", Some(1)));
+}
@@ -0,0 +1,22 @@
+//! Run with `RUSTFLAGS="--cfg for_else_nightly" cargo +nightly test --test test_try_block`.
+#![cfg(for_else_nightly)]
+#![feature(try_blocks)]
+
+use for_else::for_;
+
+#[test]
+fn test_break_in_try_block() {
+    let mut flag = true;
+    for_! { i in 0..10 {
+        try {
+            if i == 5 {
+                break;
+            }
+            Some::<()>(())?
+        };
+    } else {
+        flag = false;
+    }}
+
+    assert!(flag);
+}
@@ -0,0 +1,83 @@
+use for_else::{for_, loop_else};
+use std::collections::HashMap;
+
+fn parse_all(inputs: &[&str]) -> (Vec<u32>, bool) {
+    let mut parsed = Vec::new();
+    let mut finished = false;
+    for_! { input in inputs try break {
+        parsed.push(input.parse::<u32>()?);
+    } else {
+        finished = true;
+    }}
+    (parsed, finished)
+}
+
+#[test]
+fn test_try_continue_skips_failures() {
+    let prices = HashMap::from([("apple", 3), ("pear", 5)]);
+    let mut total = 0;
+    let mut complete = false;
+    for_! { name in ["apple", "kiwi", "pear"] try continue {
+        total += prices.get(name)?;
+    } else {
+        complete = true;
+    }}
+
+    assert_eq!(total, 8);
+    assert!(complete);
+}
+
+#[test]
+fn test_try_break_skips_else() {
+    assert_eq!(parse_all(&["1", "2"]), (vec![1, 2], true));
+    assert_eq!(parse_all(&["1", "x", "3"]), (vec![1], false));
+}
+
+#[test]
+fn test_try_in_nested_loop_targets_outer_loop() {
+    let rows = [
+        vec![Some(1), Some(2)],
+        vec![Some(3), None, Some(5)],
+        vec![Some(6)],
+    ];
+    let mut sums = Vec::new();
+    for_! { row in &rows try continue {
+        let mut sum = 0;
+        for cell in row {
+            sum += (*cell)?;
+        }
+        sums.push(sum);
+    } else {
+    }}
+
+    assert_eq!(sums, [3, 6]);
+}
+
+#[test]
+fn test_try_in_closure_keeps_its_meaning() {
+    let mut lengths = Vec::new();
+    for_! { word in ["ab", "", "cde"] try continue {
+        let first_len = || -> Option<usize> { Some(word.chars().next()?.len_utf8()) };
+        lengths.push(first_len().map(|_| word.len()));
+        word.chars().next()?;
+    } else {
+    }}
+
+    assert_eq!(lengths, [Some(2), None, Some(3)]);
+}
+
+#[test]
+fn test_try_continue_with_defer_and_while() {
+    let mut queue = vec!["4", "x", "2"];
+    let mut seen = 0;
+    let mut total = 0;
+    loop_else! { while let Some(item) = queue.pop() try continue {
+        total += item.parse::<u32>().ok()?;
+    } defer {
+        seen += 1;
+    } else {
+        assert_eq!(seen, 3);
+    }}
+
+    assert_eq!(total, 6);
+}
@@ -0,0 +1,81 @@
+use for_else::try_for_;
+
+fn parse_all(lines: &[&str]) -> Vec<Result<u32, std::num::ParseIntError>> {
+    lines.iter().map(|line| line.parse()).collect()
+}
+
+#[test]
+fn test_else_runs_when_every_item_is_ok() {
+    let mut sum = 0;
+    let mut outcome = "";
+    try_for_! { n in parse_all(&["1", "2", "3"]) {
+        if n > 10 {
+            break;
+        }
+        sum += n;
+    } catch _err {
+        outcome = "catch";
+    } else {
+        outcome = "else";
+    }}
+
+    assert_eq!(sum, 6);
+    assert_eq!(outcome, "else");
+}
+
+#[test]
+fn test_first_error_runs_catch() {
+    let mut seen = Vec::new();
+    let mut caught = None;
+    let mut else_ran = false;
+    try_for_! { n in parse_all(&["4", "x", "5", "y"]) {
+        seen.push(n);
+    } catch err {
+        caught = Some(err.to_string());
+    } else {
+        else_ran = true;
+    }}
+
+    assert_eq!(seen, [4]);
+    assert_eq!(caught.as_deref(), Some("invalid digit found in string"));
+    assert!(!else_ran);
+}
+
+#[test]
+fn test_break_skips_catch_and_else() {
+    let mut outcome = "";
+    try_for_! { n in parse_all(&["7", "8", "z"]) {
+        if n == 8 {
+            break;
+        }
+    } catch _err {
+        outcome = "catch";
+    } else {
+        outcome = "else";
+    }}
+
+    assert_eq!(outcome, "");
+}
+
+#[test]
+fn test_label_and_pattern() {
+    let rows: Vec<Result<(u32, &str), String>> =
+        vec![Ok((1, "a b")), Ok((2, "c stop")), Err("unreachable".into())];
+    let mut read = Vec::new();
+    let mut outcome = String::new();
+    try_for_! { 'rows: (id, text) in rows {
+        for word in text.split(' ') {
+            if word == "stop" {
+                break 'rows;
+            }
+            read.push((id, word));
+        }
+    } catch err {
+        outcome = err;
+    } else {
+        outcome = "else".into();
+    }}
+
+    assert_eq!(outcome, "");
+    assert_eq!(read, [(1, "a"), (1, "b"), (2, "c")]);
+}
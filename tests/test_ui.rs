@@ -0,0 +1,11 @@
+#[test]
+fn test_compile_errors() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+    #[cfg(all(feature = "futures", not(feature = "tokio")))]
+    cases.compile_fail("tests/ui/futures/*.rs");
+    #[cfg(feature = "rayon")]
+    cases.compile_fail("tests/ui/rayon/*.rs");
+    #[cfg(feature = "tokio")]
+    cases.compile_fail("tests/ui/tokio/*.rs");
+}
@@ -0,0 +1,68 @@
+#![cfg(feature = "notify")]
+
+use for_else::watch_for_;
+use std::path::PathBuf;
+use std::time::Duration;
+
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("for-else-{}-{}", name, std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn test_event_arrives() {
+    let dir = scratch_dir("watch-event");
+    let writer = {
+        let dir = dir.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(100));
+            std::fs::write(dir.join("artifact"), "done").unwrap();
+        })
+    };
+
+    let mut flag = true;
+    watch_for_! { event in &dir, idle Duration::from_secs(5) {
+        if event.paths.iter().any(|path| path.ends_with("artifact")) {
+            break;
+        }
+    } err e {
+        panic!("{}", e);
+    } else {
+        flag = false;
+    }}
+
+    writer.join().unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(flag);
+}
+
+#[test]
+fn test_idle_timeout() {
+    let dir = scratch_dir("watch-idle");
+    let mut flag = false;
+    watch_for_! { _event in recursive &dir, idle Duration::from_millis(100) {
+        break;
+    } else {
+        flag = true;
+    }}
+
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(flag);
+}
+
+#[test]
+fn test_missing_path() {
+    let mut errors = 0;
+    let mut flag = false;
+    watch_for_! { _event in "/definitely/not/a/real/path", idle Duration::from_secs(5) {
+        break;
+    } err _e {
+        errors += 1;
+    } else {
+        flag = true;
+    }}
+
+    assert_eq!(errors, 1);
+    assert!(flag);
+}
@@ -0,0 +1,74 @@
+use for_else::while_let_;
+use std::collections::VecDeque;
+
+#[test]
+fn test_else_runs_when_pattern_stops_matching() {
+    let mut stack = vec![1, 3, 5];
+    let mut seen = Vec::new();
+    let mut else_ran = false;
+    while_let_! { Some(n) = stack.pop() {
+        seen.push(n);
+        if n % 2 == 0 {
+            break;
+        }
+    } else {
+        else_ran = true;
+    }}
+
+    assert!(else_ran);
+    assert_eq!(seen, [5, 3, 1]);
+}
+
+#[test]
+fn test_break_skips_else() {
+    let mut queue = VecDeque::from([1, 2, 3]);
+    let mut else_ran = false;
+    while_let_! { Some(n) = queue.pop_front() {
+        if n == 2 {
+            break;
+        }
+    } else {
+        else_ran = true;
+    }}
+
+    assert!(!else_ran);
+    assert_eq!(queue, [3]);
+}
+
+#[test]
+fn test_label_and_clauses() {
+    let mut lines = ["a", "", "b", "stop", "c"].into_iter();
+    let mut read = Vec::new();
+    let mut outcome = "";
+    while_let_! { 'lines: Some(line) = lines.next() until read.len() == 5 {
+        if line.is_empty() {
+            continue;
+        }
+        for word in line.split(' ') {
+            if word == "stop" {
+                break 'lines;
+            }
+        }
+        read.push(line);
+    } else {
+        outcome = "else";
+    } stopped {
+        outcome = "stopped";
+    }}
+
+    assert_eq!(outcome, "");
+    assert_eq!(read, ["a", "b"]);
+    assert_eq!(lines.next(), Some("c"));
+}
+
+#[test]
+fn test_collect() {
+    let mut tokens = "3 4 x 5".split(' ');
+    let numbers: Vec<u32> = while_let_! { collect Some(token) = tokens.next() try break {
+        emit token.parse::<u32>().ok()?;
+    } else {
+        Vec::new()
+    }};
+
+    assert_eq!(numbers, [3, 4]);
+}
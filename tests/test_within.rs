@@ -0,0 +1,128 @@
+use for_else::{for_, loop_else};
+use std::thread::sleep;
+use std::time::Duration;
+
+#[test]
+fn test_finishes_in_time() {
+    let mut seen = Vec::new();
+    let mut outcome = "";
+    for_! { n in 0..3 within(Duration::from_secs(60)) {
+        seen.push(n);
+    } else {
+        outcome = "else";
+    } timed_out {
+        outcome = "timed out";
+    }}
+
+    assert_eq!(outcome, "else");
+    assert_eq!(seen, [0, 1, 2]);
+}
+
+#[test]
+fn test_times_out_between_iterations() {
+    let mut seen = Vec::new();
+    let mut outcome = "";
+    for_! { n in 0..1000 within(Duration::from_millis(30)) {
+        seen.push(n);
+        sleep(Duration::from_millis(20));
+    } else {
+        outcome = "else";
+    } timed_out {
+        outcome = "timed out";
+    }}
+
+    assert_eq!(outcome, "timed out");
+    // the iteration running when the time is up finishes
+    assert!(seen.len() >= 2 && seen.len() < 5, "{:?}", seen);
+}
+
+#[test]
+fn test_zero_and_unbounded_budgets() {
+    let mut outcome = "";
+    for_! { n in 0..10 within(Duration::ZERO) {
+        outcome = "not run";
+        if n == 0 {
+            break;
+        }
+    } else {
+        outcome = "else";
+    } timed_out {
+        outcome = "timed out";
+    }}
+
+    assert_eq!(outcome, "timed out");
+
+    for_! { n in 0..10 within(Duration::MAX) {
+        if n == 4 {
+            outcome = "broke";
+            break;
+        }
+    } else {
+        outcome = "else";
+    } timed_out {
+        outcome = "timed out";
+    }}
+
+    assert_eq!(outcome, "broke");
+}
+
+#[test]
+fn test_with_until_and_collect() {
+    let mut outcome = "";
+    for_! { n in 0..10 until n_seen(&mut outcome) within(Duration::ZERO) {
+        let _ = n;
+    } else {
+        outcome = "else";
+    } stopped {
+        outcome = "stopped";
+    } timed_out {
+        outcome = "timed out";
+    }}
+
+    assert_eq!(outcome, "stopped");
+
+    let mut polls = 0;
+    let values = loop_else! { collect while polls < 1000 within(Duration::from_millis(20)) {
+        polls += 1;
+        sleep(Duration::from_millis(5));
+        emit polls;
+    } else {
+        Vec::new()
+    }};
+
+    assert!(!values.is_empty() && values.len() < 1000);
+}
+
+/// An `until` condition that holds right away, recording that it was asked.
+fn n_seen(outcome: &mut &str) -> bool {
+    *outcome = "asked";
+    true
+}
+
+#[test]
+fn test_while_loop_else_and_timed_out() {
+    let mut attempts = 0;
+    let mut outcome = "";
+    loop_else! { while attempts < 3 within(Duration::from_secs(60)) {
+        attempts += 1;
+    } else {
+        outcome = "else";
+    } timed_out {
+        outcome = "timed out";
+    }}
+
+    assert_eq!((attempts, outcome), (3, "else"));
+
+    attempts = 0;
+    loop_else! { while attempts < 1000 within(Duration::from_millis(30)) {
+        attempts += 1;
+        sleep(Duration::from_millis(20));
+    } else {
+        outcome = "else";
+    } timed_out {
+        outcome = "timed out";
+    }}
+
+    assert_eq!(outcome, "timed out");
+    assert!(attempts < 5, "{}", attempts);
+}
@@ -0,0 +1,65 @@
+use for_else::worklist_for_;
+
+const EDGES: [(u32, u32); 4] = [(1, 2), (1, 3), (2, 4), (5, 6)];
+
+fn reachable(start: u32, goal: u32) -> bool {
+    let mut visited = Vec::new();
+    let mut found = true;
+    worklist_for_! { node in queue = [start] {
+        if visited.contains(&node) {
+            continue;
+        }
+        visited.push(node);
+        if node == goal {
+            break;
+        }
+        for &(from, to) in &EDGES {
+            if from == node {
+                queue.push_back(to);
+            }
+        }
+    } else {
+        found = false;
+    }}
+
+    found
+}
+
+#[test]
+fn test_goal_found() {
+    assert!(reachable(1, 4));
+}
+
+#[test]
+fn test_goal_unreachable() {
+    assert!(!reachable(1, 6));
+}
+
+#[test]
+fn test_depth_first_order() {
+    let mut order = Vec::new();
+    worklist_for_! { node in queue = vec![1] {
+        order.push(node);
+        for &(from, to) in EDGES.iter().rev() {
+            if from == node {
+                queue.push_front(to);
+            }
+        }
+    } else {
+        order.push(0);
+    }}
+
+    assert_eq!(order, [1, 2, 4, 3, 0]);
+}
+
+#[test]
+fn test_empty_initial_queue() {
+    let mut flag = false;
+    worklist_for_! { _node in queue = Vec::<u32>::new() {
+        break;
+    } else {
+        flag = queue.is_empty();
+    }}
+
+    assert!(flag);
+}
@@ -0,0 +1,12 @@
+#![deny(deprecated)]
+
+use for_else::for_;
+
+fn main() {
+    for_! { n in 0..3 {
+        if n == 1 {
+            break;
+        }
+    } else {
+    }}
+}
@@ -0,0 +1,11 @@
+use for_else::for_;
+
+fn main() {
+    for_! { fold n in 0..10 {
+        if n == 3 {
+            break n;
+        }
+    } else {
+        println!("no three");
+    }}
+}
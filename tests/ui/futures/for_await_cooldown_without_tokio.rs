@@ -0,0 +1,17 @@
+use for_else::for_await_;
+use futures::stream;
+use std::time::Duration;
+
+async fn poll_replies() {
+    for_await_! { reply in stream::iter(["busy", "ready"]) cooldown(Duration::from_millis(10)) {
+        if reply == "ready" {
+            break;
+        }
+    } else {
+        println!("The worker never became ready.");
+    }}
+}
+
+fn main() {
+    drop(poll_replies());
+}
@@ -0,0 +1,21 @@
+use for_else::for_await_;
+use futures::stream::{self, StreamExt};
+use std::time::Duration;
+
+async fn first_high_reading() {
+    // the stream stalls after its second item
+    let readings = stream::iter([1, 2]).chain(stream::pending());
+    for_await_! { reading in readings within(Duration::from_millis(100)) {
+        if reading > 5 {
+            break;
+        }
+    } else {
+        println!("No high reading.");
+    } timed_out {
+        println!("No high reading in time.");
+    }}
+}
+
+fn main() {
+    drop(first_high_reading());
+}
@@ -0,0 +1,12 @@
+use for_else::loop_else;
+
+fn main() {
+    let mut n = 0;
+    loop_else! { while n < 3 {
+        n += 1;
+        if n == 2 {
+            break;
+        }
+    } els {
+    }}
+}
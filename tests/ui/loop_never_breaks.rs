@@ -0,0 +1,12 @@
+#![deny(deprecated)]
+
+use for_else::for_;
+
+fn main() {
+    let mut total = 0;
+    for_! { n in 0..3 {
+        total += n;
+    } else {
+        println!("{}", total);
+    }}
+}
@@ -0,0 +1,8 @@
+use for_else::search_;
+
+fn main() {
+    let found = search_! { n in 0..10 if n > 3 {
+        break n;
+    }};
+    println!("{:?}", found);
+}
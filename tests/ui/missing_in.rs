@@ -0,0 +1,11 @@
+use for_else::for_;
+
+fn main() {
+    for_! { n of [1, 2, 3] {
+        if n == 2 {
+            break;
+        }
+    } else {
+        println!("no break");
+    }}
+}
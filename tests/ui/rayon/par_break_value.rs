@@ -0,0 +1,11 @@
+use for_else::par_for_;
+
+fn main() {
+    par_for_! { n in 0..10 {
+        if n == 3 {
+            break n;
+        }
+    } else {
+        println!("no three");
+    }}
+}
@@ -0,0 +1,13 @@
+use for_else::par_for_;
+
+fn main() {
+    'outer: for row in 0..3 {
+        par_for_! { n in 0..10 {
+            if n == row {
+                break 'outer;
+            }
+        } else {
+            println!("no match");
+        }}
+    }
+}
@@ -0,0 +1,12 @@
+use for_else::spawn_for_;
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() {
+    spawn_for_! { n in 0..10 {
+        if n == 3 {
+            break n;
+        }
+    } else {
+        println!("no three");
+    }}
+}
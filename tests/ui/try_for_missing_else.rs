@@ -0,0 +1,9 @@
+use for_else::try_for_;
+
+fn main() {
+    try_for_! { line in ["1", "x"].map(str::parse::<u32>) {
+        println!("{}", line);
+    } catch err {
+        println!("{}", err);
+    }}
+}
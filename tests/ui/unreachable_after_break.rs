@@ -0,0 +1,14 @@
+#![deny(unreachable_code)]
+
+use for_else::for_;
+
+fn main() {
+    for_! { n in 0..3 {
+        if n == 1 {
+            break;
+            println!("after the break");
+        }
+    } else {
+        println!("no break");
+    }}
+}